@@ -1,9 +1,77 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Uint128};
 use ibcx_interface::airdrop::{AirdropType, GetAirdropResponse};
 
 use crate::error::ContractError;
 
+// checks the claim period on register
+pub fn check_period(
+    starts_at: Option<u64>,
+    expires_at: Option<u64>,
+    now: u64,
+) -> Result<(), ContractError> {
+    let Some(expires_at) = expires_at else {
+        return Ok(());
+    };
+
+    if expires_at <= now {
+        return Err(ContractError::InvalidArguments {
+            arg: "expires_at".to_string(),
+            reason: "must be in the future".to_string(),
+        });
+    }
+    if starts_at.is_some_and(|v| expires_at <= v) {
+        return Err(ContractError::InvalidArguments {
+            arg: "expires_at".to_string(),
+            reason: "must be later than starts_at".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+// claims are available from starts_at until expires_at
+pub fn assert_in_period(
+    starts_at: Option<u64>,
+    expires_at: Option<u64>,
+    now: u64,
+) -> Result<(), ContractError> {
+    if let Some(starts_at) = starts_at.filter(|v| now < *v) {
+        return Err(ContractError::AirdropNotStarted { starts_at });
+    }
+    if let Some(expires_at) = expires_at.filter(|v| *v <= now) {
+        return Err(ContractError::AirdropExpired { expires_at });
+    }
+
+    Ok(())
+}
+
+// claimants and height of the last claim of each airdrop. updated on every claim
+#[cw_serde]
+#[derive(Default)]
+pub struct ClaimStats {
+    pub claimants: u64,
+    pub last_claimed_at: Option<u64>,
+}
+
+// progress of indexing claims logged before the stats existed. covers airdrop ids below `until`.
+// claims after `last` are left to the backfill. removed when finished
+#[cw_serde]
+pub struct ClaimBackfill {
+    pub until: u64,
+    pub last: Option<(u64, String)>,
+}
+
+impl ClaimBackfill {
+    pub fn is_pending(&self, id: u64, claim_key: &str) -> bool {
+        id < self.until
+            && self
+                .last
+                .as_ref()
+                .is_none_or(|(last_id, last_key)| (id, claim_key) > (*last_id, last_key.as_str()))
+    }
+}
+
 #[cw_serde]
 pub struct OpenAirdrop {
     pub creator: Addr,
@@ -16,6 +84,9 @@ pub struct OpenAirdrop {
     pub label: Option<String>,
     pub created_at: u64,
     pub closed_at: Option<u64>,
+    pub starts_at: Option<u64>,
+    pub expires_at: Option<u64>,
+    pub beneficiary: Option<Addr>,
 }
 
 impl From<OpenAirdrop> for Airdrop {
@@ -38,6 +109,9 @@ pub struct BearerAirdrop {
     pub label: Option<String>,
     pub created_at: u64,
     pub closed_at: Option<u64>,
+    pub starts_at: Option<u64>,
+    pub expires_at: Option<u64>,
+    pub beneficiary: Option<Addr>,
 }
 
 impl From<BearerAirdrop> for Airdrop {
@@ -46,11 +120,170 @@ impl From<BearerAirdrop> for Airdrop {
     }
 }
 
+#[cw_serde]
+pub struct VestingAirdrop {
+    pub creator: Addr,
+
+    pub denom: String,
+    pub total_amount: Uint128,
+    pub total_claimed: Uint128,
+    pub merkle_root: String,
+
+    pub start_time: u64,
+    pub end_time: u64,
+    pub cliff_time: Option<u64>,
+
+    pub label: Option<String>,
+    pub created_at: u64,
+    pub closed_at: Option<u64>,
+    pub starts_at: Option<u64>,
+    pub expires_at: Option<u64>,
+    pub beneficiary: Option<Addr>,
+}
+
+impl VestingAirdrop {
+    // linearly unlocked amount of the allocation. nothing is unlocked before the cliff
+    pub fn unlocked(&self, allocation: Uint128, now: u64) -> Result<Uint128, ContractError> {
+        if now < self.cliff_time.unwrap_or(self.start_time) {
+            return Ok(Uint128::zero());
+        }
+        if self.end_time <= now {
+            return Ok(allocation);
+        }
+
+        Ok(allocation
+            .checked_multiply_ratio(now - self.start_time, self.end_time - self.start_time)?)
+    }
+}
+
+impl From<VestingAirdrop> for Airdrop {
+    fn from(val: VestingAirdrop) -> Self {
+        Airdrop::Vesting(val)
+    }
+}
+
+// sorts coins by denom. rejects empty coins, duplicated denoms and zero amounts
+pub fn normalize_coins(arg: &str, mut coins: Vec<Coin>) -> Result<Vec<Coin>, ContractError> {
+    coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    if coins.is_empty() {
+        return Err(ContractError::InvalidArguments {
+            arg: arg.to_string(),
+            reason: "must not be empty".to_string(),
+        });
+    }
+    if coins.iter().any(|c| c.amount.is_zero()) {
+        return Err(ContractError::InvalidArguments {
+            arg: arg.to_string(),
+            reason: "must not contain zero amount".to_string(),
+        });
+    }
+    if coins.windows(2).any(|w| w[0].denom == w[1].denom) {
+        return Err(ContractError::InvalidArguments {
+            arg: arg.to_string(),
+            reason: "must not contain duplicated denoms".to_string(),
+        });
+    }
+
+    Ok(coins)
+}
+
+pub fn coins_to_string(coins: &[Coin]) -> String {
+    coins
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cw_serde]
+pub struct MultiDenomAirdrop {
+    pub creator: Addr,
+
+    // sorted by denom
+    pub total_amount: Vec<Coin>,
+    pub total_claimed: Vec<Coin>,
+    pub merkle_root: String,
+
+    pub label: Option<String>,
+    pub created_at: u64,
+    pub closed_at: Option<u64>,
+    pub starts_at: Option<u64>,
+    pub expires_at: Option<u64>,
+    pub beneficiary: Option<Addr>,
+}
+
+fn add_coins(coins: &mut Vec<Coin>, add: &[Coin]) -> Result<(), ContractError> {
+    for coin in add {
+        match coins.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(origin) => origin.amount = origin.amount.checked_add(coin.amount)?,
+            None => coins.push(coin.clone()),
+        }
+    }
+    coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    Ok(())
+}
+
+impl MultiDenomAirdrop {
+    pub fn fund(&mut self, funds: &[Coin]) -> Result<(), ContractError> {
+        add_coins(&mut self.total_amount, funds)
+    }
+
+    pub fn claim(&mut self, amount: &[Coin]) -> Result<(), ContractError> {
+        add_coins(&mut self.total_claimed, amount)?;
+
+        for claimed in self.total_claimed.iter() {
+            let total = self
+                .total_amount
+                .iter()
+                .find(|c| c.denom == claimed.denom)
+                .map(|c| c.amount)
+                .unwrap_or_default();
+            if total < claimed.amount {
+                return Err(ContractError::InsufficientAirdropFunds {});
+            }
+        }
+
+        Ok(())
+    }
+
+    // funds left for each denom. zero amounts are omitted
+    pub fn unclaimed(&self) -> Result<Vec<Coin>, ContractError> {
+        let mut unclaimed = vec![];
+        for total in self.total_amount.iter() {
+            let claimed = self
+                .total_claimed
+                .iter()
+                .find(|c| c.denom == total.denom)
+                .map(|c| c.amount)
+                .unwrap_or_default();
+
+            let amount = total.amount.checked_sub(claimed)?;
+            if !amount.is_zero() {
+                unclaimed.push(Coin::new(amount.u128(), &total.denom));
+            }
+        }
+
+        Ok(unclaimed)
+    }
+}
+
+impl From<MultiDenomAirdrop> for Airdrop {
+    fn from(val: MultiDenomAirdrop) -> Self {
+        Airdrop::MultiDenom(val)
+    }
+}
+
 #[cw_serde]
 pub enum Airdrop {
     Open(OpenAirdrop),
 
     Bearer(BearerAirdrop),
+
+    Vesting(VestingAirdrop),
+
+    MultiDenom(MultiDenomAirdrop),
 }
 
 impl Airdrop {
@@ -58,6 +291,8 @@ impl Airdrop {
         match self {
             Airdrop::Open { .. } => AirdropType::Open,
             Airdrop::Bearer { .. } => AirdropType::Bearer,
+            Airdrop::Vesting { .. } => AirdropType::Vesting,
+            Airdrop::MultiDenom { .. } => AirdropType::MultiDenom,
         }
     }
 
@@ -65,6 +300,8 @@ impl Airdrop {
         match self {
             Airdrop::Open { .. } => "open",
             Airdrop::Bearer { .. } => "bearer",
+            Airdrop::Vesting { .. } => "vesting",
+            Airdrop::MultiDenom { .. } => "multi_denom",
         }
     }
 
@@ -85,6 +322,97 @@ impl Airdrop {
         }
     }
 
+    pub fn unwrap_vesting(self) -> Result<VestingAirdrop, ContractError> {
+        match self {
+            Airdrop::Vesting(airdrop) => Ok(airdrop),
+            _ => Err(ContractError::invalid_airdrop_type(
+                "vesting",
+                self.type_str(),
+            )),
+        }
+    }
+
+    pub fn unwrap_multi_denom(self) -> Result<MultiDenomAirdrop, ContractError> {
+        match self {
+            Airdrop::MultiDenom(airdrop) => Ok(airdrop),
+            _ => Err(ContractError::invalid_airdrop_type(
+                "multi_denom",
+                self.type_str(),
+            )),
+        }
+    }
+
+    pub fn creator(&self) -> &Addr {
+        match self {
+            Airdrop::Open(inner) => &inner.creator,
+            Airdrop::Bearer(inner) => &inner.creator,
+            Airdrop::Vesting(inner) => &inner.creator,
+            Airdrop::MultiDenom(inner) => &inner.creator,
+        }
+    }
+
+    pub fn closed_at(&self) -> Option<u64> {
+        match self {
+            Airdrop::Open(inner) => inner.closed_at,
+            Airdrop::Bearer(inner) => inner.closed_at,
+            Airdrop::Vesting(inner) => inner.closed_at,
+            Airdrop::MultiDenom(inner) => inner.closed_at,
+        }
+    }
+
+    pub fn expires_at(&self) -> Option<u64> {
+        match self {
+            Airdrop::Open(inner) => inner.expires_at,
+            Airdrop::Bearer(inner) => inner.expires_at,
+            Airdrop::Vesting(inner) => inner.expires_at,
+            Airdrop::MultiDenom(inner) => inner.expires_at,
+        }
+    }
+
+    // receives unclaimed funds on sweep. falls back to the creator
+    pub fn beneficiary(&self) -> &Addr {
+        let beneficiary = match self {
+            Airdrop::Open(inner) => inner.beneficiary.as_ref(),
+            Airdrop::Bearer(inner) => inner.beneficiary.as_ref(),
+            Airdrop::Vesting(inner) => inner.beneficiary.as_ref(),
+            Airdrop::MultiDenom(inner) => inner.beneficiary.as_ref(),
+        };
+
+        beneficiary.unwrap_or(self.creator())
+    }
+
+    // marks the airdrop closed at the height and returns the unclaimed funds.
+    // locked allocations of vesting airdrops are also included
+    pub fn close(&mut self, height: u64) -> Result<Vec<Coin>, ContractError> {
+        let single = |denom: &str, total: Uint128, claimed: Uint128| {
+            let amount = total.checked_sub(claimed)?;
+            let coins = match amount.is_zero() {
+                true => vec![],
+                false => vec![Coin::new(amount.u128(), denom)],
+            };
+            Ok::<_, ContractError>(coins)
+        };
+
+        match self {
+            Airdrop::Open(inner) => {
+                inner.closed_at = Some(height);
+                single(&inner.denom, inner.total_amount, inner.total_claimed)
+            }
+            Airdrop::Bearer(inner) => {
+                inner.closed_at = Some(height);
+                single(&inner.denom, inner.total_amount, inner.total_claimed)
+            }
+            Airdrop::Vesting(inner) => {
+                inner.closed_at = Some(height);
+                single(&inner.denom, inner.total_amount, inner.total_claimed)
+            }
+            Airdrop::MultiDenom(inner) => {
+                inner.closed_at = Some(height);
+                inner.unclaimed()
+            }
+        }
+    }
+
     pub fn to_resp((id, airdrop): (u64, Self)) -> GetAirdropResponse {
         match airdrop {
             Self::Open(inner) => GetAirdropResponse::Open {
@@ -97,6 +425,9 @@ impl Airdrop {
                 label: inner.label,
                 created_at: inner.created_at,
                 closed_at: inner.closed_at,
+                starts_at: inner.starts_at,
+                expires_at: inner.expires_at,
+                beneficiary: inner.beneficiary.map(|v| v.to_string()),
             },
             Self::Bearer(inner) => GetAirdropResponse::Bearer {
                 id,
@@ -111,6 +442,41 @@ impl Airdrop {
                 label: inner.label,
                 created_at: inner.created_at,
                 closed_at: inner.closed_at,
+                starts_at: inner.starts_at,
+                expires_at: inner.expires_at,
+                beneficiary: inner.beneficiary.map(|v| v.to_string()),
+            },
+            Self::Vesting(inner) => GetAirdropResponse::Vesting {
+                id,
+                creator: inner.creator.to_string(),
+
+                denom: inner.denom,
+                total_amount: inner.total_amount,
+                total_claimed: inner.total_claimed,
+                merkle_root: inner.merkle_root,
+                start_time: inner.start_time,
+                end_time: inner.end_time,
+                cliff_time: inner.cliff_time,
+                label: inner.label,
+                created_at: inner.created_at,
+                closed_at: inner.closed_at,
+                starts_at: inner.starts_at,
+                expires_at: inner.expires_at,
+                beneficiary: inner.beneficiary.map(|v| v.to_string()),
+            },
+            Self::MultiDenom(inner) => GetAirdropResponse::MultiDenom {
+                id,
+                creator: inner.creator.to_string(),
+
+                total_amount: inner.total_amount,
+                total_claimed: inner.total_claimed,
+                merkle_root: inner.merkle_root,
+                label: inner.label,
+                created_at: inner.created_at,
+                closed_at: inner.closed_at,
+                starts_at: inner.starts_at,
+                expires_at: inner.expires_at,
+                beneficiary: inner.beneficiary.map(|v| v.to_string()),
             },
         }
     }
@@ -3,7 +3,11 @@ use cosmwasm_std::{entry_point, Env, MessageInfo, QueryResponse};
 use cosmwasm_std::{Deps, DepsMut, Response};
 use ibcx_interface::airdrop::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
-use crate::{error::ContractError, state::LATEST_AIRDROP_ID, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::{
+    error::ContractError,
+    state::{start_claim_backfill, LATEST_AIRDROP_ID},
+    CONTRACT_NAME, CONTRACT_VERSION,
+};
 
 #[entry_point]
 pub fn instantiate(
@@ -34,9 +38,35 @@ pub fn execute(
 
         Fund(airdrop) => execute::fund(deps, info, airdrop),
 
-        Claim(payload) => execute::claim(deps, info, payload),
+        Claim(payload) => execute::claim(deps, env, info, payload),
+
+        ClaimMany { claims, mode } => {
+            execute::claim_many(deps, env, info, claims, mode.unwrap_or_default())
+        }
+
+        ClaimAndMint { claim, mint } => execute::claim_and_mint(deps, env, info, claim, mint),
 
         Close(airdrop) => execute::close(deps, env, info, airdrop),
+
+        UpdateRoot {
+            airdrop,
+            merkle_root,
+        } => execute::update_root(deps, info, airdrop, merkle_root),
+
+        RotateSigner {
+            airdrop,
+            signer_pub,
+            signer_sig,
+        } => execute::rotate_signer(deps, info, airdrop, signer_pub, signer_sig),
+
+        RevokeClaims {
+            airdrop,
+            claim_hashes,
+        } => execute::revoke_claims(deps, env, info, airdrop, claim_hashes),
+
+        Sweep(airdrop) => execute::sweep(deps, env, info, airdrop),
+
+        BackfillClaims { limit } => execute::backfill_claims(deps, info, limit),
     }
 }
 
@@ -48,7 +78,7 @@ fn to_binary<T: Serialize>(res: Result<T, ContractError>) -> Result<QueryRespons
 }
 
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
     use crate::query;
     use QueryMsg::*;
 
@@ -57,14 +87,38 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<QueryResponse, Cont
         ListAirdrops { option } => to_binary(query::list_airdrops(deps, option)),
         LatestAirdropId {} => to_binary(query::latest_airdrop_id(deps)),
 
-        GetClaim { airdrop, claim_key } => to_binary(query::get_claim(deps, airdrop, claim_key)),
-        VerifyClaim { claim: payload } => to_binary(query::verify_claim(deps, payload)),
+        GetClaim { airdrop, claim_key } => {
+            to_binary(query::get_claim(deps, env, airdrop, claim_key))
+        }
+        VerifyClaim { claim: payload } => to_binary(query::verify_claim(deps, env, payload)),
         ListClaims {
             airdrop,
             start_after,
             limit,
             order,
-        } => to_binary(query::list_claims(deps, airdrop, start_after, limit, order)),
+        } => to_binary(query::list_claims(
+            deps,
+            env,
+            airdrop,
+            start_after,
+            limit,
+            order,
+        )),
+
+        ListClaimsByAccount {
+            account,
+            start_after,
+            limit,
+            order,
+        } => to_binary(query::list_claims_by_account(
+            deps,
+            env,
+            account,
+            start_after,
+            limit,
+            order,
+        )),
+        GetAirdropStats { airdrop } => to_binary(query::get_airdrop_stats(deps, airdrop)),
 
         GetLabel { label } => to_binary(query::get_label(deps, label)),
         ListLabels {
@@ -83,7 +137,10 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     }
 
-    Ok(Default::default())
+    // claims logged by the previous version are indexed through `BackfillClaims`
+    let backfill = start_claim_backfill(deps.storage)?;
+
+    Ok(Response::new().add_attribute("claim_backfill", backfill.to_string()))
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -18,6 +18,9 @@ pub enum ContractError {
     #[error("{0}")]
     CheckedMultiplyRatioError(#[from] cosmwasm_std::CheckedMultiplyRatioError),
 
+    #[error("{0}")]
+    CheckedMultiplyFractionError(#[from] cosmwasm_std::CheckedMultiplyFractionError),
+
     #[error("{0}")]
     ParseReplyError(#[from] cw_utils::ParseReplyError),
 
@@ -36,6 +39,9 @@ pub enum ContractError {
     #[error("Already claimed. id:{airdrop_id:?}, claim_key:{claim_key:?}")]
     AlreadyClaimed { airdrop_id: u64, claim_key: String },
 
+    #[error("Claim revoked. id:{airdrop_id:?}, claim_hash:{claim_hash:?}")]
+    ClaimRevoked { airdrop_id: u64, claim_hash: String },
+
     #[error("Wrong length")]
     WrongLength {},
 
@@ -45,12 +51,33 @@ pub enum ContractError {
     #[error("Airdrop has closed")]
     AirdropClosed {},
 
+    #[error("Airdrop has not started. starts_at:{starts_at:?}")]
+    AirdropNotStarted { starts_at: u64 },
+
+    #[error("Airdrop has expired. expires_at:{expires_at:?}")]
+    AirdropExpired { expires_at: u64 },
+
+    #[error("Airdrop has not expired")]
+    AirdropNotExpired {},
+
+    #[error("No claim backfill in progress")]
+    NoClaimBackfill {},
+
     #[error("Invalid arguments. arg:{arg:?}, reason:{reason:?}")]
     InvalidArguments { arg: String, reason: String },
 
     #[error("Unabled to claim more than supplied funds.")]
     InsufficientAirdropFunds {},
 
+    #[error("Insufficient mint output. min_output:{min_output:?}, output:{output:?}")]
+    InsufficientMintOutput {
+        min_output: Uint128,
+        output: Uint128,
+    },
+
+    #[error("Nothing to claim. id:{airdrop_id:?}, claim_key:{claim_key:?}")]
+    NothingToClaim { airdrop_id: u64, claim_key: String },
+
     #[error("Invalid airdrop type. expected:{expected:?}, actual:{actual:?}")]
     InvalidAirdropType { expected: String, actual: String },
 
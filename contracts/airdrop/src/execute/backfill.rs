@@ -0,0 +1,224 @@
+use crate::airdrop::Airdrop;
+use crate::error::ContractError;
+use crate::state::{airdrops, backfill_claim, CLAIM_BACKFILL, CLAIM_LOGS, MULTI_DENOM_CLAIM_LOGS};
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Order, Response, StdResult};
+use cw_storage_plus::Bound;
+use ibcx_interface::{get_and_check_limit, DEFAULT_LIMIT, MAX_LIMIT};
+
+// indexes the next page of claims logged before the stats existed. anyone can execute
+pub fn backfill_claims(
+    deps: DepsMut,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let Some(mut backfill) = CLAIM_BACKFILL.may_load(deps.storage)? else {
+        return Err(ContractError::NoClaimBackfill {});
+    };
+    let limit = get_and_check_limit(limit, MAX_LIMIT, DEFAULT_LIMIT)? as usize;
+
+    let mut next_id = backfill
+        .last
+        .as_ref()
+        .map(|(id, _)| *id)
+        .unwrap_or_default();
+    let mut processed = 0;
+    let mut done = false;
+
+    while processed < limit {
+        let airdrop = airdrops()
+            .range(
+                deps.storage,
+                Some(Bound::inclusive(next_id)),
+                Some(Bound::exclusive(backfill.until)),
+                Order::Ascending,
+            )
+            .next()
+            .transpose()?;
+        let Some((id, airdrop)) = airdrop else {
+            done = true;
+            break;
+        };
+
+        let start = backfill
+            .last
+            .as_ref()
+            .filter(|(last_id, _)| *last_id == id)
+            .map(|(_, last_key)| Bound::exclusive(last_key.as_str()));
+        let claim_keys = match airdrop {
+            Airdrop::MultiDenom(_) => MULTI_DENOM_CLAIM_LOGS
+                .prefix(id)
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(limit - processed)
+                .collect::<StdResult<Vec<_>>>()?,
+            _ => CLAIM_LOGS
+                .prefix(id)
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(limit - processed)
+                .collect::<StdResult<Vec<_>>>()?,
+        };
+
+        // claim keys of the other airdrop types are the claimers
+        let indexed = !matches!(airdrop, Airdrop::Bearer(_));
+        for claim_key in claim_keys {
+            let claimer = indexed.then_some(claim_key.as_str());
+            backfill_claim(deps.storage, id, &claim_key, claimer)?;
+            backfill.last = Some((id, claim_key));
+            processed += 1;
+        }
+
+        next_id = id + 1;
+    }
+
+    if done {
+        CLAIM_BACKFILL.remove(deps.storage);
+    } else {
+        CLAIM_BACKFILL.save(deps.storage, &backfill)?;
+    }
+
+    // response
+    let attrs = vec![
+        attr("method", "backfill_claims"),
+        attr("executor", info.sender),
+        attr("processed", processed.to_string()),
+        attr("done", done.to_string()),
+    ];
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coins,
+        testing::{mock_dependencies, mock_env, mock_info},
+        Order, Uint128,
+    };
+    use ibcx_interface::airdrop::InstantiateMsg;
+
+    use crate::{
+        airdrop::ClaimStats,
+        contract::instantiate,
+        error::ContractError,
+        execute::tests::{
+            mock_bearer_airdrop, mock_multi_denom_airdrop, mock_open_airdrop, register_airdrop,
+        },
+        state::{
+            record_claim, start_claim_backfill, CLAIMS_BY_ACCOUNT, CLAIM_BACKFILL, CLAIM_LOGS,
+            CLAIM_STATS, MULTI_DENOM_CLAIM_LOGS,
+        },
+    };
+
+    use super::backfill_claims;
+
+    #[test]
+    fn test_backfill_claims() {
+        let env = mock_env();
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        // 0: open, 1: bearer, 2: multi-denom
+        let open = mock_open_airdrop(None, env.block.height);
+        register_airdrop(deps.as_mut(), env.clone(), open.into(), None);
+        let (bearer, bearer_sign) = mock_bearer_airdrop(None, env.block.height);
+        register_airdrop(deps.as_mut(), env.clone(), bearer.into(), Some(bearer_sign));
+        let multi_denom = mock_multi_denom_airdrop(None, env.block.height);
+        register_airdrop(deps.as_mut(), env.clone(), multi_denom.into(), None);
+
+        // claims logged by the previous version
+        let storage = deps.as_mut().storage;
+        for (id, claim_key) in [(0, "alice"), (0, "bob"), (0, "carol"), (1, "h1"), (1, "h2")] {
+            CLAIM_LOGS
+                .save(storage, (id, claim_key), &Uint128::new(1))
+                .unwrap();
+        }
+        MULTI_DENOM_CLAIM_LOGS
+            .save(storage, (2, "alice"), &coins(1, "uosmo"))
+            .unwrap();
+
+        assert!(start_claim_backfill(storage).unwrap());
+        assert!(!start_claim_backfill(storage).unwrap());
+
+        // claim after the cursor is left to the backfill
+        CLAIM_LOGS
+            .save(storage, (0, "dave"), &Uint128::new(1))
+            .unwrap();
+        record_claim(storage, env.block.height, 0, "dave", "dave").unwrap();
+        assert_eq!(
+            CLAIM_STATS.load(storage, 0).unwrap(),
+            ClaimStats {
+                claimants: 0,
+                last_claimed_at: Some(env.block.height),
+            }
+        );
+
+        let res = backfill_claims(deps.as_mut(), mock_info("keeper", &[]), Some(2)).unwrap();
+        assert_eq!(res.attributes[2].value, "2");
+        assert_eq!(res.attributes[3].value, "false");
+
+        // claim before the cursor is counted right away
+        let storage = deps.as_mut().storage;
+        CLAIM_LOGS
+            .save(storage, (0, "aaron"), &Uint128::new(1))
+            .unwrap();
+        record_claim(storage, env.block.height, 0, "aaron", "aaron").unwrap();
+        assert_eq!(CLAIM_STATS.load(storage, 0).unwrap().claimants, 3);
+
+        // bearer claim after the cursor
+        CLAIM_LOGS
+            .save(storage, (1, "h0"), &Uint128::new(1))
+            .unwrap();
+        record_claim(storage, env.block.height, 1, "h0", "eve").unwrap();
+
+        // airdrop registered after the upgrade is out of the backfill
+        let open = mock_open_airdrop(None, env.block.height);
+        register_airdrop(deps.as_mut(), env.clone(), open.into(), None);
+        let storage = deps.as_mut().storage;
+        CLAIM_LOGS
+            .save(storage, (3, "alice"), &Uint128::new(1))
+            .unwrap();
+        record_claim(storage, env.block.height, 3, "alice", "alice").unwrap();
+
+        let res = backfill_claims(deps.as_mut(), mock_info("keeper", &[]), Some(30)).unwrap();
+        assert_eq!(res.attributes[2].value, "6");
+        assert_eq!(res.attributes[3].value, "true");
+
+        let storage = deps.as_ref().storage;
+        assert!(!CLAIM_BACKFILL.exists(storage));
+
+        let claimants = |id| CLAIM_STATS.load(storage, id).unwrap().claimants;
+        assert_eq!(claimants(0), 5);
+        assert_eq!(claimants(1), 3);
+        assert_eq!(claimants(2), 1);
+        assert_eq!(claimants(3), 1);
+        assert_eq!(CLAIM_STATS.load(storage, 2).unwrap().last_claimed_at, None);
+
+        // claimers of bearer airdrops are not logged
+        let claims = |account| {
+            CLAIMS_BY_ACCOUNT
+                .prefix(account)
+                .range(storage, None, None, Order::Ascending)
+                .map(|v| v.unwrap().0)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            claims("alice"),
+            vec![
+                (0, "alice".to_string()),
+                (2, "alice".to_string()),
+                (3, "alice".to_string())
+            ]
+        );
+        assert_eq!(claims("eve"), vec![(1, "h0".to_string())]);
+        assert!(!CLAIMS_BY_ACCOUNT.has(storage, ("h1", (1, "h1"))));
+
+        let err = backfill_claims(deps.as_mut(), mock_info("keeper", &[]), None).unwrap_err();
+        assert!(matches!(err, ContractError::NoClaimBackfill {}));
+    }
+}
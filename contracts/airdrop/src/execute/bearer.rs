@@ -0,0 +1,319 @@
+use crate::error::ContractError;
+use crate::state::{airdrops, assert_not_claimed, load_airdrop, REVOKED_CLAIMS};
+use crate::verify::verify_signer;
+use cosmwasm_std::{attr, Binary, DepsMut, Env, MessageInfo, Response};
+use ibcx_interface::airdrop::{AirdropId, AirdropType};
+
+pub fn rotate_signer(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: AirdropId,
+    signer_pub: String,
+    signer_sig: String,
+) -> Result<Response, ContractError> {
+    let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
+
+    // validation
+    let mut airdrop = airdrop.unwrap_bearer()?;
+    if airdrop.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if airdrop.closed_at.is_some() {
+        return Err(ContractError::AirdropClosed {});
+    }
+
+    // verify signer_sig
+    let signer_pub = hex::decode(signer_pub)?;
+    let signer_sig = hex::decode(signer_sig)?;
+    let signer = verify_signer(deps.api, "rotate_signer", &signer_pub, &signer_sig)?;
+
+    let prev_signer = airdrop.signer;
+    airdrop.signer = deps.api.addr_validate(&signer)?;
+    airdrop.signer_pub = Binary::from(signer_pub);
+
+    // response
+    let attrs = vec![
+        attr("method", "rotate_signer"),
+        attr("executor", info.sender),
+        attr("airdrop_type", AirdropType::Bearer),
+        attr("airdrop_id", airdrop_id.to_string()),
+        attr("prev_signer", prev_signer),
+        attr("signer", &airdrop.signer),
+    ];
+
+    // apply states
+    airdrops().save(deps.storage, airdrop_id, &airdrop.into())?;
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+pub fn revoke_claims(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: AirdropId,
+    claim_hashes: Vec<String>,
+) -> Result<Response, ContractError> {
+    let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
+
+    // validation
+    let airdrop = airdrop.unwrap_bearer()?;
+    if airdrop.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if airdrop.closed_at.is_some() {
+        return Err(ContractError::AirdropClosed {});
+    }
+    if claim_hashes.is_empty() {
+        return Err(ContractError::InvalidArguments {
+            arg: "claim_hashes".to_string(),
+            reason: "must not be empty".to_string(),
+        });
+    }
+
+    // apply states
+    for claim_hash in &claim_hashes {
+        assert_not_claimed(deps.storage, airdrop_id, claim_hash)?;
+        if REVOKED_CLAIMS.has(deps.storage, (airdrop_id, claim_hash)) {
+            return Err(ContractError::ClaimRevoked {
+                airdrop_id,
+                claim_hash: claim_hash.clone(),
+            });
+        }
+
+        REVOKED_CLAIMS.save(deps.storage, (airdrop_id, claim_hash), &env.block.height)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("method", "revoke_claims"),
+        attr("executor", info.sender),
+        attr("airdrop_type", AirdropType::Bearer),
+        attr("airdrop_id", airdrop_id.to_string()),
+        attr("revoked", claim_hashes.join(",")),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+        Addr, OwnedDeps,
+    };
+    use ibcx_interface::airdrop::{AirdropId, ClaimPayload, InstantiateMsg};
+
+    use crate::{
+        contract::instantiate,
+        error::ContractError,
+        execute::{
+            claim,
+            tests::{mock_bearer_airdrop, mock_open_airdrop, register_airdrop},
+        },
+        query::verify_claim,
+        state::airdrops,
+    };
+
+    use super::{revoke_claims, rotate_signer};
+
+    const CLAIMER: &str = "osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a";
+    const CLAIM_HASH: &str = "c2ae4a162c574a85c596cfda7c057c5aa15c7b4d6a5679f44fb365d76e63e24e";
+    const MERKLE_PROOF: &[&str] = &[
+        "7673db0bbf54220efc87bf8f57b5bc0fa489fe4508fa6051ed938ccbb11f5e7b",
+        "f4e567b6b15b97e312757c1a2bd5273a14f4300b4e5d3df8980f7a3d400e90ce",
+        "3591dea5b253198a4ba4bbbc440a1fb955967a11cce4abe442f8867c224bff19",
+        "2a301d9535b68c35e0ae38108085966fb8657b396c92df1b86d7e07c183436a6",
+    ];
+
+    // signed by osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks (registered signer)
+    const CLAIM_SIGN: &str = "418e71e063fa630855ca431a6cf1b3ba7d7ef88364f5e24de6da1068071521542cdb061b66ec8a75c534720354f2347d64e3e0e75ca24edb0766f7b9336a3694";
+
+    // key of claim_open_on_behalf test
+    const NEW_SIGNER: &str = "osmo15wgtkntdf26hqan77g0kdsldcxjddypx5ny5a6";
+    const NEW_SIGNER_PUB: &str =
+        "02bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020d";
+    const NEW_SIGNER_SIG: &str = "36fc455a05e57f9b31bb97be33aa44a0a9314b9df3bb6127e1fa087f705d743b5f39ccd541a99c72642c9f0bcd79be04c240e5040a255f3c9b855487bc2cfeb1";
+    // signed by the new signer
+    const NEW_CLAIM_SIGN: &str = "04a1a32cf09f347496aa7a09e940dfa2b6d7e8b87fb401fad4025e97b71b41dd0a0ec1477b7874d21681d95208aefd4549a019510575106d7f97a6f6736a1d89";
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let env = mock_env();
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        let (airdrop, sign) = mock_bearer_airdrop(None, env.block.height);
+        register_airdrop(deps.as_mut(), env.clone(), airdrop.into(), Some(sign));
+        register_airdrop(
+            deps.as_mut(),
+            env.clone(),
+            mock_open_airdrop(None, env.block.height).into(),
+            None,
+        );
+
+        deps
+    }
+
+    fn bearer_claim(claim_sign: &str) -> ClaimPayload {
+        ClaimPayload::bearer_id(
+            0,
+            10000,
+            Some(CLAIMER),
+            CLAIM_HASH,
+            claim_sign,
+            MERKLE_PROOF,
+        )
+    }
+
+    #[test]
+    fn test_rotate_signer() {
+        let mut deps = setup();
+
+        let cases = [
+            (
+                "anyone",
+                0,
+                NEW_SIGNER_SIG,
+                Some(ContractError::Unauthorized {}),
+            ),
+            (
+                "tester",
+                1,
+                NEW_SIGNER_SIG,
+                Some(ContractError::invalid_airdrop_type("bearer", "open")),
+            ),
+            (
+                "tester",
+                0,
+                CLAIM_SIGN,
+                Some(ContractError::invalid_signature("rotate_signer")),
+            ),
+            ("tester", 0, NEW_SIGNER_SIG, None),
+        ];
+        for (sender, id, signer_sig, expect_err) in cases {
+            let resp = rotate_signer(
+                deps.as_mut(),
+                mock_info(sender, &[]),
+                AirdropId::id(id),
+                NEW_SIGNER_PUB.to_string(),
+                signer_sig.to_string(),
+            );
+            match expect_err {
+                Some(err) => assert_eq!(resp.unwrap_err(), err),
+                None => {
+                    resp.unwrap();
+                }
+            }
+        }
+
+        let airdrop = airdrops().load(&deps.storage, 0).unwrap().unwrap_bearer();
+        assert_eq!(airdrop.unwrap().signer, Addr::unchecked(NEW_SIGNER));
+
+        // signature of the previous signer is no longer valid
+        let err = claim(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CLAIMER, &[]),
+            bearer_claim(CLAIM_SIGN),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::invalid_signature("claim_bearer"));
+
+        claim(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CLAIMER, &[]),
+            bearer_claim(NEW_CLAIM_SIGN),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_revoke_claims() {
+        let mut deps = setup();
+
+        let resp = verify_claim(deps.as_ref(), mock_env(), bearer_claim(CLAIM_SIGN)).unwrap();
+        assert!(resp.valid);
+
+        let err = revoke_claims(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            AirdropId::id(0),
+            vec![CLAIM_HASH.to_string()],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        revoke_claims(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("tester", &[]),
+            AirdropId::id(0),
+            vec![CLAIM_HASH.to_string()],
+        )
+        .unwrap();
+
+        let revoked = ContractError::ClaimRevoked {
+            airdrop_id: 0,
+            claim_hash: CLAIM_HASH.to_string(),
+        };
+
+        let err = revoke_claims(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("tester", &[]),
+            AirdropId::id(0),
+            vec![CLAIM_HASH.to_string()],
+        )
+        .unwrap_err();
+        assert_eq!(err, revoked);
+
+        let err = claim(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CLAIMER, &[]),
+            bearer_claim(CLAIM_SIGN),
+        )
+        .unwrap_err();
+        assert_eq!(err, revoked);
+
+        let resp = verify_claim(deps.as_ref(), mock_env(), bearer_claim(CLAIM_SIGN)).unwrap();
+        assert!(!resp.valid);
+        assert!(resp.revoked);
+        assert_eq!(resp.reason, Some("claim hash is revoked".to_string()));
+    }
+
+    #[test]
+    fn test_revoke_claimed() {
+        let mut deps = setup();
+
+        claim(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CLAIMER, &[]),
+            bearer_claim(CLAIM_SIGN),
+        )
+        .unwrap();
+
+        let err = revoke_claims(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("tester", &[]),
+            AirdropId::id(0),
+            vec![CLAIM_HASH.to_string()],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::AlreadyClaimed {
+                airdrop_id: 0,
+                claim_key: CLAIM_HASH.to_string(),
+            }
+        );
+    }
+}
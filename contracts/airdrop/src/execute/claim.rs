@@ -1,13 +1,21 @@
+use crate::airdrop::{assert_in_period, coins_to_string, normalize_coins};
 use crate::error::ContractError;
-use crate::state::{airdrops, assert_not_claimed, load_airdrop, CLAIM_LOGS};
-use crate::verify::{sha256_digest, verify_merkle_proof};
+use crate::state::{
+    airdrops, assert_not_claimed, assert_not_revoked, load_airdrop, record_claim, CLAIM_LOGS,
+    MULTI_DENOM_CLAIM_LOGS, VESTING_ALLOCATIONS,
+};
+use crate::verify::{
+    sha256_digest, verify_delegation, verify_merkle_proof, verify_multi_denom_merkle_proof,
+};
 use cosmwasm_std::{
-    attr, coins, Addr, Attribute, BankMsg, DepsMut, MessageInfo, Response, Uint128,
+    attr, coins, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg, DepsMut, Env, MessageInfo,
+    Response, StdError, Uint128,
 };
 use ibcx_interface::airdrop::{AirdropId, AirdropType, ClaimPayload};
 
 pub fn claim(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     payload: ClaimPayload,
 ) -> Result<Response, ContractError> {
@@ -24,7 +32,35 @@ pub fn claim(
                 .transpose()?
                 .unwrap_or_else(|| info.sender.clone());
 
-            claim_open(deps, info.sender, id, amount, claimer, proof)
+            claim_open(deps, env, info.sender, id, amount, claimer, None, proof)
+        }
+
+        ClaimPayload::OpenOnBehalf {
+            airdrop: id,
+            amount,
+            account,
+            recipient,
+            account_pub,
+            recipient_sig,
+            merkle_proof: proof,
+        } => {
+            let claimer = deps.api.addr_validate(&account)?;
+            let delegation = Delegation {
+                recipient: deps.api.addr_validate(&recipient)?,
+                account_pub: hex::decode(account_pub)?.into(),
+                recipient_sig: hex::decode(recipient_sig)?.into(),
+            };
+
+            claim_open(
+                deps,
+                env,
+                info.sender,
+                id,
+                amount,
+                claimer,
+                Some(delegation),
+                proof,
+            )
         }
 
         ClaimPayload::Bearer {
@@ -41,8 +77,64 @@ pub fn claim(
                 .transpose()?
                 .unwrap_or_else(|| info.sender.clone());
 
-            claim_bearer(deps, info.sender, id, amount, claimer, hash, sign, proof)
+            claim_bearer(
+                deps,
+                env,
+                info.sender,
+                id,
+                amount,
+                claimer,
+                hash,
+                sign,
+                proof,
+            )
+        }
+
+        ClaimPayload::Vesting {
+            airdrop: id,
+            amount,
+            account,
+            merkle_proof: proof,
+        } => {
+            // use tx sender if account is not provided
+            let claimer = account
+                .map(|x| deps.api.addr_validate(&x))
+                .transpose()?
+                .unwrap_or_else(|| info.sender.clone());
+
+            claim_vesting(deps, env, info.sender, id, amount, claimer, proof)
         }
+
+        ClaimPayload::MultiDenom {
+            airdrop: id,
+            amount,
+            account,
+            merkle_proof: proof,
+        } => {
+            // use tx sender if account is not provided
+            let claimer = account
+                .map(|x| deps.api.addr_validate(&x))
+                .transpose()?
+                .unwrap_or_else(|| info.sender.clone());
+
+            claim_multi_denom(deps, env, info.sender, id, amount, claimer, proof)
+        }
+    }
+}
+
+// removes the transfer of claimed funds from the claim response
+pub(super) fn take_claimed_funds(
+    resp: &mut Response,
+) -> Result<(String, Vec<Coin>), ContractError> {
+    let pos = resp
+        .messages
+        .iter()
+        .position(|v| matches!(v.msg, CosmosMsg::Bank(BankMsg::Send { .. })))
+        .ok_or_else(|| StdError::generic_err("claimed funds not found"))?;
+
+    match resp.messages.remove(pos).msg {
+        CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => Ok((to_address, amount)),
+        _ => unreachable!(),
     }
 }
 
@@ -51,7 +143,7 @@ fn claim_open_event(
     typ: AirdropType,
     id: u64,
     claimer: Addr,
-    amount: impl Into<u128>,
+    amount: impl ToString,
 ) -> Vec<Attribute> {
     vec![
         attr("action", "claim"),
@@ -59,16 +151,26 @@ fn claim_open_event(
         attr("airdrop_type", typ),
         attr("airdrop_id", id.to_string()),
         attr("claimer", claimer),
-        attr("amount", amount.into().to_string()),
+        attr("amount", amount.to_string()),
     ]
 }
 
+// redirects funds of the claim to the recipient. authorized by the claimer
+struct Delegation {
+    recipient: Addr,
+    account_pub: Binary,
+    recipient_sig: Binary,
+}
+
+#[allow(clippy::too_many_arguments)]
 fn claim_open(
     deps: DepsMut,
+    env: Env,
     sender: Addr,
     id: AirdropId,
     amount: Uint128,
     claimer: Addr,
+    delegation: Option<Delegation>,
     merkle_proof: Vec<String>,
 ) -> Result<Response, ContractError> {
     let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
@@ -78,35 +180,78 @@ fn claim_open(
     if airdrop.closed_at.is_some() {
         return Err(ContractError::AirdropClosed {});
     }
+    assert_in_period(
+        airdrop.starts_at,
+        airdrop.expires_at,
+        env.block.time.seconds(),
+    )?;
 
-    assert_not_claimed(deps.storage, airdrop_id, claimer.as_str())?;
+    // leaf holds the cumulative amount since the root can be updated. pays what is not claimed yet
+    let claimed = CLAIM_LOGS
+        .may_load(deps.storage, (airdrop_id, claimer.as_str()))?
+        .unwrap_or_default();
+    if claimed >= amount {
+        return Err(ContractError::AlreadyClaimed {
+            airdrop_id,
+            claim_key: claimer.to_string(),
+        });
+    }
 
     // verify claimer
     verify_merkle_proof(&airdrop.merkle_root, merkle_proof, claimer.as_str(), amount)?;
 
+    // verify recipient
+    let recipient = match delegation {
+        Some(delegation) => {
+            verify_delegation(
+                deps.api,
+                &env,
+                airdrop_id,
+                claimer.as_str(),
+                delegation.recipient.as_str(),
+                amount,
+                &delegation.account_pub,
+                &delegation.recipient_sig,
+            )?;
+            delegation.recipient
+        }
+        None => claimer.clone(),
+    };
+
     // claim
-    airdrop.total_claimed = airdrop.total_claimed.checked_add(amount)?;
+    let claim_amount = amount.checked_sub(claimed)?;
+    airdrop.total_claimed = airdrop.total_claimed.checked_add(claim_amount)?;
     if airdrop.total_claimed > airdrop.total_amount {
         return Err(ContractError::InsufficientAirdropFunds {});
     }
 
     // response
     let claim_msg = BankMsg::Send {
-        to_address: claimer.to_string(),
-        amount: coins(amount.u128(), &airdrop.denom),
+        to_address: recipient.to_string(),
+        amount: coins(claim_amount.u128(), &airdrop.denom),
     };
 
-    let attrs = claim_open_event(
+    let mut attrs = claim_open_event(
         sender,
         AirdropType::Open,
         airdrop_id,
         claimer.clone(),
-        amount,
+        claim_amount,
     );
+    if recipient != claimer {
+        attrs.push(attr("recipient", recipient));
+    }
 
     // apply to state
     airdrops().save(deps.storage, airdrop_id, &airdrop.into())?;
     CLAIM_LOGS.save(deps.storage, (airdrop_id, claimer.as_str()), &amount)?;
+    record_claim(
+        deps.storage,
+        env.block.height,
+        airdrop_id,
+        claimer.as_str(),
+        claimer.as_str(),
+    )?;
 
     Ok(Response::new().add_message(claim_msg).add_attributes(attrs))
 }
@@ -133,6 +278,7 @@ fn claim_bearer_event(
 #[allow(clippy::too_many_arguments)]
 fn claim_bearer(
     deps: DepsMut,
+    env: Env,
     sender: Addr,
     id: AirdropId,
     amount: Uint128,
@@ -148,7 +294,13 @@ fn claim_bearer(
     if airdrop.closed_at.is_some() {
         return Err(ContractError::AirdropClosed {});
     }
+    assert_in_period(
+        airdrop.starts_at,
+        airdrop.expires_at,
+        env.block.time.seconds(),
+    )?;
     assert_not_claimed(deps.storage, airdrop_id, claim_hash.as_str())?;
+    assert_not_revoked(deps.storage, airdrop_id, claim_hash.as_str())?;
 
     // verifications
     verify_merkle_proof(&airdrop.merkle_root, merkle_proof, &claim_hash, amount)?;
@@ -180,13 +332,174 @@ fn claim_bearer(
         AirdropType::Bearer,
         airdrop_id,
         airdrop.signer.clone(),
-        claimer,
+        claimer.clone(),
         amount,
     );
 
     // apply to state
     airdrops().save(deps.storage, airdrop_id, &airdrop.into())?;
     CLAIM_LOGS.save(deps.storage, (airdrop_id, &claim_hash), &amount)?;
+    record_claim(
+        deps.storage,
+        env.block.height,
+        airdrop_id,
+        &claim_hash,
+        claimer.as_str(),
+    )?;
+
+    Ok(Response::new().add_message(claim_msg).add_attributes(attrs))
+}
+
+fn claim_vesting_event(
+    sender: Addr,
+    id: u64,
+    claimer: Addr,
+    amount: impl Into<u128>,
+    claimed: impl Into<u128>,
+) -> Vec<Attribute> {
+    vec![
+        attr("action", "claim"),
+        attr("executor", sender),
+        attr("airdrop_type", AirdropType::Vesting),
+        attr("airdrop_id", id.to_string()),
+        attr("claimer", claimer),
+        attr("amount", amount.into().to_string()),
+        attr("claimed", claimed.into().to_string()),
+    ]
+}
+
+fn claim_vesting(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: AirdropId,
+    allocation: Uint128,
+    claimer: Addr,
+    merkle_proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
+
+    // pre-validations
+    let mut airdrop = airdrop.unwrap_vesting()?;
+    if airdrop.closed_at.is_some() {
+        return Err(ContractError::AirdropClosed {});
+    }
+    assert_in_period(
+        airdrop.starts_at,
+        airdrop.expires_at,
+        env.block.time.seconds(),
+    )?;
+
+    // verify claimer
+    verify_merkle_proof(
+        &airdrop.merkle_root,
+        merkle_proof,
+        claimer.as_str(),
+        allocation,
+    )?;
+
+    // claim unlocked amount except already claimed
+    let claimed = CLAIM_LOGS
+        .may_load(deps.storage, (airdrop_id, claimer.as_str()))?
+        .unwrap_or_default();
+    let unlocked = airdrop.unlocked(allocation, env.block.time.seconds())?;
+    let amount = unlocked.checked_sub(claimed)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {
+            airdrop_id,
+            claim_key: claimer.to_string(),
+        });
+    }
+    let claimed = claimed.checked_add(amount)?;
+
+    airdrop.total_claimed = airdrop.total_claimed.checked_add(amount)?;
+    if airdrop.total_claimed > airdrop.total_amount {
+        return Err(ContractError::InsufficientAirdropFunds {});
+    }
+
+    // response
+    let claim_msg = BankMsg::Send {
+        to_address: claimer.to_string(),
+        amount: coins(amount.u128(), &airdrop.denom),
+    };
+
+    let attrs = claim_vesting_event(sender, airdrop_id, claimer.clone(), amount, claimed);
+
+    // apply to state
+    airdrops().save(deps.storage, airdrop_id, &airdrop.into())?;
+    CLAIM_LOGS.save(deps.storage, (airdrop_id, claimer.as_str()), &claimed)?;
+    VESTING_ALLOCATIONS.save(deps.storage, (airdrop_id, claimer.as_str()), &allocation)?;
+    record_claim(
+        deps.storage,
+        env.block.height,
+        airdrop_id,
+        claimer.as_str(),
+        claimer.as_str(),
+    )?;
+
+    Ok(Response::new().add_message(claim_msg).add_attributes(attrs))
+}
+
+fn claim_multi_denom(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    id: AirdropId,
+    amount: Vec<Coin>,
+    claimer: Addr,
+    merkle_proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
+
+    // pre-validations
+    let mut airdrop = airdrop.unwrap_multi_denom()?;
+    if airdrop.closed_at.is_some() {
+        return Err(ContractError::AirdropClosed {});
+    }
+    assert_in_period(
+        airdrop.starts_at,
+        airdrop.expires_at,
+        env.block.time.seconds(),
+    )?;
+
+    assert_not_claimed(deps.storage, airdrop_id, claimer.as_str())?;
+
+    // verify claimer
+    let amount = normalize_coins("amount", amount)?;
+    verify_multi_denom_merkle_proof(
+        &airdrop.merkle_root,
+        merkle_proof,
+        claimer.as_str(),
+        &amount,
+    )?;
+
+    // claim
+    airdrop.claim(&amount)?;
+
+    // response
+    let claim_msg = BankMsg::Send {
+        to_address: claimer.to_string(),
+        amount: amount.clone(),
+    };
+
+    let attrs = claim_open_event(
+        sender,
+        AirdropType::MultiDenom,
+        airdrop_id,
+        claimer.clone(),
+        coins_to_string(&amount),
+    );
+
+    // apply to state
+    airdrops().save(deps.storage, airdrop_id, &airdrop.into())?;
+    MULTI_DENOM_CLAIM_LOGS.save(deps.storage, (airdrop_id, claimer.as_str()), &amount)?;
+    record_claim(
+        deps.storage,
+        env.block.height,
+        airdrop_id,
+        claimer.as_str(),
+        claimer.as_str(),
+    )?;
 
     Ok(Response::new().add_message(claim_msg).add_attributes(attrs))
 }
@@ -194,23 +507,29 @@ fn claim_bearer(
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{
+        attr, coin,
         testing::{
             mock_dependencies_with_balances, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
         },
-        Addr, OwnedDeps,
+        Addr, BankMsg, OwnedDeps, SubMsg, Timestamp, Uint128,
     };
-    use ibcx_interface::airdrop::{AirdropType, ClaimPayload, InstantiateMsg};
+    use ibcx_interface::airdrop::{AirdropId, AirdropType, ClaimPayload, InstantiateMsg};
 
     use crate::{
         contract::instantiate,
         error::ContractError,
         execute::{
             claim::claim_bearer_event,
-            tests::{mock_bearer_airdrop, mock_open_airdrop, register_airdrop, Balances},
+            tests::{
+                mock_bearer_airdrop, mock_multi_denom_airdrop, mock_open_airdrop,
+                mock_vesting_airdrop, register_airdrop, Balances,
+            },
         },
+        query::{get_airdrop_stats, get_claim, list_claims_by_account, verify_claim},
+        state::airdrops,
     };
 
-    use super::{claim, claim_open_event};
+    use super::{claim, claim_open_event, claim_vesting_event};
 
     fn setup(airdrop_type: AirdropType) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
         let env = mock_env();
@@ -241,6 +560,15 @@ mod tests {
                     Some(mock_airdrop_sign),
                 );
             }
+            AirdropType::Vesting => {
+                let now = env.block.time.seconds();
+                let mock_airdrop = mock_vesting_airdrop(None, env.block.height, now);
+                register_airdrop(deps.as_mut(), env, mock_airdrop.into(), None);
+            }
+            AirdropType::MultiDenom => {
+                let mock_airdrop = mock_multi_denom_airdrop(None, env.block.height);
+                register_airdrop(deps.as_mut(), env, mock_airdrop.into(), None);
+            }
         }
 
         deps
@@ -315,6 +643,7 @@ mod tests {
         for (id, key, amount, proof, expect_err) in cases {
             let resp = claim(
                 deps.as_mut(),
+                mock_env(),
                 mock_info("anyone", &[]),
                 ClaimPayload::open_id(id, amount, Some(key), proof),
             );
@@ -334,6 +663,170 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_claim_period() {
+        let env = mock_env();
+        let now = env.block.time.seconds();
+
+        let mut deps = mock_dependencies_with_balances(&[]);
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        let mut mock_airdrop = mock_open_airdrop(None, env.block.height);
+        mock_airdrop.starts_at = Some(now + 100);
+        mock_airdrop.expires_at = Some(now + 200);
+        register_airdrop(deps.as_mut(), env.clone(), mock_airdrop.into(), None);
+
+        let claimer = "osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a";
+        let merkle_proof: &[&str] = &[
+            "7ea10756e42edf91a6fae6fa8a1acd00751c52c5e0f9d497a7abff7813512667",
+            "eda896591efa2cd33541930d90ea37449af60460ef8e527109ee9940238266ce",
+            "b712f5b328047024ff46b9e105ecb71dfcb9813088a87a7e6a46731e7db62638",
+            "eca3408c50efba13b12ec9b352e0403369ff423ee89f23d1f7ada03a90d7e84f",
+        ];
+
+        let cases = [
+            (
+                now + 99,
+                Some(ContractError::AirdropNotStarted {
+                    starts_at: now + 100,
+                }),
+            ),
+            (
+                now + 200,
+                Some(ContractError::AirdropExpired {
+                    expires_at: now + 200,
+                }),
+            ),
+            (now + 100, None),
+        ];
+        for (time, expect_err) in cases {
+            let mut env = mock_env();
+            env.block.time = Timestamp::from_seconds(time);
+
+            let payload = ClaimPayload::open_id(0, 1000u128, Some(claimer), merkle_proof);
+
+            let verify_resp = verify_claim(deps.as_ref(), env.clone(), payload.clone()).unwrap();
+            assert_eq!(verify_resp.valid, expect_err.is_none());
+            assert_eq!(
+                verify_resp.reason,
+                expect_err.as_ref().map(|e| e.to_string())
+            );
+
+            let resp = claim(deps.as_mut(), env, mock_info("anyone", &[]), payload);
+            match expect_err {
+                Some(err) => assert_eq!(resp.unwrap_err(), err),
+                None => assert!(resp.is_ok()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_claim_open_on_behalf() {
+        let env = mock_env();
+
+        let mut deps = mock_dependencies_with_balances(&[]);
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        // osmo15wgtkntdf26hqan77g0kdsldcxjddypx5ny5a6:1000
+        // osmo1z7huy904a3yf3aj8mxt5z6shy7dezrlw5gduju:500
+        let mut mock_airdrop = mock_open_airdrop(None, env.block.height);
+        mock_airdrop.merkle_root =
+            "806b77d35677d481ca65bc86308424032adb08c870cba01e62390470610901c3".to_string();
+        register_airdrop(deps.as_mut(), env, mock_airdrop.into(), None);
+
+        let account = "osmo15wgtkntdf26hqan77g0kdsldcxjddypx5ny5a6";
+        let account_pub = "02bb50e2d89a4ed70663d080659fe0ad4b9bc3e06c17a227433966cb59ceee020d";
+        // sign(sha256(cosmos-testnet-14002/cosmos2contract/0/osmo15wgtkntdf26hqan77g0kdsldcxjddypx5ny5a6/vault/1000))
+        let recipient_sig = "3cd2deaebd2210a2f515e283d8faf8b8737cd59679f3d1d1578da65e89922dc467062fcb31efbddf243aaede8fedfe90c4f48a2ebe2e4e87900568a85516915f";
+        // same claim signed for another airdrop contract
+        let other_contract_sig = "9b114985b75ef82f7905d395a1a9a4c636fe551f51a08df0b09fb22da24be4727e894fae1fb93c9f5cd277cba19e2fff992937b3c2368a90f49d584a5137bd76";
+        // same claim signed for another chain
+        let other_chain_sig = "95383d9ff6d820d6176b4a1a65bc3e2857992f42c6c40607355447668eff2c6c352bdbcb6d8726b6f3e3c7be1d27dfd832627b84f63c733050a33fa8a1cfe97a";
+        let merkle_proof: &[&str] =
+            &["a1596fa65978906623d9e9919a101586b727569cf7213897769aa0946f5bf05a"];
+
+        // public key of someone else
+        let (bearer, _) = mock_bearer_airdrop(None, 0);
+        let other_pub = hex::encode(bearer.signer_pub);
+
+        let invalid_sig = || Some(ContractError::invalid_signature("claim_on_behalf"));
+        let cases = [
+            (
+                "vault",
+                other_pub.as_str(),
+                recipient_sig,
+                Some(ContractError::InvalidPubKey {}),
+            ),
+            ("abuser", account_pub, recipient_sig, invalid_sig()),
+            ("vault", account_pub, other_contract_sig, invalid_sig()),
+            ("vault", account_pub, other_chain_sig, invalid_sig()),
+            ("vault", account_pub, recipient_sig, None),
+        ];
+        for (recipient, account_pub, recipient_sig, expect_err) in cases {
+            let payload = ClaimPayload::open_on_behalf_id(
+                0,
+                1000,
+                account,
+                recipient,
+                account_pub,
+                recipient_sig,
+                merkle_proof,
+            );
+
+            let verify_resp = verify_claim(deps.as_ref(), mock_env(), payload.clone()).unwrap();
+            assert_eq!(verify_resp.valid, expect_err.is_none());
+
+            let resp = claim(deps.as_mut(), mock_env(), mock_info("anyone", &[]), payload);
+            match expect_err {
+                Some(err) => assert_eq!(resp.unwrap_err(), err),
+                None => {
+                    let resp = resp.unwrap();
+                    assert_eq!(
+                        resp.messages,
+                        vec![SubMsg::new(BankMsg::Send {
+                            to_address: "vault".to_string(),
+                            amount: vec![coin(1000, "ukrw")],
+                        })]
+                    );
+
+                    let mut attrs = claim_open_event(
+                        Addr::unchecked("anyone"),
+                        AirdropType::Open,
+                        0,
+                        Addr::unchecked(account),
+                        1000u128,
+                    );
+                    attrs.push(attr("recipient", "vault"));
+                    assert_eq!(resp.attributes, attrs);
+                }
+            }
+        }
+
+        // claim is logged to the account
+        let claim_resp = get_claim(
+            deps.as_ref(),
+            mock_env(),
+            AirdropId::id(0),
+            account.to_string(),
+        )
+        .unwrap();
+        assert_eq!(claim_resp.amount, Uint128::new(1000));
+    }
+
     #[test]
     fn test_claim_bearer() {
         let mut deps = setup(AirdropType::Bearer);
@@ -442,6 +935,7 @@ mod tests {
         for (id, amount, claimer, hash, sign, proof, expect_err) in cases {
             let resp = claim(
                 deps.as_mut(),
+                mock_env(),
                 mock_info("anyone", &[]),
                 ClaimPayload::bearer_id(id, amount, Some(claimer), hash, sign, proof),
             );
@@ -462,4 +956,350 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_claim_vesting() {
+        let mut deps = setup(AirdropType::Vesting);
+
+        let now = mock_env().block.time.seconds();
+        let allocation = 1000u128;
+        let claimer = "osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a";
+        let merkle_proof: &[&str] = &[
+            "7ea10756e42edf91a6fae6fa8a1acd00751c52c5e0f9d497a7abff7813512667",
+            "eda896591efa2cd33541930d90ea37449af60460ef8e527109ee9940238266ce",
+            "b712f5b328047024ff46b9e105ecb71dfcb9813088a87a7e6a46731e7db62638",
+            "eca3408c50efba13b12ec9b352e0403369ff423ee89f23d1f7ada03a90d7e84f",
+        ];
+
+        let nothing_to_claim = || ContractError::NothingToClaim {
+            airdrop_id: 0,
+            claim_key: claimer.to_string(),
+        };
+
+        // vesting from now to now + 1000, cliff at now + 100
+        let cases = [
+            (now + 50, Err(nothing_to_claim())),
+            (now + 100, Ok((100u128, 100u128))),
+            (now + 100, Err(nothing_to_claim())),
+            (now + 500, Ok((400, 500))),
+            (now + 2000, Ok((500, 1000))),
+            (now + 3000, Err(nothing_to_claim())),
+        ];
+
+        for (time, expected) in cases {
+            let mut env = mock_env();
+            env.block.time = Timestamp::from_seconds(time);
+
+            let resp = claim(
+                deps.as_mut(),
+                env,
+                mock_info("anyone", &[]),
+                ClaimPayload::vesting_id(0, allocation, Some(claimer), merkle_proof),
+            );
+            match expected {
+                Ok((amount, claimed)) => assert_eq!(
+                    resp.unwrap().attributes,
+                    claim_vesting_event(
+                        Addr::unchecked("anyone"),
+                        0,
+                        Addr::unchecked(claimer),
+                        amount,
+                        claimed,
+                    )
+                ),
+                Err(err) => assert_eq!(resp.unwrap_err(), err),
+            }
+        }
+
+        // invalid allocation
+        let resp = claim(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ClaimPayload::vesting_id(0, allocation + 1, Some(claimer), merkle_proof),
+        );
+        assert_eq!(resp.unwrap_err(), ContractError::InvalidProof {});
+
+        // query has no sender to fall back on
+        let verify = |account: Option<&str>| {
+            let payload = ClaimPayload::vesting_id(0, allocation, account, merkle_proof);
+            verify_claim(deps.as_ref(), mock_env(), payload).unwrap()
+        };
+        assert!(verify(Some(claimer)).valid);
+
+        let resp = verify(None);
+        assert!(!resp.valid);
+        assert_eq!(resp.reason.as_deref(), Some("account is required"));
+    }
+
+    #[test]
+    fn test_get_claim_vesting() {
+        let mut deps = setup(AirdropType::Vesting);
+
+        let now = mock_env().block.time.seconds();
+        let claimer = "osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a";
+        let merkle_proof: &[&str] = &[
+            "7ea10756e42edf91a6fae6fa8a1acd00751c52c5e0f9d497a7abff7813512667",
+            "eda896591efa2cd33541930d90ea37449af60460ef8e527109ee9940238266ce",
+            "b712f5b328047024ff46b9e105ecb71dfcb9813088a87a7e6a46731e7db62638",
+            "eca3408c50efba13b12ec9b352e0403369ff423ee89f23d1f7ada03a90d7e84f",
+        ];
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(now + 200);
+        claim(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ClaimPayload::vesting_id(0, 1000, Some(claimer), merkle_proof),
+        )
+        .unwrap();
+
+        for (time, claimable) in [(now + 200, 0u128), (now + 600, 400), (now + 1000, 800)] {
+            env.block.time = Timestamp::from_seconds(time);
+
+            let resp = get_claim(
+                deps.as_ref(),
+                env.clone(),
+                AirdropId::id(0),
+                claimer.to_string(),
+            )
+            .unwrap();
+            assert_eq!(resp.amount, Uint128::new(200));
+            assert_eq!(resp.claimable, Uint128::new(claimable));
+        }
+    }
+
+    #[test]
+    fn test_claim_multi_denom() {
+        let mut deps = setup(AirdropType::MultiDenom);
+
+        let claimer = "osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a";
+        let merkle_proof: &[&str] = &[
+            "7e21391df0a21fad09c6f87a5fec8bd38282c55916eccbe3444057de6b9ff1c6",
+            "65698c501f586287e1158483203c6e9cf005b34cf8574b8a760ccb50a4232baa",
+        ];
+
+        let cases = [
+            // missing denom
+            (
+                vec![coin(1000, "uatom")],
+                Some(ContractError::InvalidProof {}),
+            ),
+            // duplicated denom
+            (
+                vec![coin(1000, "uatom"), coin(1000, "uatom"), coin(2000, "ukrw")],
+                Some(ContractError::InvalidArguments {
+                    arg: "amount".to_string(),
+                    reason: "must not contain duplicated denoms".to_string(),
+                }),
+            ),
+            // order of coins doesn't matter
+            (vec![coin(2000, "ukrw"), coin(1000, "uatom")], None),
+            // already claimed
+            (
+                vec![coin(1000, "uatom"), coin(2000, "ukrw")],
+                Some(ContractError::AlreadyClaimed {
+                    airdrop_id: 0,
+                    claim_key: claimer.to_string(),
+                }),
+            ),
+        ];
+        for (amount, expect_err) in cases {
+            let resp = claim(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("anyone", &[]),
+                ClaimPayload::multi_denom_id(0, amount, Some(claimer), merkle_proof),
+            );
+            match expect_err {
+                Some(err) => assert_eq!(resp.unwrap_err(), err),
+                None => {
+                    let resp = resp.unwrap();
+                    assert_eq!(
+                        resp.messages,
+                        vec![SubMsg::new(BankMsg::Send {
+                            to_address: claimer.to_string(),
+                            amount: vec![coin(1000, "uatom"), coin(2000, "ukrw")],
+                        })]
+                    );
+                    assert_eq!(
+                        resp.attributes,
+                        claim_open_event(
+                            Addr::unchecked("anyone"),
+                            AirdropType::MultiDenom,
+                            0,
+                            Addr::unchecked(claimer),
+                            "1000uatom,2000ukrw",
+                        )
+                    );
+                }
+            }
+        }
+
+        let claim_resp = get_claim(
+            deps.as_ref(),
+            mock_env(),
+            AirdropId::id(0),
+            claimer.to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            claim_resp.coins,
+            vec![coin(1000, "uatom"), coin(2000, "ukrw")]
+        );
+        assert_eq!(claim_resp.amount, Uint128::zero());
+
+        // exceeds the funded amount of uatom
+        let mut deps = setup(AirdropType::MultiDenom);
+        let airdrop = airdrops().load(&deps.storage, 0).unwrap();
+        let mut airdrop = airdrop.unwrap_multi_denom().unwrap();
+        airdrop.total_claimed = vec![coin(1500, "uatom")];
+        airdrops()
+            .save(&mut deps.storage, 0, &airdrop.into())
+            .unwrap();
+
+        let err = claim(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ClaimPayload::multi_denom_id(
+                0,
+                vec![coin(1000, "uatom"), coin(2000, "ukrw")],
+                Some(claimer),
+                merkle_proof,
+            ),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InsufficientAirdropFunds {});
+
+        // query has no sender to fall back on
+        let verify = |account: Option<&str>| {
+            let amount = vec![coin(1000, "uatom"), coin(2000, "ukrw")];
+            let payload = ClaimPayload::multi_denom_id(0, amount, account, merkle_proof);
+            verify_claim(deps.as_ref(), mock_env(), payload).unwrap()
+        };
+        assert!(verify(Some(claimer)).valid);
+
+        let resp = verify(None);
+        assert!(!resp.valid);
+        assert_eq!(resp.reason.as_deref(), Some("account is required"));
+    }
+
+    #[test]
+    fn test_claim_stats() {
+        let mut deps = setup(AirdropType::Open);
+        let env = mock_env();
+        let mock_airdrop = mock_multi_denom_airdrop(None, env.block.height);
+        register_airdrop(deps.as_mut(), env.clone(), mock_airdrop.into(), None);
+
+        let claimer = "osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a";
+        let total_amount = airdrops()
+            .load(&deps.storage, 0)
+            .unwrap()
+            .unwrap_open()
+            .unwrap()
+            .total_amount;
+
+        let stats = get_airdrop_stats(deps.as_ref(), AirdropId::id(0)).unwrap();
+        assert_eq!(stats.claimants, 0);
+        assert_eq!(stats.claimed, vec![]);
+        assert_eq!(stats.remaining, vec![coin(total_amount.u128(), "ukrw")]);
+        assert_eq!(stats.last_claimed_at, None);
+
+        claim(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ClaimPayload::open_id(
+                0,
+                1000,
+                Some(claimer),
+                &[
+                    "7ea10756e42edf91a6fae6fa8a1acd00751c52c5e0f9d497a7abff7813512667",
+                    "eda896591efa2cd33541930d90ea37449af60460ef8e527109ee9940238266ce",
+                    "b712f5b328047024ff46b9e105ecb71dfcb9813088a87a7e6a46731e7db62638",
+                    "eca3408c50efba13b12ec9b352e0403369ff423ee89f23d1f7ada03a90d7e84f",
+                ],
+            ),
+        )
+        .unwrap();
+
+        let mut env = env;
+        env.block.height += 10;
+        claim(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            ClaimPayload::multi_denom_id(
+                1,
+                vec![coin(1000, "uatom"), coin(2000, "ukrw")],
+                Some(claimer),
+                &[
+                    "7e21391df0a21fad09c6f87a5fec8bd38282c55916eccbe3444057de6b9ff1c6",
+                    "65698c501f586287e1158483203c6e9cf005b34cf8574b8a760ccb50a4232baa",
+                ],
+            ),
+        )
+        .unwrap();
+
+        let stats = get_airdrop_stats(deps.as_ref(), AirdropId::id(0)).unwrap();
+        assert_eq!(stats.claimants, 1);
+        assert_eq!(stats.claimed, vec![coin(1000, "ukrw")]);
+        assert_eq!(
+            stats.remaining,
+            vec![coin(total_amount.u128() - 1000, "ukrw")]
+        );
+        assert_eq!(stats.last_claimed_at, Some(mock_env().block.height));
+
+        let stats = get_airdrop_stats(deps.as_ref(), AirdropId::id(1)).unwrap();
+        assert_eq!(stats.claimants, 1);
+        assert_eq!(stats.claimed, vec![coin(1000, "uatom"), coin(2000, "ukrw")]);
+        assert_eq!(
+            stats.remaining,
+            vec![coin(1000, "uatom"), coin(1000, "ukrw")]
+        );
+        assert_eq!(stats.last_claimed_at, Some(env.block.height));
+
+        let claims = list_claims_by_account(
+            deps.as_ref(),
+            env.clone(),
+            claimer.to_string(),
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+        .0;
+        assert_eq!(claims.iter().map(|c| c.id).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(claims[0].amount, Uint128::new(1000));
+        assert_eq!(
+            claims[1].coins,
+            vec![coin(1000, "uatom"), coin(2000, "ukrw")]
+        );
+
+        let claims = list_claims_by_account(
+            deps.as_ref(),
+            env.clone(),
+            claimer.to_string(),
+            Some((0, claimer.to_string())),
+            None,
+            None,
+        )
+        .unwrap()
+        .0;
+        assert_eq!(claims.len(), 1);
+        assert_eq!(claims[0].id, 1);
+
+        let claims = list_claims_by_account(
+            deps.as_ref(),
+            env,
+            "osmo1z7huy904a3yf3aj8mxt5z6shy7dezrlw5gduju".to_string(),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(claims.0.is_empty());
+    }
 }
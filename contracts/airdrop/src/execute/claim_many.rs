@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+
+use crate::error::ContractError;
+use cosmwasm_std::{attr, BankMsg, Coin, DepsMut, Env, Event, MessageInfo, Response, Uint128};
+use ibcx_interface::airdrop::{ClaimManyMode, ClaimPayload};
+
+use super::claim::{claim, take_claimed_funds};
+
+pub fn claim_many(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    claims: Vec<ClaimPayload>,
+    mode: ClaimManyMode,
+) -> Result<Response, ContractError> {
+    if claims.is_empty() {
+        return Err(ContractError::InvalidArguments {
+            arg: "claims".to_string(),
+            reason: "must not be empty".to_string(),
+        });
+    }
+
+    // recipient => denom => amount
+    let mut transfers: BTreeMap<String, BTreeMap<String, Uint128>> = BTreeMap::new();
+    let mut events = vec![];
+    let total = claims.len() as u64;
+    let mut failed = 0u64;
+
+    for (index, payload) in claims.into_iter().enumerate() {
+        // claim handlers apply states only after every check has passed,
+        // so a failed claim leaves nothing behind in best-effort mode
+        let mut resp = match claim(deps.branch(), env.clone(), info.clone(), payload) {
+            Ok(resp) => resp,
+            Err(err) => match mode {
+                ClaimManyMode::Atomic => return Err(err),
+                ClaimManyMode::BestEffort => {
+                    failed += 1;
+                    events.push(
+                        Event::new("claim_failed")
+                            .add_attribute("index", index.to_string())
+                            .add_attribute("reason", err.to_string()),
+                    );
+                    continue;
+                }
+            },
+        };
+
+        let (recipient, funds) = take_claimed_funds(&mut resp)?;
+        let balances = transfers.entry(recipient).or_default();
+        for Coin { denom, amount } in funds {
+            let balance = balances.entry(denom).or_default();
+            *balance = balance.checked_add(amount)?;
+        }
+
+        events.push(
+            Event::new("claim")
+                .add_attribute("index", index.to_string())
+                .add_attributes(resp.attributes),
+        );
+    }
+
+    let send_msgs = transfers
+        .into_iter()
+        .map(|(to_address, balances)| BankMsg::Send {
+            to_address,
+            amount: balances
+                .into_iter()
+                .map(|(denom, amount)| Coin { denom, amount })
+                .collect(),
+        });
+
+    Ok(Response::new()
+        .add_messages(send_msgs)
+        .add_events(events)
+        .add_attributes(vec![
+            attr("method", "claim_many"),
+            attr("executor", info.sender),
+            attr("mode", mode),
+            attr("claimed", (total - failed).to_string()),
+            attr("failed", failed.to_string()),
+        ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coin,
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+        BankMsg, Event, OwnedDeps, SubMsg,
+    };
+    use ibcx_interface::airdrop::{ClaimManyMode, ClaimPayload, InstantiateMsg};
+
+    use crate::{
+        contract::instantiate,
+        error::ContractError,
+        execute::tests::{mock_multi_denom_airdrop, mock_open_airdrop, register_airdrop},
+    };
+
+    use super::claim_many;
+
+    const CLAIMER: &str = "osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a";
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let env = mock_env();
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        register_airdrop(
+            deps.as_mut(),
+            env.clone(),
+            mock_open_airdrop(None, env.block.height).into(),
+            None,
+        );
+        register_airdrop(
+            deps.as_mut(),
+            env.clone(),
+            mock_multi_denom_airdrop(None, env.block.height).into(),
+            None,
+        );
+
+        deps
+    }
+
+    fn open_claim() -> ClaimPayload {
+        ClaimPayload::open_id(
+            0,
+            1000u128,
+            Some(CLAIMER),
+            &[
+                "7ea10756e42edf91a6fae6fa8a1acd00751c52c5e0f9d497a7abff7813512667",
+                "eda896591efa2cd33541930d90ea37449af60460ef8e527109ee9940238266ce",
+                "b712f5b328047024ff46b9e105ecb71dfcb9813088a87a7e6a46731e7db62638",
+                "eca3408c50efba13b12ec9b352e0403369ff423ee89f23d1f7ada03a90d7e84f",
+            ],
+        )
+    }
+
+    fn multi_denom_claim() -> ClaimPayload {
+        ClaimPayload::multi_denom_id(
+            1,
+            vec![coin(1000, "uatom"), coin(2000, "ukrw")],
+            Some(CLAIMER),
+            &[
+                "7e21391df0a21fad09c6f87a5fec8bd38282c55916eccbe3444057de6b9ff1c6",
+                "65698c501f586287e1158483203c6e9cf005b34cf8574b8a760ccb50a4232baa",
+            ],
+        )
+    }
+
+    fn invalid_claim() -> ClaimPayload {
+        ClaimPayload::multi_denom_id(1, vec![coin(1000, "uatom")], Some(CLAIMER), &[])
+    }
+
+    #[test]
+    fn test_claim_many_empty() {
+        let mut deps = setup();
+
+        let err = claim_many(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            vec![],
+            ClaimManyMode::Atomic,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidArguments {
+                arg: "claims".to_string(),
+                reason: "must not be empty".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_claim_many_atomic() {
+        let mut deps = setup();
+
+        let err = claim_many(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            vec![open_claim(), invalid_claim()],
+            ClaimManyMode::Atomic,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidProof {});
+
+        // failed execution is reverted on chain, so start over
+        let mut deps = setup();
+        let resp = claim_many(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            vec![open_claim(), multi_denom_claim()],
+            ClaimManyMode::Atomic,
+        )
+        .unwrap();
+
+        // transfers to the same recipient are merged
+        assert_eq!(
+            resp.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: CLAIMER.to_string(),
+                amount: vec![coin(1000, "uatom"), coin(3000, "ukrw")],
+            })]
+        );
+        assert_eq!(
+            resp.events
+                .iter()
+                .map(|v| v.ty.as_str())
+                .collect::<Vec<_>>(),
+            vec!["claim", "claim"]
+        );
+    }
+
+    #[test]
+    fn test_claim_many_best_effort() {
+        let mut deps = setup();
+
+        let resp = claim_many(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            vec![
+                open_claim(),
+                invalid_claim(),
+                multi_denom_claim(),
+                open_claim(),
+            ],
+            ClaimManyMode::BestEffort,
+        )
+        .unwrap();
+
+        assert_eq!(
+            resp.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: CLAIMER.to_string(),
+                amount: vec![coin(1000, "uatom"), coin(3000, "ukrw")],
+            })]
+        );
+        assert_eq!(
+            resp.events
+                .iter()
+                .filter(|v| v.ty == "claim_failed")
+                .cloned()
+                .collect::<Vec<_>>(),
+            vec![
+                Event::new("claim_failed")
+                    .add_attribute("index", "1")
+                    .add_attribute("reason", ContractError::InvalidProof {}.to_string()),
+                Event::new("claim_failed")
+                    .add_attribute("index", "3")
+                    .add_attribute(
+                        "reason",
+                        ContractError::AlreadyClaimed {
+                            airdrop_id: 0,
+                            claim_key: CLAIMER.to_string(),
+                        }
+                        .to_string()
+                    ),
+            ]
+        );
+        assert!(resp
+            .attributes
+            .iter()
+            .any(|v| v.key == "claimed" && v.value == "2"));
+        assert!(resp
+            .attributes
+            .iter()
+            .any(|v| v.key == "failed" && v.value == "2"));
+    }
+}
@@ -1,7 +1,7 @@
-use crate::airdrop::{Airdrop, BearerAirdrop, OpenAirdrop};
+use crate::airdrop::{coins_to_string, Airdrop};
 use crate::error::ContractError;
 use crate::state::{airdrops, load_airdrop};
-use cosmwasm_std::{attr, coins, Addr, Attribute, BankMsg, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{attr, Addr, Attribute, BankMsg, DepsMut, Env, MessageInfo, Response, Storage};
 use ibcx_interface::airdrop::{AirdropId, AirdropType};
 
 pub fn close(
@@ -12,95 +12,72 @@ pub fn close(
 ) -> Result<Response, ContractError> {
     let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
 
-    match airdrop {
-        Airdrop::Open(inner) => close_open(deps, env, info, airdrop_id, inner),
-        Airdrop::Bearer(inner) => close_bearer(deps, env, info, airdrop_id, inner),
-    }
-}
-
-fn close_event(sender: Addr, typ: AirdropType, id: u64, redeem: impl Into<u128>) -> Vec<Attribute> {
-    vec![
-        attr("method", "close"),
-        attr("executor", sender),
-        attr("airdrop_type", typ),
-        attr("airdrop_id", id.to_string()),
-        attr("redeemed", redeem.into().to_string()),
-    ]
-}
-
-fn close_open(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    airdrop_id: u64,
-    mut airdrop: OpenAirdrop,
-) -> Result<Response, ContractError> {
     // validation
-    if airdrop.creator != info.sender {
+    if airdrop.creator() != info.sender {
         return Err(ContractError::Unauthorized {});
     }
-    if airdrop.closed_at.is_some() {
+    if airdrop.closed_at().is_some() {
         return Err(ContractError::AirdropClosed {});
     }
 
-    let redeem_amount = airdrop.total_amount.checked_sub(airdrop.total_claimed)?;
-
-    airdrop.closed_at = Some(env.block.height);
+    let typ = airdrop.type_of();
+    let creator = airdrop.creator().clone();
+    let (send_msg, redeemed) = redeem(deps.storage, &env, (airdrop_id, airdrop), &creator)?;
 
     // response
-    let send_msg = BankMsg::Send {
-        to_address: airdrop.creator.to_string(),
-        amount: coins(redeem_amount.u128(), &airdrop.denom),
-    };
-
-    let attrs = close_event(info.sender, AirdropType::Open, airdrop_id, redeem_amount);
+    let attrs = close_event(info.sender, typ, airdrop_id, redeemed);
 
-    // apply states
-    airdrops().save(deps.storage, airdrop_id, &airdrop.into())?;
-
-    Ok(Response::new().add_message(send_msg).add_attributes(attrs))
+    Ok(Response::new().add_messages(send_msg).add_attributes(attrs))
 }
 
-fn close_bearer(
-    deps: DepsMut,
-    env: Env,
-    info: MessageInfo,
-    airdrop_id: u64,
-    mut airdrop: BearerAirdrop,
-) -> Result<Response, ContractError> {
-    // validation
-    if airdrop.creator != info.sender {
-        return Err(ContractError::Unauthorized {});
-    }
-    if airdrop.closed_at.is_some() {
-        return Err(ContractError::AirdropClosed {});
-    }
-
-    let redeem_amount = airdrop.total_amount.checked_sub(airdrop.total_claimed)?;
-
-    airdrop.closed_at = Some(env.block.height);
+fn close_event(sender: Addr, typ: AirdropType, id: u64, redeem: impl ToString) -> Vec<Attribute> {
+    vec![
+        attr("method", "close"),
+        attr("executor", sender),
+        attr("airdrop_type", typ),
+        attr("airdrop_id", id.to_string()),
+        attr("redeemed", redeem.to_string()),
+    ]
+}
 
-    // response
-    let send_msg = BankMsg::Send {
-        to_address: airdrop.creator.to_string(),
-        amount: coins(redeem_amount.u128(), &airdrop.denom),
+// closes the airdrop and sends the unclaimed funds to the receiver. shared with sweep.
+// returns the send message (none if nothing is left) and the redeemed amount for the event
+pub fn redeem(
+    storage: &mut dyn Storage,
+    env: &Env,
+    (airdrop_id, mut airdrop): (u64, Airdrop),
+    receiver: &Addr,
+) -> Result<(Option<BankMsg>, String), ContractError> {
+    let redeem_amount = airdrop.close(env.block.height)?;
+
+    // single denom airdrops log the amount only
+    let redeemed = match airdrop {
+        Airdrop::MultiDenom(_) => coins_to_string(&redeem_amount),
+        _ => redeem_amount
+            .first()
+            .map(|c| c.amount)
+            .unwrap_or_default()
+            .to_string(),
     };
-
-    let attrs = close_event(info.sender, AirdropType::Bearer, airdrop_id, redeem_amount);
+    let send_msg = (!redeem_amount.is_empty()).then(|| BankMsg::Send {
+        to_address: receiver.to_string(),
+        amount: redeem_amount,
+    });
 
     // apply states
-    airdrops().save(deps.storage, airdrop_id, &airdrop.into())?;
+    airdrops().save(storage, airdrop_id, &airdrop)?;
 
-    Ok(Response::new().add_message(send_msg).add_attributes(attrs))
+    Ok((send_msg, redeemed))
 }
 
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{
+        coin,
         testing::{
             mock_dependencies_with_balances, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
         },
-        Addr, OwnedDeps,
+        Addr, BankMsg, OwnedDeps, SubMsg,
     };
     use ibcx_interface::airdrop::{AirdropId, AirdropType, InstantiateMsg};
 
@@ -109,8 +86,8 @@ mod tests {
         execute::{
             close::close_event,
             tests::{
-                mock_bearer_airdrop, mock_open_airdrop, normalize_amount, register_airdrop,
-                Balances,
+                mock_bearer_airdrop, mock_multi_denom_airdrop, mock_open_airdrop,
+                mock_vesting_airdrop, normalize_amount, register_airdrop, Balances,
             },
         },
     };
@@ -146,6 +123,15 @@ mod tests {
                     Some(mock_airdrop_sign),
                 );
             }
+            AirdropType::Vesting => {
+                let now = env.block.time.seconds();
+                let mock_airdrop = mock_vesting_airdrop(None, env.block.height, now);
+                register_airdrop(deps.as_mut(), env, mock_airdrop.into(), None);
+            }
+            AirdropType::MultiDenom => {
+                let mock_airdrop = mock_multi_denom_airdrop(None, env.block.height);
+                register_airdrop(deps.as_mut(), env, mock_airdrop.into(), None);
+            }
         }
 
         deps
@@ -194,4 +180,55 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_close_vesting() {
+        let mut deps = setup(AirdropType::Vesting);
+
+        let close_resp = close(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("tester", &[]),
+            AirdropId::id(0),
+        )
+        .unwrap();
+        assert_eq!(
+            close_resp.attributes,
+            close_event(
+                Addr::unchecked("tester"),
+                AirdropType::Vesting,
+                0,
+                normalize_amount(0.01)
+            )
+        );
+    }
+
+    #[test]
+    fn test_close_multi_denom() {
+        let mut deps = setup(AirdropType::MultiDenom);
+
+        let close_resp = close(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("tester", &[]),
+            AirdropId::id(0),
+        )
+        .unwrap();
+        assert_eq!(
+            close_resp.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "tester".to_string(),
+                amount: vec![coin(2000, "uatom"), coin(3000, "ukrw")],
+            })]
+        );
+        assert_eq!(
+            close_resp.attributes,
+            close_event(
+                Addr::unchecked("tester"),
+                AirdropType::MultiDenom,
+                0,
+                "2000uatom,3000ukrw"
+            )
+        );
+    }
 }
@@ -1,4 +1,7 @@
-use crate::airdrop::{Airdrop, BearerAirdrop, OpenAirdrop};
+use crate::airdrop::{
+    coins_to_string, normalize_coins, Airdrop, BearerAirdrop, MultiDenomAirdrop, OpenAirdrop,
+    VestingAirdrop,
+};
 use crate::error::ContractError;
 use crate::state::{airdrops, load_airdrop};
 use cosmwasm_std::{attr, Addr, Attribute, DepsMut, MessageInfo, Response};
@@ -10,16 +13,18 @@ pub fn fund(deps: DepsMut, info: MessageInfo, id: AirdropId) -> Result<Response,
     match airdrop {
         Airdrop::Open(inner) => fund_open(deps, info, airdrop_id, inner),
         Airdrop::Bearer(inner) => fund_bearer(deps, info, airdrop_id, inner),
+        Airdrop::Vesting(inner) => fund_vesting(deps, info, airdrop_id, inner),
+        Airdrop::MultiDenom(inner) => fund_multi_denom(deps, info, airdrop_id, inner),
     }
 }
 
-fn fund_event(sender: Addr, typ: AirdropType, id: u64, add: impl Into<u128>) -> Vec<Attribute> {
+fn fund_event(sender: Addr, typ: AirdropType, id: u64, add: impl ToString) -> Vec<Attribute> {
     vec![
         attr("action", "fund"),
         attr("executor", sender),
         attr("airdrop_type", typ),
         attr("airdrop_id", id.to_string()),
-        attr("amount", add.into().to_string()),
+        attr("amount", add.to_string()),
     ]
 }
 
@@ -73,6 +78,65 @@ fn fund_bearer(
     Ok(Response::new().add_attributes(attrs))
 }
 
+fn fund_vesting(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    mut airdrop: VestingAirdrop,
+) -> Result<Response, ContractError> {
+    if airdrop.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if airdrop.closed_at.is_some() {
+        return Err(ContractError::AirdropClosed {});
+    }
+
+    let additional_funds = cw_utils::must_pay(&info, &airdrop.denom)?;
+    airdrop.total_amount = airdrop.total_amount.checked_add(additional_funds)?;
+
+    // event attributes
+    let attrs = fund_event(info.sender, AirdropType::Vesting, id, additional_funds);
+
+    // apply to state
+    airdrops().save(deps.storage, id, &airdrop.into())?;
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+// any denom can be added to multi-denom airdrops
+fn fund_multi_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+    mut airdrop: MultiDenomAirdrop,
+) -> Result<Response, ContractError> {
+    if airdrop.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if airdrop.closed_at.is_some() {
+        return Err(ContractError::AirdropClosed {});
+    }
+
+    if info.funds.is_empty() {
+        return Err(cw_utils::PaymentError::NoFunds {}.into());
+    }
+    let additional_funds = normalize_coins("funds", info.funds)?;
+    airdrop.fund(&additional_funds)?;
+
+    // event attributes
+    let attrs = fund_event(
+        info.sender,
+        AirdropType::MultiDenom,
+        id,
+        coins_to_string(&additional_funds),
+    );
+
+    // apply to state
+    airdrops().save(deps.storage, id, &airdrop.into())?;
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{
@@ -82,7 +146,7 @@ mod tests {
         },
         Addr, OwnedDeps,
     };
-    use ibcx_interface::airdrop::{AirdropId, AirdropType, InstantiateMsg};
+    use ibcx_interface::airdrop::{AirdropId, AirdropType, GetAirdropResponse, InstantiateMsg};
 
     use crate::{
         contract::instantiate,
@@ -91,10 +155,11 @@ mod tests {
             close,
             fund::fund_event,
             tests::{
-                mock_bearer_airdrop, mock_open_airdrop, normalize_amount, register_airdrop,
-                Balances,
+                mock_bearer_airdrop, mock_multi_denom_airdrop, mock_open_airdrop,
+                mock_vesting_airdrop, normalize_amount, register_airdrop, Balances,
             },
         },
+        query,
     };
 
     use super::fund;
@@ -128,6 +193,15 @@ mod tests {
                     Some(mock_airdrop_sign),
                 );
             }
+            AirdropType::Vesting => {
+                let now = env.block.time.seconds();
+                let mock_airdrop = mock_vesting_airdrop(None, env.block.height, now);
+                register_airdrop(deps.as_mut(), env, mock_airdrop.into(), None);
+            }
+            AirdropType::MultiDenom => {
+                let mock_airdrop = mock_multi_denom_airdrop(None, env.block.height);
+                register_airdrop(deps.as_mut(), env, mock_airdrop.into(), None);
+            }
         }
 
         deps
@@ -273,4 +347,60 @@ mod tests {
             assert_eq!(fund_resp, ContractError::AirdropClosed {});
         }
     }
+
+    #[test]
+    fn test_fund_multi_denom() {
+        let mut deps = setup(AirdropType::MultiDenom);
+
+        // any denom can be added
+        {
+            let info_fund_creator = mock_info("tester", &[coin(10, "ukrw"), coin(20, "ujpy")]);
+            let fund_resp = fund(deps.as_mut(), info_fund_creator, AirdropId::id(0)).unwrap();
+            assert_eq!(
+                fund_resp.attributes,
+                fund_event(
+                    Addr::unchecked("tester"),
+                    AirdropType::MultiDenom,
+                    0,
+                    "20ujpy,10ukrw"
+                )
+            );
+
+            let airdrop = query::get_airdrop(deps.as_ref(), AirdropId::id(0)).unwrap();
+            let GetAirdropResponse::MultiDenom { total_amount, .. } = airdrop else {
+                panic!("unexpected airdrop type");
+            };
+            assert_eq!(
+                total_amount,
+                vec![coin(2000, "uatom"), coin(20, "ujpy"), coin(3010, "ukrw")]
+            );
+        }
+
+        // check fund
+        {
+            let info_fund_no_fund = mock_info("tester", &[]);
+            let fund_resp = fund(deps.as_mut(), info_fund_no_fund, AirdropId::id(0)).unwrap_err();
+            assert_eq!(
+                fund_resp,
+                ContractError::PaymentError(cw_utils::PaymentError::NoFunds {})
+            );
+
+            let info_fund_zero_fund = mock_info("tester", &[coin(10, "ukrw"), coin(0, "ujpy")]);
+            let fund_resp = fund(deps.as_mut(), info_fund_zero_fund, AirdropId::id(0)).unwrap_err();
+            assert_eq!(
+                fund_resp,
+                ContractError::InvalidArguments {
+                    arg: "funds".to_string(),
+                    reason: "must not contain zero amount".to_string(),
+                }
+            );
+        }
+
+        // check unauthorized
+        {
+            let info_fund_abuser = mock_info("abuser", &[coin(10, "ukrw")]);
+            let fund_resp = fund(deps.as_mut(), info_fund_abuser, AirdropId::id(0)).unwrap_err();
+            assert_eq!(fund_resp, ContractError::Unauthorized {});
+        }
+    }
 }
@@ -0,0 +1,399 @@
+use crate::airdrop::coins_to_string;
+use crate::error::ContractError;
+use cosmwasm_std::{attr, Coin, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+use ibcx_interface::{
+    airdrop::{ClaimPayload, MintOption},
+    core,
+    helpers::{IbcCore, IbcPeriphery},
+    periphery,
+};
+
+use super::claim::{claim, take_claimed_funds};
+
+pub fn claim_and_mint(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    payload: ClaimPayload,
+    option: MintOption,
+) -> Result<Response, ContractError> {
+    let sender = info.sender.clone();
+    // core applies fee tiers and access policy of this contract, which sends the mint
+    let minter = env.contract.address.clone();
+
+    let mut resp = claim(deps.branch(), env, info, payload)?;
+
+    // forward claimed funds to the mint instead of the recipient
+    let (recipient, funds) = take_claimed_funds(&mut resp)?;
+    if recipient != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let (mint_msg, attrs) = match option {
+        MintOption::Periphery {
+            periphery,
+            core,
+            min_output_amount,
+            swap_info,
+        } => {
+            let [input] = funds.as_slice() else {
+                return Err(ContractError::InvalidArguments {
+                    arg: "mint".to_string(),
+                    reason: "periphery takes a single denom".to_string(),
+                });
+            };
+
+            let periphery = IbcPeriphery(deps.api.addr_validate(&periphery)?);
+            let mint_msg = periphery.call_with_funds(
+                periphery::ExecuteMsg::MintExactAmountIn {
+                    core_addr: deps.api.addr_validate(&core)?.to_string(),
+                    input_asset: input.denom.clone(),
+                    min_output_amount,
+                    swap_info,
+                    referral: None,
+                    receiver: Some(recipient),
+                },
+                funds.clone(),
+            )?;
+
+            (
+                mint_msg,
+                vec![
+                    attr("mint_via", "periphery"),
+                    attr("min_output", min_output_amount),
+                ],
+            )
+        }
+
+        MintOption::Core {
+            core,
+            min_output_amount,
+        } => {
+            let core = IbcCore(deps.api.addr_validate(&core)?);
+            let amount = calc_mint_amount(deps.as_ref(), &core, &funds)?;
+
+            let sim = core.simulate_mint(
+                &deps.querier,
+                amount,
+                Some(funds.clone()),
+                None,
+                Some(minter.to_string()),
+            )?;
+            if sim.mint_amount < min_output_amount {
+                return Err(ContractError::InsufficientMintOutput {
+                    min_output: min_output_amount,
+                    output: sim.mint_amount,
+                });
+            }
+
+            let mint_msg = core.call_with_funds(
+                core::ExecuteMsg::Mint {
+                    amount,
+                    receiver: Some(recipient.clone()),
+                    refund_to: Some(recipient),
+                },
+                funds.clone(),
+            )?;
+
+            (
+                mint_msg,
+                vec![attr("mint_via", "core"), attr("output", sim.mint_amount)],
+            )
+        }
+    };
+
+    Ok(resp
+        .add_message(mint_msg)
+        .add_attribute("mint_input", coins_to_string(&funds))
+        .add_attributes(attrs))
+}
+
+// max amount of the index mintable with the funds
+fn calc_mint_amount(deps: Deps, core: &IbcCore, funds: &[Coin]) -> Result<Uint128, ContractError> {
+    let portfolio = core.get_portfolio(&deps.querier, None)?;
+
+    let mut amount: Option<Uint128> = None;
+    for (denom, unit) in portfolio.units {
+        let fund = funds
+            .iter()
+            .find(|c| c.denom == denom)
+            .map(|c| c.amount)
+            .unwrap_or_default();
+
+        let mintable = fund.checked_div_floor(unit)?;
+        amount = Some(amount.map_or(mintable, |v| v.min(mintable)));
+    }
+
+    match amount {
+        Some(amount) if !amount.is_zero() => Ok(amount),
+        _ => Err(ContractError::InvalidArguments {
+            arg: "mint".to_string(),
+            reason: "claimed funds don't cover the index units".to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coin, from_json,
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+        to_json_binary, ContractResult, Decimal, OwnedDeps, SubMsg, SystemResult, Uint128, WasmMsg,
+        WasmQuery,
+    };
+    use ibcx_interface::{
+        airdrop::{ClaimPayload, InstantiateMsg, MintOption},
+        core, periphery,
+        periphery::SwapInfosCompact,
+    };
+
+    use crate::{
+        contract::instantiate,
+        error::ContractError,
+        execute::tests::{mock_multi_denom_airdrop, mock_open_airdrop, register_airdrop},
+    };
+
+    use super::claim_and_mint;
+
+    const CLAIMER: &str = "osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a";
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let env = mock_env();
+        let mut deps = mock_dependencies();
+
+        // core holding 1 uatom + 2 ukrw per unit, minting with 1% fee. the claimer is exempted
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => {
+                let resp = match from_json(msg).unwrap() {
+                    core::QueryMsg::GetPortfolio { .. } => {
+                        to_json_binary(&core::GetPortfolioResponse {
+                            total_supply: Uint128::zero(),
+                            assets: vec![],
+                            units: vec![
+                                ("uatom".to_string(), Decimal::one()),
+                                ("ukrw".to_string(), Decimal::from_ratio(2u128, 1u128)),
+                            ],
+                        })
+                    }
+                    core::QueryMsg::SimulateMint {
+                        amount,
+                        funds,
+                        sender,
+                        ..
+                    } => {
+                        let fee = match sender.as_deref() {
+                            Some(CLAIMER) => Decimal::zero(),
+                            _ => Decimal::percent(1),
+                        };
+                        to_json_binary(&core::SimulateMintResponse {
+                            mint_amount: amount * (Decimal::one() - fee),
+                            fee_amount: amount * fee,
+                            refund_amount: vec![],
+                            fund_spent: funds.unwrap_or_default(),
+                        })
+                    }
+                    _ => unimplemented!(),
+                };
+                SystemResult::Ok(ContractResult::Ok(resp.unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        register_airdrop(
+            deps.as_mut(),
+            env.clone(),
+            mock_open_airdrop(None, env.block.height).into(),
+            None,
+        );
+        register_airdrop(
+            deps.as_mut(),
+            env.clone(),
+            mock_multi_denom_airdrop(None, env.block.height).into(),
+            None,
+        );
+
+        deps
+    }
+
+    fn open_claim() -> ClaimPayload {
+        ClaimPayload::open_id(
+            0,
+            1000u128,
+            Some(CLAIMER),
+            &[
+                "7ea10756e42edf91a6fae6fa8a1acd00751c52c5e0f9d497a7abff7813512667",
+                "eda896591efa2cd33541930d90ea37449af60460ef8e527109ee9940238266ce",
+                "b712f5b328047024ff46b9e105ecb71dfcb9813088a87a7e6a46731e7db62638",
+                "eca3408c50efba13b12ec9b352e0403369ff423ee89f23d1f7ada03a90d7e84f",
+            ],
+        )
+    }
+
+    fn multi_denom_claim() -> ClaimPayload {
+        ClaimPayload::multi_denom_id(
+            1,
+            vec![coin(1000, "uatom"), coin(2000, "ukrw")],
+            Some(CLAIMER),
+            &[
+                "7e21391df0a21fad09c6f87a5fec8bd38282c55916eccbe3444057de6b9ff1c6",
+                "65698c501f586287e1158483203c6e9cf005b34cf8574b8a760ccb50a4232baa",
+            ],
+        )
+    }
+
+    fn periphery_option() -> MintOption {
+        MintOption::Periphery {
+            periphery: "periphery".to_string(),
+            core: "core".to_string(),
+            min_output_amount: Uint128::new(900),
+            swap_info: SwapInfosCompact(vec![]),
+        }
+    }
+
+    #[test]
+    fn test_claim_and_mint_unauthorized() {
+        let mut deps = setup();
+
+        let err = claim_and_mint(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            open_claim(),
+            periphery_option(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn test_claim_and_mint_periphery() {
+        let mut deps = setup();
+
+        // multiple denoms can't be swapped through the periphery
+        let err = claim_and_mint(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CLAIMER, &[]),
+            multi_denom_claim(),
+            periphery_option(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidArguments {
+                arg: "mint".to_string(),
+                reason: "periphery takes a single denom".to_string(),
+            }
+        );
+
+        let resp = claim_and_mint(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CLAIMER, &[]),
+            open_claim(),
+            periphery_option(),
+        )
+        .unwrap();
+        assert_eq!(
+            resp.messages,
+            vec![SubMsg::new(WasmMsg::Execute {
+                contract_addr: "periphery".to_string(),
+                msg: to_json_binary(&periphery::ExecuteMsg::MintExactAmountIn {
+                    core_addr: "core".to_string(),
+                    input_asset: "ukrw".to_string(),
+                    min_output_amount: Uint128::new(900),
+                    swap_info: SwapInfosCompact(vec![]),
+                    referral: None,
+                    receiver: Some(CLAIMER.to_string()),
+                })
+                .unwrap(),
+                funds: vec![coin(1000, "ukrw")],
+            })]
+        );
+    }
+
+    #[test]
+    fn test_claim_and_mint_core() {
+        let mut deps = setup();
+
+        // claimed funds mint 1000 units, 990 after fee
+        let err = claim_and_mint(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CLAIMER, &[]),
+            multi_denom_claim(),
+            MintOption::Core {
+                core: "core".to_string(),
+                min_output_amount: Uint128::new(1000),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientMintOutput {
+                min_output: Uint128::new(1000),
+                output: Uint128::new(990),
+            }
+        );
+
+        // failed execution is reverted on chain, so start over
+        let mut deps = setup();
+        let resp = claim_and_mint(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CLAIMER, &[]),
+            multi_denom_claim(),
+            MintOption::Core {
+                core: "core".to_string(),
+                min_output_amount: Uint128::new(990),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            resp.messages,
+            vec![SubMsg::new(WasmMsg::Execute {
+                contract_addr: "core".to_string(),
+                msg: to_json_binary(&core::ExecuteMsg::Mint {
+                    amount: Uint128::new(1000),
+                    receiver: Some(CLAIMER.to_string()),
+                    refund_to: Some(CLAIMER.to_string()),
+                })
+                .unwrap(),
+                funds: vec![coin(1000, "uatom"), coin(2000, "ukrw")],
+            })]
+        );
+    }
+
+    #[test]
+    fn test_claim_and_mint_core_discount() {
+        let mut deps = setup();
+
+        // discount of the claimer doesn't apply to the mint sent by the airdrop contract
+        let err = claim_and_mint(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(CLAIMER, &[]),
+            multi_denom_claim(),
+            MintOption::Core {
+                core: "core".to_string(),
+                min_output_amount: Uint128::new(995),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientMintOutput {
+                min_output: Uint128::new(995),
+                output: Uint128::new(990),
+            }
+        );
+    }
+}
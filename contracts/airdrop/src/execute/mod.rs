@@ -1,19 +1,31 @@
+mod backfill;
+mod bearer;
 mod claim;
+mod claim_many;
 mod close;
 mod fund;
+mod mint;
 mod register;
+mod sweep;
+mod update_root;
 
+pub use backfill::backfill_claims;
+pub use bearer::{revoke_claims, rotate_signer};
 pub use claim::claim;
+pub use claim_many::claim_many;
 pub use close::close;
 pub use fund::fund;
+pub use mint::claim_and_mint;
 pub use register::register;
+pub use sweep::sweep;
+pub use update_root::update_root;
 
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{coin, testing::mock_info, Addr, Binary, Coin, DepsMut, Env};
     use ibcx_interface::airdrop::RegisterPayload;
 
-    use crate::airdrop::{Airdrop, BearerAirdrop, OpenAirdrop};
+    use crate::airdrop::{Airdrop, BearerAirdrop, MultiDenomAirdrop, OpenAirdrop, VestingAirdrop};
 
     use super::register;
 
@@ -37,7 +49,11 @@ mod tests {
                     deps,
                     env,
                     info_register_open,
-                    RegisterPayload::open(&inner.merkle_root, &inner.denom, None),
+                    RegisterPayload::open(&inner.merkle_root, &inner.denom, None).with_period(
+                        inner.starts_at,
+                        inner.expires_at,
+                        inner.beneficiary.as_ref().map(|v| v.as_str()),
+                    ),
                 )
                 .unwrap();
             }
@@ -58,6 +74,53 @@ mod tests {
                         hex::encode(&inner.signer_pub),
                         sign.unwrap(),
                         None,
+                    )
+                    .with_period(
+                        inner.starts_at,
+                        inner.expires_at,
+                        inner.beneficiary.as_ref().map(|v| v.as_str()),
+                    ),
+                )
+                .unwrap();
+            }
+            Airdrop::Vesting(inner) => {
+                // vesting
+                let info_register_vesting = mock_info(
+                    inner.creator.as_str(),
+                    &[coin(inner.total_amount.u128(), &inner.denom)],
+                );
+                register(
+                    deps,
+                    env,
+                    info_register_vesting,
+                    RegisterPayload::vesting(
+                        &inner.merkle_root,
+                        &inner.denom,
+                        inner.start_time,
+                        inner.end_time,
+                        inner.cliff_time,
+                        None,
+                    )
+                    .with_period(
+                        inner.starts_at,
+                        inner.expires_at,
+                        inner.beneficiary.as_ref().map(|v| v.as_str()),
+                    ),
+                )
+                .unwrap();
+            }
+            Airdrop::MultiDenom(inner) => {
+                // multi-denom
+                let info_register_multi_denom =
+                    mock_info(inner.creator.as_str(), &inner.total_amount);
+                register(
+                    deps,
+                    env,
+                    info_register_multi_denom,
+                    RegisterPayload::multi_denom(&inner.merkle_root, None).with_period(
+                        inner.starts_at,
+                        inner.expires_at,
+                        inner.beneficiary.as_ref().map(|v| v.as_str()),
                     ),
                 )
                 .unwrap();
@@ -82,6 +145,9 @@ mod tests {
             label: label.map(|v| format!("{registerer}/{v}")),
             created_at,
             closed_at: None,
+            starts_at: None,
+            expires_at: None,
+            beneficiary: None,
         }
     }
 
@@ -110,8 +176,63 @@ mod tests {
                 label: label.map(|v| format!("{registerer}/{v}")),
                 created_at,
                 closed_at: None,
+                starts_at: None,
+                expires_at: None,
+                beneficiary: None,
             },
             signer_sig,
         )
     }
+
+    pub fn mock_vesting_airdrop(label: Option<&str>, created_at: u64, now: u64) -> VestingAirdrop {
+        let registerer = "tester".to_string();
+
+        // shares the tree with local-out/osmo-staker.json
+        let merkle_root = "597f35d2e2f4f5c02e31be44695da0c3e0ce03bbb212c6cfc4ef94d7d4940bb5";
+
+        VestingAirdrop {
+            creator: Addr::unchecked(&registerer),
+
+            denom: "ukrw".to_string(),
+            total_amount: normalize_amount(0.01).into(),
+            total_claimed: normalize_amount(0.0).into(),
+            merkle_root: merkle_root.to_string(),
+
+            start_time: now,
+            end_time: now + 1000,
+            cliff_time: Some(now + 100),
+
+            label: label.map(|v| format!("{registerer}/{v}")),
+            created_at,
+            closed_at: None,
+            starts_at: None,
+            expires_at: None,
+            beneficiary: None,
+        }
+    }
+
+    pub fn mock_multi_denom_airdrop(label: Option<&str>, created_at: u64) -> MultiDenomAirdrop {
+        let registerer = "tester".to_string();
+
+        // osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a:1000uatom,2000ukrw
+        // osmo1z7huy904a3yf3aj8mxt5z6shy7dezrlw5gduju:500ukrw
+        // osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks:300uatom
+        // osmo1jv65s3grqf6v6jl3dp4t6c9t9rk99cd80yhvld:100uatom,100ukrw
+        let merkle_root = "556cf80af5076e913ad43047ce75b914ef549f75065cd09f739545af14899214";
+
+        MultiDenomAirdrop {
+            creator: Addr::unchecked(&registerer),
+
+            total_amount: vec![coin(2000, "uatom"), coin(3000, "ukrw")],
+            total_claimed: vec![],
+            merkle_root: merkle_root.to_string(),
+
+            label: label.map(|v| format!("{registerer}/{v}")),
+            created_at,
+            closed_at: None,
+            starts_at: None,
+            expires_at: None,
+            beneficiary: None,
+        }
+    }
 }
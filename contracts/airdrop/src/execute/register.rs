@@ -1,10 +1,40 @@
-use crate::airdrop::{BearerAirdrop, OpenAirdrop};
+use crate::airdrop::{
+    check_period, coins_to_string, normalize_coins, BearerAirdrop, MultiDenomAirdrop, OpenAirdrop,
+    VestingAirdrop,
+};
 use crate::error::ContractError;
 use crate::state::{airdrops, save_label, LATEST_AIRDROP_ID};
-use crate::verify::{pub_to_addr, sha256_digest};
-use cosmwasm_std::{attr, Attribute, Binary, DepsMut, Env, MessageInfo, Response, Uint128};
+use crate::verify::verify_signer;
+use cosmwasm_std::{
+    attr, Addr, Api, Attribute, Binary, DepsMut, Env, MessageInfo, Response, Uint128,
+};
 use ibcx_interface::airdrop::{AirdropType, RegisterPayload};
 
+// claim period shared by all airdrop types
+struct Period {
+    starts_at: Option<u64>,
+    expires_at: Option<u64>,
+    beneficiary: Option<Addr>,
+}
+
+impl Period {
+    fn new(
+        api: &dyn Api,
+        env: &Env,
+        starts_at: Option<u64>,
+        expires_at: Option<u64>,
+        beneficiary: Option<String>,
+    ) -> Result<Self, ContractError> {
+        check_period(starts_at, expires_at, env.block.time.seconds())?;
+
+        Ok(Self {
+            starts_at,
+            expires_at,
+            beneficiary: beneficiary.map(|x| api.addr_validate(&x)).transpose()?,
+        })
+    }
+}
+
 pub fn register(
     deps: DepsMut,
     env: Env,
@@ -16,23 +46,75 @@ pub fn register(
             merkle_root,
             denom,
             label,
-        } => register_open(deps, env, info, merkle_root, denom, label),
+            starts_at,
+            expires_at,
+            beneficiary,
+        } => {
+            let period = Period::new(deps.api, &env, starts_at, expires_at, beneficiary)?;
+
+            register_open(deps, env, info, merkle_root, denom, label, period)
+        }
         RegisterPayload::Bearer {
             merkle_root,
             denom,
             label,
             signer_pub,
             signer_sig,
-        } => register_bearer(
-            deps,
-            env,
-            info,
+            starts_at,
+            expires_at,
+            beneficiary,
+        } => {
+            let period = Period::new(deps.api, &env, starts_at, expires_at, beneficiary)?;
+
+            register_bearer(
+                deps,
+                env,
+                info,
+                merkle_root,
+                denom,
+                label,
+                signer_pub,
+                signer_sig,
+                period,
+            )
+        }
+        RegisterPayload::Vesting {
             merkle_root,
             denom,
+            start_time,
+            end_time,
+            cliff_time,
             label,
-            signer_pub,
-            signer_sig,
-        ),
+            starts_at,
+            expires_at,
+            beneficiary,
+        } => {
+            let period = Period::new(deps.api, &env, starts_at, expires_at, beneficiary)?;
+
+            register_vesting(
+                deps,
+                env,
+                info,
+                merkle_root,
+                denom,
+                start_time,
+                end_time,
+                cliff_time,
+                label,
+                period,
+            )
+        }
+        RegisterPayload::MultiDenom {
+            merkle_root,
+            label,
+            starts_at,
+            expires_at,
+            beneficiary,
+        } => {
+            let period = Period::new(deps.api, &env, starts_at, expires_at, beneficiary)?;
+
+            register_multi_denom(deps, env, info, merkle_root, label, period)
+        }
     }
 }
 
@@ -55,6 +137,7 @@ fn register_open(
     merkle_root: String,
     denom: String,
     label: Option<String>,
+    period: Period,
 ) -> Result<Response, ContractError> {
     // check merkle root length
     let mut root_buf: [u8; 32] = [0; 32];
@@ -80,6 +163,9 @@ fn register_open(
         label,
         created_at: env.block.height,
         closed_at: None,
+        starts_at: period.starts_at,
+        expires_at: period.expires_at,
+        beneficiary: period.beneficiary,
     };
 
     // event attributes
@@ -117,6 +203,7 @@ fn register_bearer(
     label: Option<String>,
     signer_pub: String,
     signer_sig: String,
+    period: Period,
 ) -> Result<Response, ContractError> {
     // check merkle root length
     let mut root_buf: [u8; 32] = [0; 32];
@@ -131,17 +218,7 @@ fn register_bearer(
     // verify signer_sig
     let signer_pub = hex::decode(signer_pub)?;
     let signer_sig = hex::decode(signer_sig)?;
-    // TODO: do we have to make prefix injectable?
-    let signer = pub_to_addr(signer_pub.clone().into(), "osmo")?;
-
-    let digest = sha256_digest(signer.as_bytes())?;
-
-    let verified =
-        deps.api
-            .secp256k1_verify(&digest, signer_sig.as_slice(), signer_pub.as_slice())?;
-    if !verified {
-        return Err(ContractError::invalid_signature("register"));
-    }
+    let signer = verify_signer(deps.api, "register", &signer_pub, &signer_sig)?;
 
     // make bearer airdrop
     let total_amount = cw_utils::must_pay(&info, &denom)?;
@@ -159,6 +236,9 @@ fn register_bearer(
         label,
         created_at: env.block.height,
         closed_at: None,
+        starts_at: period.starts_at,
+        expires_at: period.expires_at,
+        beneficiary: period.beneficiary,
     };
 
     // event attributes
@@ -172,6 +252,167 @@ fn register_bearer(
     Ok(Response::new().add_attributes(attrs))
 }
 
+fn register_vesting_event(id: u64, airdrop: VestingAirdrop) -> Vec<Attribute> {
+    vec![
+        attr("action", "register"),
+        attr("executor", &airdrop.creator),
+        attr("id", id.to_string()),
+        attr("type", AirdropType::Vesting),
+        attr("merkle_root", airdrop.merkle_root),
+        attr("total_amount", airdrop.total_amount.to_string()),
+        attr("start_time", airdrop.start_time.to_string()),
+        attr("end_time", airdrop.end_time.to_string()),
+        attr(
+            "cliff_time",
+            airdrop
+                .cliff_time
+                .map(|v| v.to_string())
+                .unwrap_or_default(),
+        ),
+        attr("label", airdrop.label.unwrap_or_default()),
+    ]
+}
+
+#[allow(clippy::too_many_arguments)]
+// vesting airdrop registerer
+fn register_vesting(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    merkle_root: String,
+    denom: String,
+    start_time: u64,
+    end_time: u64,
+    cliff_time: Option<u64>,
+    label: Option<String>,
+    period: Period,
+) -> Result<Response, ContractError> {
+    // check merkle root length
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(&merkle_root, &mut root_buf)?;
+
+    // check vesting schedule
+    if end_time <= start_time {
+        return Err(ContractError::InvalidArguments {
+            arg: "end_time".to_string(),
+            reason: "must be later than start_time".to_string(),
+        });
+    }
+    if cliff_time.is_some_and(|v| v < start_time || end_time < v) {
+        return Err(ContractError::InvalidArguments {
+            arg: "cliff_time".to_string(),
+            reason: "must be between start_time and end_time".to_string(),
+        });
+    }
+    if period.expires_at.is_some_and(|v| v < end_time) {
+        return Err(ContractError::InvalidArguments {
+            arg: "expires_at".to_string(),
+            reason: "must not be earlier than end_time".to_string(),
+        });
+    }
+
+    // fetch next airdrop id and increment it
+    let airdrop_id = LATEST_AIRDROP_ID.load(deps.storage)?;
+
+    // make label with tx sender
+    let label = label.map(|x| format!("{}/{x}", info.sender));
+
+    // make vesting airdrop
+    let total_amount = cw_utils::must_pay(&info, &denom)?;
+
+    let airdrop = VestingAirdrop {
+        creator: info.sender,
+
+        denom,
+        total_amount,
+        total_claimed: Uint128::zero(),
+        merkle_root,
+
+        start_time,
+        end_time,
+        cliff_time,
+
+        label,
+        created_at: env.block.height,
+        closed_at: None,
+        starts_at: period.starts_at,
+        expires_at: period.expires_at,
+        beneficiary: period.beneficiary,
+    };
+
+    // event attributes
+    let attrs = register_vesting_event(airdrop_id, airdrop.clone());
+
+    // apply to state (LABELS, AIRDROP, LATEST_AIRDROP_ID)
+    save_label(deps.storage, airdrop_id, &airdrop.label)?;
+    airdrops().save(deps.storage, airdrop_id, &airdrop.into())?;
+    LATEST_AIRDROP_ID.save(deps.storage, &(airdrop_id + 1))?;
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+fn register_multi_denom_event(id: u64, airdrop: MultiDenomAirdrop) -> Vec<Attribute> {
+    vec![
+        attr("action", "register"),
+        attr("executor", &airdrop.creator),
+        attr("id", id.to_string()),
+        attr("type", AirdropType::MultiDenom),
+        attr("merkle_root", airdrop.merkle_root),
+        attr("total_amount", coins_to_string(&airdrop.total_amount)),
+        attr("label", airdrop.label.unwrap_or_default()),
+    ]
+}
+
+fn register_multi_denom(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    merkle_root: String,
+    label: Option<String>,
+    period: Period,
+) -> Result<Response, ContractError> {
+    // check merkle root length
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(&merkle_root, &mut root_buf)?;
+
+    // fetch next airdrop id and increment it
+    let airdrop_id = LATEST_AIRDROP_ID.load(deps.storage)?;
+
+    // make label with tx sender
+    let label = label.map(|x| format!("{}/{x}", info.sender));
+
+    // make multi-denom airdrop with every attached coin
+    if info.funds.is_empty() {
+        return Err(cw_utils::PaymentError::NoFunds {}.into());
+    }
+    let total_amount = normalize_coins("funds", info.funds)?;
+
+    let airdrop = MultiDenomAirdrop {
+        creator: info.sender,
+
+        total_amount,
+        total_claimed: vec![],
+        merkle_root,
+
+        label,
+        created_at: env.block.height,
+        closed_at: None,
+        starts_at: period.starts_at,
+        expires_at: period.expires_at,
+        beneficiary: period.beneficiary,
+    };
+
+    // event attributes
+    let attrs = register_multi_denom_event(airdrop_id, airdrop.clone());
+
+    // apply to state (LABELS, AIRDROP, LATEST_AIRDROP_ID)
+    save_label(deps.storage, airdrop_id, &airdrop.label)?;
+    airdrops().save(deps.storage, airdrop_id, &airdrop.into())?;
+    LATEST_AIRDROP_ID.save(deps.storage, &(airdrop_id + 1))?;
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{
@@ -179,7 +420,7 @@ mod tests {
         testing::{
             mock_dependencies_with_balances, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
         },
-        OwnedDeps,
+        Addr, OwnedDeps,
     };
     use ibcx_interface::airdrop::{
         AirdropId, GetLabelResponse, InstantiateMsg, ListAirdropsQueryOptions, RegisterPayload,
@@ -188,9 +429,16 @@ mod tests {
     use crate::{
         airdrop::Airdrop,
         contract::instantiate,
+        error::ContractError,
         execute::{
-            register::{register_bearer_event, register_open_event},
-            tests::{mock_bearer_airdrop, mock_open_airdrop, Balances},
+            register::{
+                register_bearer_event, register_multi_denom_event, register_open_event,
+                register_vesting_event,
+            },
+            tests::{
+                mock_bearer_airdrop, mock_multi_denom_airdrop, mock_open_airdrop,
+                mock_vesting_airdrop, Balances,
+            },
         },
         query,
     };
@@ -322,4 +570,210 @@ mod tests {
         );
         assert_state(deps, expected_registerer, label, expected);
     }
+
+    #[test]
+    fn test_register_vesting() {
+        let env = mock_env();
+        let now = env.block.time.seconds();
+
+        let label = Some("test_vesting_airdrop");
+        let mock_airdrop = mock_vesting_airdrop(label, env.block.height, now);
+
+        let mut deps = mock_dependencies_with_balances(&[]);
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        let info = mock_info(
+            mock_airdrop.creator.as_str(),
+            &[coin(mock_airdrop.total_amount.u128(), &mock_airdrop.denom)],
+        );
+
+        let invalid_schedules = [
+            (now, now, None, "end_time"),
+            (now + 1, now, None, "end_time"),
+            (now, now + 1000, Some(now - 1), "cliff_time"),
+            (now, now + 1000, Some(now + 1001), "cliff_time"),
+        ];
+        for (start_time, end_time, cliff_time, arg) in invalid_schedules {
+            let payload = RegisterPayload::vesting(
+                &mock_airdrop.merkle_root,
+                &mock_airdrop.denom,
+                start_time,
+                end_time,
+                cliff_time,
+                label,
+            );
+
+            let err = register(deps.as_mut(), env.clone(), info.clone(), payload).unwrap_err();
+            assert!(matches!(err, ContractError::InvalidArguments { arg: v, .. } if v == arg));
+        }
+
+        let payload = RegisterPayload::vesting(
+            &mock_airdrop.merkle_root,
+            &mock_airdrop.denom,
+            mock_airdrop.start_time,
+            mock_airdrop.end_time,
+            mock_airdrop.cliff_time,
+            label,
+        );
+
+        let expected_registerer = mock_airdrop.creator.to_string();
+        let expected: (u64, Airdrop) = (0u64, mock_airdrop.into());
+
+        let resp = register(deps.as_mut(), env, info, payload).unwrap();
+        assert_eq!(
+            resp.attributes,
+            register_vesting_event(expected.0, expected.1.clone().unwrap_vesting().unwrap())
+        );
+        assert_state(deps, expected_registerer, label, expected);
+    }
+
+    #[test]
+    fn test_register_period() {
+        let env = mock_env();
+        let now = env.block.time.seconds();
+
+        let mut mock_airdrop = mock_open_airdrop(None, env.block.height);
+
+        let mut deps = mock_dependencies_with_balances(&[]);
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        let info = mock_info(
+            mock_airdrop.creator.as_str(),
+            &[coin(mock_airdrop.total_amount.u128(), &mock_airdrop.denom)],
+        );
+        let payload = RegisterPayload::open(&mock_airdrop.merkle_root, &mock_airdrop.denom, None);
+
+        let invalid_periods = [
+            (None, Some(now), "must be in the future"),
+            (
+                Some(now + 100),
+                Some(now + 100),
+                "must be later than starts_at",
+            ),
+        ];
+        for (starts_at, expires_at, reason) in invalid_periods {
+            let err = register(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                payload.clone().with_period(starts_at, expires_at, None),
+            )
+            .unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::InvalidArguments {
+                    arg: "expires_at".to_string(),
+                    reason: reason.to_string(),
+                }
+            );
+        }
+
+        // vesting must not expire before fully unlocked
+        let mock_vesting = mock_vesting_airdrop(None, env.block.height, now);
+        let err = register(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            RegisterPayload::vesting(
+                &mock_vesting.merkle_root,
+                &mock_vesting.denom,
+                mock_vesting.start_time,
+                mock_vesting.end_time,
+                mock_vesting.cliff_time,
+                None,
+            )
+            .with_period(None, Some(mock_vesting.end_time - 1), None),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidArguments {
+                arg: "expires_at".to_string(),
+                reason: "must not be earlier than end_time".to_string(),
+            }
+        );
+
+        mock_airdrop.starts_at = Some(now + 100);
+        mock_airdrop.expires_at = Some(now + 200);
+        mock_airdrop.beneficiary = Some(Addr::unchecked("beneficiary"));
+
+        register(
+            deps.as_mut(),
+            env,
+            info,
+            payload.with_period(Some(now + 100), Some(now + 200), Some("beneficiary")),
+        )
+        .unwrap();
+        assert_state(
+            deps,
+            mock_airdrop.creator.to_string(),
+            None,
+            (0, mock_airdrop.into()),
+        );
+    }
+
+    #[test]
+    fn test_register_multi_denom() {
+        let env = mock_env();
+
+        let label = Some("test_multi_denom_airdrop");
+        let mock_airdrop = mock_multi_denom_airdrop(label, env.block.height);
+
+        let mut deps = mock_dependencies_with_balances(&[]);
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        let payload = RegisterPayload::multi_denom(&mock_airdrop.merkle_root, label);
+
+        let err = register(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(mock_airdrop.creator.as_str(), &[]),
+            payload.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::PaymentError(cw_utils::PaymentError::NoFunds {})
+        );
+
+        // funds are sorted by denom
+        let info = mock_info(
+            mock_airdrop.creator.as_str(),
+            &[coin(3000, "ukrw"), coin(2000, "uatom")],
+        );
+
+        let expected_registerer = mock_airdrop.creator.to_string();
+        let expected: (u64, Airdrop) = (0u64, mock_airdrop.into());
+
+        let resp = register(deps.as_mut(), env, info, payload).unwrap();
+        assert_eq!(
+            resp.attributes,
+            register_multi_denom_event(
+                expected.0,
+                expected.1.clone().unwrap_multi_denom().unwrap()
+            )
+        );
+        assert_state(deps, expected_registerer, label, expected);
+    }
 }
@@ -0,0 +1,284 @@
+use crate::error::ContractError;
+use crate::execute::close::redeem;
+use crate::state::load_airdrop;
+use cosmwasm_std::{attr, Addr, Attribute, DepsMut, Env, MessageInfo, Response};
+use ibcx_interface::airdrop::{AirdropId, AirdropType};
+
+// expiry of vesting airdrops is never earlier than end_time, so nothing is locked at this point
+pub fn sweep(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: AirdropId,
+) -> Result<Response, ContractError> {
+    let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
+
+    // validation
+    if airdrop.closed_at().is_some() {
+        return Err(ContractError::AirdropClosed {});
+    }
+    if airdrop
+        .expires_at()
+        .is_none_or(|v| env.block.time.seconds() < v)
+    {
+        return Err(ContractError::AirdropNotExpired {});
+    }
+
+    let typ = airdrop.type_of();
+    let beneficiary = airdrop.beneficiary().clone();
+    let (send_msg, redeemed) = redeem(deps.storage, &env, (airdrop_id, airdrop), &beneficiary)?;
+
+    // response
+    let attrs = sweep_event(info.sender, typ, airdrop_id, &beneficiary, redeemed);
+
+    Ok(Response::new().add_messages(send_msg).add_attributes(attrs))
+}
+
+fn sweep_event(
+    sender: Addr,
+    typ: AirdropType,
+    id: u64,
+    beneficiary: &Addr,
+    redeem: impl ToString,
+) -> Vec<Attribute> {
+    vec![
+        attr("method", "sweep"),
+        attr("executor", sender),
+        attr("airdrop_type", typ),
+        attr("airdrop_id", id.to_string()),
+        attr("beneficiary", beneficiary),
+        attr("redeemed", redeem.to_string()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coins,
+        testing::{mock_dependencies, mock_env, mock_info},
+        Addr, BankMsg, SubMsg, Timestamp,
+    };
+    use ibcx_interface::airdrop::{AirdropId, AirdropType, InstantiateMsg};
+
+    use crate::{
+        airdrop::{coins_to_string, Airdrop},
+        contract::instantiate,
+        error::ContractError,
+        execute::tests::{
+            mock_bearer_airdrop, mock_multi_denom_airdrop, mock_open_airdrop, mock_vesting_airdrop,
+            register_airdrop,
+        },
+        state::airdrops,
+    };
+
+    use super::{sweep, sweep_event};
+
+    fn with_period(mut airdrop: Airdrop, expires_at: u64, beneficiary: Option<&str>) -> Airdrop {
+        let beneficiary = beneficiary.map(Addr::unchecked);
+        match &mut airdrop {
+            Airdrop::Open(inner) => {
+                inner.expires_at = Some(expires_at);
+                inner.beneficiary = beneficiary;
+            }
+            Airdrop::Bearer(inner) => {
+                inner.expires_at = Some(expires_at);
+                inner.beneficiary = beneficiary;
+            }
+            Airdrop::Vesting(inner) => {
+                inner.expires_at = Some(expires_at);
+                inner.beneficiary = beneficiary;
+            }
+            Airdrop::MultiDenom(inner) => {
+                inner.expires_at = Some(expires_at);
+                inner.beneficiary = beneficiary;
+            }
+        }
+        airdrop
+    }
+
+    #[test]
+    fn test_sweep() {
+        let env = mock_env();
+        let now = env.block.time.seconds();
+        let expires_at = now + 2000;
+
+        for (airdrop_type, beneficiary) in [
+            (AirdropType::Open, None),
+            (AirdropType::Bearer, Some("beneficiary")),
+            (AirdropType::Vesting, Some("beneficiary")),
+            (AirdropType::MultiDenom, Some("beneficiary")),
+        ] {
+            let mut deps = mock_dependencies();
+
+            instantiate(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("creator", &[]),
+                InstantiateMsg {},
+            )
+            .unwrap();
+
+            let (airdrop, sign) = match airdrop_type {
+                AirdropType::Open => (mock_open_airdrop(None, env.block.height).into(), None),
+                AirdropType::Bearer => {
+                    let (airdrop, sign) = mock_bearer_airdrop(None, env.block.height);
+                    (airdrop.into(), Some(sign))
+                }
+                AirdropType::Vesting => (
+                    mock_vesting_airdrop(None, env.block.height, now).into(),
+                    None,
+                ),
+                AirdropType::MultiDenom => (
+                    mock_multi_denom_airdrop(None, env.block.height).into(),
+                    None,
+                ),
+            };
+            let airdrop = with_period(airdrop, expires_at, beneficiary);
+            let total_amount = match &airdrop {
+                Airdrop::Open(inner) => coins(inner.total_amount.u128(), &inner.denom),
+                Airdrop::Bearer(inner) => coins(inner.total_amount.u128(), &inner.denom),
+                Airdrop::Vesting(inner) => coins(inner.total_amount.u128(), &inner.denom),
+                Airdrop::MultiDenom(inner) => inner.total_amount.clone(),
+            };
+            register_airdrop(deps.as_mut(), env.clone(), airdrop, sign);
+
+            // not expired yet
+            let mut sweep_env = env.clone();
+            sweep_env.block.time = Timestamp::from_seconds(expires_at - 1);
+            let err = sweep(
+                deps.as_mut(),
+                sweep_env.clone(),
+                mock_info("anyone", &[]),
+                AirdropId::Id(0),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::AirdropNotExpired {});
+
+            // anyone can sweep after expiry
+            sweep_env.block.time = Timestamp::from_seconds(expires_at);
+            let res = sweep(
+                deps.as_mut(),
+                sweep_env.clone(),
+                mock_info("anyone", &[]),
+                AirdropId::Id(0),
+            )
+            .unwrap();
+
+            let beneficiary = Addr::unchecked(beneficiary.unwrap_or("tester"));
+            assert_eq!(
+                res.messages,
+                vec![SubMsg::new(BankMsg::Send {
+                    to_address: beneficiary.to_string(),
+                    amount: total_amount.clone(),
+                })]
+            );
+            assert_eq!(
+                res.attributes,
+                sweep_event(
+                    Addr::unchecked("anyone"),
+                    airdrop_type.clone(),
+                    0,
+                    &beneficiary,
+                    match airdrop_type {
+                        AirdropType::MultiDenom => coins_to_string(&total_amount),
+                        _ => total_amount[0].amount.to_string(),
+                    }
+                )
+            );
+
+            let closed_at = match airdrops().load(&deps.storage, 0).unwrap() {
+                Airdrop::Open(inner) => inner.closed_at,
+                Airdrop::Bearer(inner) => inner.closed_at,
+                Airdrop::Vesting(inner) => inner.closed_at,
+                Airdrop::MultiDenom(inner) => inner.closed_at,
+            };
+            assert_eq!(closed_at, Some(sweep_env.block.height));
+
+            // already swept
+            let err = sweep(
+                deps.as_mut(),
+                sweep_env,
+                mock_info("anyone", &[]),
+                AirdropId::Id(0),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::AirdropClosed {});
+        }
+    }
+
+    #[test]
+    fn test_sweep_without_expiry() {
+        let env = mock_env();
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        let airdrop = mock_open_airdrop(None, env.block.height);
+        register_airdrop(deps.as_mut(), env.clone(), airdrop.into(), None);
+
+        let err = sweep(
+            deps.as_mut(),
+            env,
+            mock_info("anyone", &[]),
+            AirdropId::Id(0),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::AirdropNotExpired {});
+    }
+
+    #[test]
+    fn test_sweep_fully_claimed() {
+        let env = mock_env();
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        let mut airdrop = mock_open_airdrop(None, env.block.height);
+        airdrop.expires_at = Some(env.block.time.seconds() + 100);
+        register_airdrop(deps.as_mut(), env.clone(), airdrop.into(), None);
+
+        let mut airdrop = airdrops()
+            .load(&deps.storage, 0)
+            .unwrap()
+            .unwrap_open()
+            .unwrap();
+        airdrop.total_claimed = airdrop.total_amount;
+        airdrops()
+            .save(&mut deps.storage, 0, &airdrop.into())
+            .unwrap();
+
+        // nothing left to send
+        let mut sweep_env = env;
+        sweep_env.block.time = sweep_env.block.time.plus_seconds(100);
+        let res = sweep(
+            deps.as_mut(),
+            sweep_env,
+            mock_info("anyone", &[]),
+            AirdropId::Id(0),
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(
+            res.attributes,
+            sweep_event(
+                Addr::unchecked("anyone"),
+                AirdropType::Open,
+                0,
+                &Addr::unchecked("tester"),
+                "0"
+            )
+        );
+    }
+}
@@ -0,0 +1,228 @@
+use crate::error::ContractError;
+use crate::state::{airdrops, load_airdrop};
+use cosmwasm_std::{attr, DepsMut, MessageInfo, Response};
+use ibcx_interface::airdrop::{AirdropId, AirdropType};
+
+// replaces the merkle root of open airdrop. leaves of the new root must hold cumulative amounts,
+// so previous claimers can claim the increment only. attached funds are added to the airdrop
+pub fn update_root(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: AirdropId,
+    merkle_root: String,
+) -> Result<Response, ContractError> {
+    let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
+
+    // validation
+    let mut airdrop = airdrop.unwrap_open()?;
+    if airdrop.creator != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if airdrop.closed_at.is_some() {
+        return Err(ContractError::AirdropClosed {});
+    }
+
+    // check merkle root length
+    let mut root_buf: [u8; 32] = [0; 32];
+    hex::decode_to_slice(&merkle_root, &mut root_buf)?;
+
+    let additional_funds = cw_utils::may_pay(&info, &airdrop.denom)?;
+    airdrop.total_amount = airdrop.total_amount.checked_add(additional_funds)?;
+
+    let prev_root = airdrop.merkle_root;
+    airdrop.merkle_root = merkle_root;
+
+    // response
+    let attrs = vec![
+        attr("method", "update_root"),
+        attr("executor", info.sender),
+        attr("airdrop_type", AirdropType::Open),
+        attr("airdrop_id", airdrop_id.to_string()),
+        attr("prev_merkle_root", prev_root),
+        attr("merkle_root", &airdrop.merkle_root),
+        attr("amount", additional_funds),
+    ];
+
+    // apply states
+    airdrops().save(deps.storage, airdrop_id, &airdrop.into())?;
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coins,
+        testing::{mock_dependencies, mock_env, mock_info},
+        BankMsg, SubMsg, Uint128,
+    };
+    use ibcx_interface::airdrop::{AirdropId, ClaimPayload, InstantiateMsg};
+
+    use crate::{
+        contract::instantiate,
+        error::ContractError,
+        execute::{
+            claim,
+            tests::{mock_bearer_airdrop, mock_open_airdrop, register_airdrop},
+        },
+        state::{airdrops, CLAIM_LOGS},
+    };
+
+    use super::update_root;
+
+    const CLAIMER: &str = "osmo15wgtkntdf26hqan77g0kdsldcxjddypx5ny5a6";
+    const NEWCOMER: &str = "osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a";
+
+    // osmo15wgtkntdf26hqan77g0kdsldcxjddypx5ny5a6:1000
+    // osmo1z7huy904a3yf3aj8mxt5z6shy7dezrlw5gduju:500
+    const ROOT: &str = "806b77d35677d481ca65bc86308424032adb08c870cba01e62390470610901c3";
+
+    // osmo15wgtkntdf26hqan77g0kdsldcxjddypx5ny5a6:1500
+    // osmo1z7huy904a3yf3aj8mxt5z6shy7dezrlw5gduju:500
+    // osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a:700
+    const NEW_ROOT: &str = "30060a3b10abc05748a1712728be4f5efef2e92d294c46678ec176f1852858a6";
+
+    fn open_claim(account: &str, amount: u128, proof: &[&str]) -> ClaimPayload {
+        ClaimPayload::open_id(0, amount, Some(account), proof)
+    }
+
+    #[test]
+    fn test_update_root() {
+        let env = mock_env();
+        let mut deps = mock_dependencies();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        let mut airdrop = mock_open_airdrop(None, env.block.height);
+        airdrop.merkle_root = ROOT.to_string();
+        let total_amount = airdrop.total_amount;
+        register_airdrop(deps.as_mut(), env.clone(), airdrop.into(), None);
+
+        let (airdrop, sign) = mock_bearer_airdrop(None, env.block.height);
+        register_airdrop(deps.as_mut(), env.clone(), airdrop.into(), Some(sign));
+
+        claim(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            open_claim(
+                CLAIMER,
+                1000,
+                &["a1596fa65978906623d9e9919a101586b727569cf7213897769aa0946f5bf05a"],
+            ),
+        )
+        .unwrap();
+
+        let cases = [
+            ("anyone", 0, NEW_ROOT, Some(ContractError::Unauthorized {})),
+            (
+                "tester",
+                1,
+                NEW_ROOT,
+                Some(ContractError::invalid_airdrop_type("open", "bearer")),
+            ),
+            (
+                "tester",
+                0,
+                "30060a3b",
+                Some(ContractError::FromHexError(
+                    hex::FromHexError::InvalidStringLength,
+                )),
+            ),
+            ("tester", 0, NEW_ROOT, None),
+        ];
+        for (sender, id, root, expect_err) in cases {
+            let resp = update_root(
+                deps.as_mut(),
+                mock_info(sender, &coins(1000, "ukrw")),
+                AirdropId::id(id),
+                root.to_string(),
+            );
+            match expect_err {
+                Some(err) => assert_eq!(resp.unwrap_err(), err),
+                None => {
+                    resp.unwrap();
+                }
+            }
+        }
+
+        let airdrop = airdrops()
+            .load(&deps.storage, 0)
+            .unwrap()
+            .unwrap_open()
+            .unwrap();
+        assert_eq!(airdrop.merkle_root, NEW_ROOT);
+        assert_eq!(airdrop.total_amount, total_amount + Uint128::new(1000));
+
+        let claimer_proof: &[&str] = &[
+            "a1596fa65978906623d9e9919a101586b727569cf7213897769aa0946f5bf05a",
+            "ee47a7f2800b70b877356282ba332caaae3f14d37fc13aecaeea652f78ff7688",
+        ];
+        let already_claimed = |account: &str| ContractError::AlreadyClaimed {
+            airdrop_id: 0,
+            claim_key: account.to_string(),
+        };
+
+        let cases = [
+            // leaf of the previous root
+            (
+                open_claim(
+                    CLAIMER,
+                    1000,
+                    &["a1596fa65978906623d9e9919a101586b727569cf7213897769aa0946f5bf05a"],
+                ),
+                Err(already_claimed(CLAIMER)),
+            ),
+            // pays the increment only
+            (open_claim(CLAIMER, 1500, claimer_proof), Ok((CLAIMER, 500))),
+            (
+                open_claim(CLAIMER, 1500, claimer_proof),
+                Err(already_claimed(CLAIMER)),
+            ),
+            // new recipient
+            (
+                open_claim(
+                    NEWCOMER,
+                    700,
+                    &["456b88f440d43e810c8c0c148b5fc94ad70b466cc57f64bd4f5aea37931887a6"],
+                ),
+                Ok((NEWCOMER, 700)),
+            ),
+        ];
+        for (payload, expected) in cases {
+            let resp = claim(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("anyone", &[]),
+                payload,
+            );
+            match expected {
+                Ok((account, amount)) => assert_eq!(
+                    resp.unwrap().messages,
+                    vec![SubMsg::new(BankMsg::Send {
+                        to_address: account.to_string(),
+                        amount: coins(amount, "ukrw"),
+                    })]
+                ),
+                Err(err) => assert_eq!(resp.unwrap_err(), err),
+            }
+        }
+
+        // claim logs keep the cumulative amount
+        let claimed = CLAIM_LOGS.load(&deps.storage, (0, CLAIMER)).unwrap();
+        assert_eq!(claimed, Uint128::new(1500));
+
+        let airdrop = airdrops()
+            .load(&deps.storage, 0)
+            .unwrap()
+            .unwrap_open()
+            .unwrap();
+        assert_eq!(airdrop.total_claimed, Uint128::new(1500 + 700));
+    }
+}
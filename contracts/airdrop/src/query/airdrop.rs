@@ -1,8 +1,8 @@
-use cosmwasm_std::{Deps, StdResult};
+use cosmwasm_std::{Coin, Deps, StdResult, Uint128};
 use ibcx_interface::{
     airdrop::{
-        AirdropId, GetAirdropResponse, LatestAirdropResponse, ListAirdropsQueryOptions,
-        ListAirdropsResponse,
+        AirdropId, GetAirdropResponse, GetAirdropStatsResponse, LatestAirdropResponse,
+        ListAirdropsQueryOptions, ListAirdropsResponse,
     },
     range_option,
 };
@@ -10,13 +10,79 @@ use ibcx_interface::{
 use crate::{
     airdrop::Airdrop,
     error::ContractError,
-    state::{airdrops, load_airdrop, LABELS, LATEST_AIRDROP_ID},
+    state::{airdrops, load_airdrop, CLAIM_STATS, LABELS, LATEST_AIRDROP_ID},
 };
 
 pub fn get_airdrop(deps: Deps, id: AirdropId) -> Result<GetAirdropResponse, ContractError> {
     Ok(Airdrop::to_resp(load_airdrop(deps.storage, id)?))
 }
 
+pub fn get_airdrop_stats(
+    deps: Deps,
+    id: AirdropId,
+) -> Result<GetAirdropStatsResponse, ContractError> {
+    let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
+    let stats = CLAIM_STATS
+        .may_load(deps.storage, airdrop_id)?
+        .unwrap_or_default();
+
+    let single = |denom: &str, total: Uint128, claimed: Uint128, closed: bool| {
+        let remaining = if closed {
+            Uint128::zero()
+        } else {
+            total.checked_sub(claimed)?
+        };
+        let to_coins = |amount: Uint128| match amount.is_zero() {
+            true => vec![],
+            false => vec![Coin::new(amount.u128(), denom)],
+        };
+
+        Ok::<_, ContractError>((to_coins(claimed), to_coins(remaining)))
+    };
+
+    let (claimed, remaining) = match airdrop {
+        Airdrop::Open(inner) => single(
+            &inner.denom,
+            inner.total_amount,
+            inner.total_claimed,
+            inner.closed_at.is_some(),
+        )?,
+        Airdrop::Bearer(inner) => single(
+            &inner.denom,
+            inner.total_amount,
+            inner.total_claimed,
+            inner.closed_at.is_some(),
+        )?,
+        Airdrop::Vesting(inner) => single(
+            &inner.denom,
+            inner.total_amount,
+            inner.total_claimed,
+            inner.closed_at.is_some(),
+        )?,
+        Airdrop::MultiDenom(inner) => {
+            let remaining = match inner.closed_at {
+                Some(_) => vec![],
+                None => inner.unclaimed()?,
+            };
+            let claimed = inner
+                .total_claimed
+                .into_iter()
+                .filter(|c| !c.amount.is_zero())
+                .collect();
+
+            (claimed, remaining)
+        }
+    };
+
+    Ok(GetAirdropStatsResponse {
+        id: airdrop_id,
+        claimants: stats.claimants,
+        claimed,
+        remaining,
+        last_claimed_at: stats.last_claimed_at,
+    })
+}
+
 pub fn latest_airdrop_id(deps: Deps) -> Result<LatestAirdropResponse, ContractError> {
     Ok(LatestAirdropResponse(LATEST_AIRDROP_ID.load(deps.storage)?))
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::{Deps, Order, StdResult};
+use cosmwasm_std::{Deps, Env, Order, Storage, Uint128};
 use cw_storage_plus::Bound;
 use ibcx_interface::{
     airdrop::{AirdropId, ClaimPayload, GetClaimResponse, ListClaimsResponse, VerifyClaimResponse},
@@ -8,35 +8,96 @@ use ibcx_interface::{
 };
 
 use crate::{
+    airdrop::{assert_in_period, normalize_coins, Airdrop},
     error::ContractError,
-    state::{load_airdrop, CLAIM_LOGS},
-    verify::{sha256_digest, verify_merkle_proof},
+    state::{
+        airdrops, load_airdrop, CLAIMS_BY_ACCOUNT, CLAIM_LOGS, MULTI_DENOM_CLAIM_LOGS,
+        REVOKED_CLAIMS, VESTING_ALLOCATIONS,
+    },
+    verify::{
+        sha256_digest, verify_delegation, verify_merkle_proof, verify_multi_denom_merkle_proof,
+    },
 };
 
-pub fn get_claim(
-    deps: Deps,
-    id: AirdropId,
+// unlocked amount which is not claimed yet. only vesting airdrops have it
+fn calc_claimable(
+    storage: &dyn Storage,
+    now: u64,
+    (airdrop_id, airdrop): (u64, &Airdrop),
+    claim_key: &str,
+    claimed: Uint128,
+) -> Result<Uint128, ContractError> {
+    let Airdrop::Vesting(inner) = airdrop else {
+        return Ok(Uint128::zero());
+    };
+    if inner.closed_at.is_some() {
+        return Ok(Uint128::zero());
+    }
+
+    let allocation = VESTING_ALLOCATIONS.may_load(storage, (airdrop_id, claim_key))?;
+
+    match allocation {
+        Some(allocation) => Ok(inner.unlocked(allocation, now)?.checked_sub(claimed)?),
+        None => Ok(Uint128::zero()),
+    }
+}
+
+fn to_claim_resp(
+    storage: &dyn Storage,
+    now: u64,
+    (airdrop_id, airdrop): (u64, &Airdrop),
     claim_key: String,
 ) -> Result<GetClaimResponse, ContractError> {
-    let (airdrop_id, _) = load_airdrop(deps.storage, id)?;
+    // multi-denom airdrops log claimed coins instead of amount
+    if let Airdrop::MultiDenom(_) = airdrop {
+        let coins = MULTI_DENOM_CLAIM_LOGS.load(storage, (airdrop_id, &claim_key))?;
+
+        return Ok(GetClaimResponse {
+            id: airdrop_id,
+            amount: Uint128::zero(),
+            claimable: Uint128::zero(),
+            coins,
+            claim_key,
+        });
+    }
 
-    let claim = CLAIM_LOGS.load(deps.storage, (airdrop_id, &claim_key))?;
+    let claim = CLAIM_LOGS.load(storage, (airdrop_id, &claim_key))?;
+    let claimable = calc_claimable(storage, now, (airdrop_id, airdrop), &claim_key, claim)?;
 
     Ok(GetClaimResponse {
         id: airdrop_id,
         amount: claim,
+        claimable,
+        coins: vec![],
         claim_key,
     })
 }
 
+pub fn get_claim(
+    deps: Deps,
+    env: Env,
+    id: AirdropId,
+    claim_key: String,
+) -> Result<GetClaimResponse, ContractError> {
+    let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
+
+    to_claim_resp(
+        deps.storage,
+        env.block.time.seconds(),
+        (airdrop_id, &airdrop),
+        claim_key,
+    )
+}
+
 pub fn list_claims(
     deps: Deps,
+    env: Env,
     id: AirdropId,
     start_after: Option<String>,
     limit: Option<u32>,
     order: Option<RangeOrder>,
 ) -> Result<ListClaimsResponse, ContractError> {
-    let (airdrop_id, _) = load_airdrop(deps.storage, id)?;
+    let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
 
     let start = start_after.as_deref();
     let limit = get_and_check_limit(limit, MAX_LIMIT, DEFAULT_LIMIT)? as usize;
@@ -46,29 +107,100 @@ pub fn list_claims(
         Order::Descending => (None, start.map(Bound::exclusive)),
     };
 
+    if let Airdrop::MultiDenom(_) = airdrop {
+        let resps = MULTI_DENOM_CLAIM_LOGS
+            .prefix(airdrop_id)
+            .range(deps.storage, min, max, order)
+            .take(limit)
+            .map(|item| {
+                let (k, v) = item?;
+
+                Ok(GetClaimResponse {
+                    id: airdrop_id,
+                    amount: Uint128::zero(),
+                    claimable: Uint128::zero(),
+                    coins: v,
+                    claim_key: k,
+                })
+            })
+            .collect::<Result<_, ContractError>>()?;
+
+        return Ok(ListClaimsResponse(resps));
+    }
+
     let resps = CLAIM_LOGS
         .prefix(airdrop_id)
         .range(deps.storage, min, max, order)
         .take(limit)
         .map(|item| {
             let (k, v) = item?;
+            let claimable = calc_claimable(
+                deps.storage,
+                env.block.time.seconds(),
+                (airdrop_id, &airdrop),
+                &k,
+                v,
+            )?;
 
             Ok(GetClaimResponse {
                 id: airdrop_id,
                 amount: v,
+                claimable,
+                coins: vec![],
                 claim_key: k,
             })
         })
-        .collect::<StdResult<_>>()?;
+        .collect::<Result<_, ContractError>>()?;
+
+    Ok(ListClaimsResponse(resps))
+}
+
+// claims of the account across all airdrops
+pub fn list_claims_by_account(
+    deps: Deps,
+    env: Env,
+    account: String,
+    start_after: Option<(u64, String)>,
+    limit: Option<u32>,
+    order: Option<RangeOrder>,
+) -> Result<ListClaimsResponse, ContractError> {
+    let account = deps.api.addr_validate(&account)?;
+
+    let start = start_after.as_ref().map(|(id, k)| (*id, k.as_str()));
+    let limit = get_and_check_limit(limit, MAX_LIMIT, DEFAULT_LIMIT)? as usize;
+    let order = order.unwrap_or(RangeOrder::Asc).into();
+    let (min, max) = match order {
+        Order::Ascending => (start.map(Bound::exclusive), None),
+        Order::Descending => (None, start.map(Bound::exclusive)),
+    };
+
+    let resps = CLAIMS_BY_ACCOUNT
+        .prefix(account.as_str())
+        .keys(deps.storage, min, max, order)
+        .take(limit)
+        .map(|item| {
+            let (airdrop_id, claim_key) = item?;
+            let airdrop = airdrops().load(deps.storage, airdrop_id)?;
+
+            to_claim_resp(
+                deps.storage,
+                env.block.time.seconds(),
+                (airdrop_id, &airdrop),
+                claim_key,
+            )
+        })
+        .collect::<Result<_, ContractError>>()?;
 
     Ok(ListClaimsResponse(resps))
 }
 
 pub fn verify_claim(
     deps: Deps,
+    env: Env,
     payload: ClaimPayload,
 ) -> Result<VerifyClaimResponse, ContractError> {
     let resp = VerifyClaimResponse::default();
+    let now = env.block.time.seconds();
 
     match payload {
         ClaimPayload::Open {
@@ -83,6 +215,9 @@ pub fn verify_claim(
             if airdrop.closed_at.is_some() {
                 return Ok(resp.fail("airdrop is closed"));
             }
+            if let Err(e) = assert_in_period(airdrop.starts_at, airdrop.expires_at, now) {
+                return Ok(resp.fail(e));
+            }
 
             let verify_result =
                 verify_merkle_proof(&airdrop.merkle_root, proof, &account.unwrap(), amount);
@@ -91,6 +226,46 @@ pub fn verify_claim(
             }
         }
 
+        ClaimPayload::OpenOnBehalf {
+            airdrop: id,
+            amount,
+            account,
+            recipient,
+            account_pub,
+            recipient_sig,
+            merkle_proof: proof,
+        } => {
+            let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
+
+            let airdrop = airdrop.unwrap_open()?;
+            if airdrop.closed_at.is_some() {
+                return Ok(resp.fail("airdrop is closed"));
+            }
+            if let Err(e) = assert_in_period(airdrop.starts_at, airdrop.expires_at, now) {
+                return Ok(resp.fail(e));
+            }
+
+            let verify_result = verify_merkle_proof(&airdrop.merkle_root, proof, &account, amount);
+            if let Err(e) = verify_result {
+                return Ok(resp.fail(e));
+            }
+
+            // validate recipient
+            let verify_result = verify_delegation(
+                deps.api,
+                &env,
+                airdrop_id,
+                &account,
+                &recipient,
+                amount,
+                &hex::decode(account_pub)?,
+                &hex::decode(recipient_sig)?,
+            );
+            if let Err(e) = verify_result {
+                return Ok(resp.fail(e));
+            }
+        }
+
         ClaimPayload::Bearer {
             airdrop: id,
             amount,
@@ -100,15 +275,22 @@ pub fn verify_claim(
             merkle_proof: proof,
         } => {
             let account = account.unwrap();
-            let (_, airdrop) = load_airdrop(deps.storage, id)?;
+            let (airdrop_id, airdrop) = load_airdrop(deps.storage, id)?;
 
             let airdrop = airdrop.unwrap_bearer()?;
             if airdrop.closed_at.is_some() {
                 return Ok(resp.fail("airdrop is closed"));
             }
+            if let Err(e) = assert_in_period(airdrop.starts_at, airdrop.expires_at, now) {
+                return Ok(resp.fail(e));
+            }
+            if REVOKED_CLAIMS.has(deps.storage, (airdrop_id, &claim_hash)) {
+                return Ok(resp.revoked());
+            }
 
             // validate claim hash
-            let verify_result = verify_merkle_proof(&airdrop.merkle_root, proof, &account, amount);
+            let verify_result =
+                verify_merkle_proof(&airdrop.merkle_root, proof, &claim_hash, amount);
             if let Err(e) = verify_result {
                 return Ok(resp.fail(e));
             }
@@ -126,6 +308,59 @@ pub fn verify_claim(
                 return Ok(resp.fail("invalid claim signature"));
             }
         }
+
+        ClaimPayload::Vesting {
+            airdrop: id,
+            amount,
+            account,
+            merkle_proof: proof,
+        } => {
+            let (_, airdrop) = load_airdrop(deps.storage, id)?;
+
+            let airdrop = airdrop.unwrap_vesting()?;
+            if airdrop.closed_at.is_some() {
+                return Ok(resp.fail("airdrop is closed"));
+            }
+            if let Err(e) = assert_in_period(airdrop.starts_at, airdrop.expires_at, now) {
+                return Ok(resp.fail(e));
+            }
+            let Some(account) = account else {
+                return Ok(resp.fail("account is required"));
+            };
+
+            let verify_result = verify_merkle_proof(&airdrop.merkle_root, proof, &account, amount);
+            if let Err(e) = verify_result {
+                return Ok(resp.fail(e));
+            }
+        }
+
+        ClaimPayload::MultiDenom {
+            airdrop: id,
+            amount,
+            account,
+            merkle_proof: proof,
+        } => {
+            let (_, airdrop) = load_airdrop(deps.storage, id)?;
+
+            let airdrop = airdrop.unwrap_multi_denom()?;
+            if airdrop.closed_at.is_some() {
+                return Ok(resp.fail("airdrop is closed"));
+            }
+            if let Err(e) = assert_in_period(airdrop.starts_at, airdrop.expires_at, now) {
+                return Ok(resp.fail(e));
+            }
+
+            let Some(account) = account else {
+                return Ok(resp.fail("account is required"));
+            };
+
+            let verify_result = normalize_coins("amount", amount).and_then(|amount| {
+                verify_multi_denom_merkle_proof(&airdrop.merkle_root, proof, &account, &amount)
+            });
+            if let Err(e) = verify_result {
+                return Ok(resp.fail(e));
+            }
+        }
     }
 
     Ok(resp.ok())
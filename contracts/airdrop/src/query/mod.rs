@@ -2,6 +2,6 @@ mod airdrop;
 mod claim;
 mod label;
 
-pub use airdrop::{get_airdrop, latest_airdrop_id, list_airdrops};
-pub use claim::{get_claim, list_claims, verify_claim};
+pub use airdrop::{get_airdrop, get_airdrop_stats, latest_airdrop_id, list_airdrops};
+pub use claim::{get_claim, list_claims, list_claims_by_account, verify_claim};
 pub use label::{get_label, list_labels};
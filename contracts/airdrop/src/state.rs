@@ -1,5 +1,8 @@
-use crate::{airdrop::Airdrop, error::ContractError};
-use cosmwasm_std::{Addr, Storage, Uint128};
+use crate::{
+    airdrop::{Airdrop, ClaimBackfill, ClaimStats},
+    error::ContractError,
+};
+use cosmwasm_std::{Addr, Coin, Empty, Storage, Uint128};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use ibcx_interface::airdrop::AirdropId;
 
@@ -23,6 +26,8 @@ pub fn airdrops<'a>() -> IndexedMap<'a, u64, Airdrop, AirdropIndexes<'a>> {
             |_, k| match k {
                 Airdrop::Open(_) => "open",
                 Airdrop::Bearer(_) => "bearer",
+                Airdrop::Vesting(_) => "vesting",
+                Airdrop::MultiDenom(_) => "multi_denom",
             },
             "airdrops",
             "airdrops__by_type",
@@ -31,6 +36,8 @@ pub fn airdrops<'a>() -> IndexedMap<'a, u64, Airdrop, AirdropIndexes<'a>> {
             |_, k| match k {
                 Airdrop::Open(inner) => inner.creator.clone(),
                 Airdrop::Bearer(inner) => inner.creator.clone(),
+                Airdrop::Vesting(inner) => inner.creator.clone(),
+                Airdrop::MultiDenom(inner) => inner.creator.clone(),
             },
             "airdrops",
             "airdrops__by_creator",
@@ -45,9 +52,33 @@ pub const LATEST_AIRDROP_ID: Item<u64> = Item::new(LATEST_AIRDROP_KEY);
 pub const LABELS_PREFIX: &str = "labels";
 pub const LABELS: Map<&str, u64> = Map::new(LABELS_PREFIX);
 
+// claimed amount of each claim key. cumulative for vesting airdrops
 pub const CLAIM_LOGS_PREFIX: &str = "claim_logs";
 pub const CLAIM_LOGS: Map<(u64, &str), Uint128> = Map::new(CLAIM_LOGS_PREFIX);
 
+// verified allocation of vesting airdrop claimers. saved on the first claim
+pub const VESTING_ALLOCATIONS_PREFIX: &str = "vesting_allocations";
+pub const VESTING_ALLOCATIONS: Map<(u64, &str), Uint128> = Map::new(VESTING_ALLOCATIONS_PREFIX);
+
+// claimed coins of multi-denom airdrop claimers
+pub const MULTI_DENOM_CLAIM_LOGS_PREFIX: &str = "multi_denom_claim_logs";
+pub const MULTI_DENOM_CLAIM_LOGS: Map<(u64, &str), Vec<Coin>> =
+    Map::new(MULTI_DENOM_CLAIM_LOGS_PREFIX);
+
+// claim hashes of bearer airdrops revoked by the creator. height of revocation
+pub const REVOKED_CLAIMS_PREFIX: &str = "revoked_claims";
+pub const REVOKED_CLAIMS: Map<(u64, &str), u64> = Map::new(REVOKED_CLAIMS_PREFIX);
+
+pub const CLAIM_STATS_PREFIX: &str = "claim_stats";
+pub const CLAIM_STATS: Map<u64, ClaimStats> = Map::new(CLAIM_STATS_PREFIX);
+
+// secondary index of claim logs by claimer - (claimer, (airdrop_id, claim_key))
+pub const CLAIMS_BY_ACCOUNT_PREFIX: &str = "claims_by_account";
+pub const CLAIMS_BY_ACCOUNT: Map<(&str, (u64, &str)), Empty> = Map::new(CLAIMS_BY_ACCOUNT_PREFIX);
+
+pub const CLAIM_BACKFILL_KEY: &str = "claim_backfill";
+pub const CLAIM_BACKFILL: Item<ClaimBackfill> = Item::new(CLAIM_BACKFILL_KEY);
+
 pub fn load_airdrop(storage: &dyn Storage, id: AirdropId) -> Result<(u64, Airdrop), ContractError> {
     let airdrop_id = match id {
         AirdropId::Id(id) => id,
@@ -82,7 +113,9 @@ pub fn assert_not_claimed(
     id: u64,
     claim_key: &str,
 ) -> Result<(), ContractError> {
-    if CLAIM_LOGS.may_load(storage, (id, claim_key))?.is_some() {
+    if CLAIM_LOGS.has(storage, (id, claim_key))
+        || MULTI_DENOM_CLAIM_LOGS.has(storage, (id, claim_key))
+    {
         return Err(ContractError::AlreadyClaimed {
             airdrop_id: id,
             claim_key: claim_key.to_string(),
@@ -91,3 +124,85 @@ pub fn assert_not_claimed(
 
     Ok(())
 }
+
+pub fn assert_not_revoked(
+    storage: &dyn Storage,
+    id: u64,
+    claim_hash: &str,
+) -> Result<(), ContractError> {
+    if REVOKED_CLAIMS.has(storage, (id, claim_hash)) {
+        return Err(ContractError::ClaimRevoked {
+            airdrop_id: id,
+            claim_hash: claim_hash.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+// indexes the claim by claimer and updates the stats of the airdrop
+pub fn record_claim(
+    storage: &mut dyn Storage,
+    height: u64,
+    id: u64,
+    claim_key: &str,
+    claimer: &str,
+) -> Result<(), ContractError> {
+    let mut stats = CLAIM_STATS.may_load(storage, id)?.unwrap_or_default();
+
+    // the backfill counts the claim when it gets there
+    let pending = CLAIM_BACKFILL
+        .may_load(storage)?
+        .is_some_and(|v| v.is_pending(id, claim_key));
+
+    let key = (claimer, (id, claim_key));
+    if !CLAIMS_BY_ACCOUNT.has(storage, key) {
+        CLAIMS_BY_ACCOUNT.save(storage, key, &Empty {})?;
+        if !pending {
+            stats.claimants += 1;
+        }
+    }
+    stats.last_claimed_at = Some(height);
+
+    CLAIM_STATS.save(storage, id, &stats)?;
+
+    Ok(())
+}
+
+// starts the backfill if claims were logged before the stats existed
+pub fn start_claim_backfill(storage: &mut dyn Storage) -> Result<bool, ContractError> {
+    if CLAIM_BACKFILL.exists(storage)
+        || !CLAIM_STATS.is_empty(storage)
+        || (CLAIM_LOGS.is_empty(storage) && MULTI_DENOM_CLAIM_LOGS.is_empty(storage))
+    {
+        return Ok(false);
+    }
+
+    let backfill = ClaimBackfill {
+        until: LATEST_AIRDROP_ID.load(storage)?,
+        last: None,
+    };
+    CLAIM_BACKFILL.save(storage, &backfill)?;
+
+    Ok(true)
+}
+
+// counts a claim logged before the stats existed. claim keys of bearer airdrops are claim hashes,
+// so those claims are counted without being indexed. height of the claim is unknown
+pub fn backfill_claim(
+    storage: &mut dyn Storage,
+    id: u64,
+    claim_key: &str,
+    claimer: Option<&str>,
+) -> Result<(), ContractError> {
+    let mut stats = CLAIM_STATS.may_load(storage, id)?.unwrap_or_default();
+    stats.claimants += 1;
+
+    if let Some(claimer) = claimer {
+        CLAIMS_BY_ACCOUNT.save(storage, (claimer, (id, claim_key)), &Empty {})?;
+    }
+
+    CLAIM_STATS.save(storage, id, &stats)?;
+
+    Ok(())
+}
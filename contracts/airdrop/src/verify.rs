@@ -1,6 +1,7 @@
+use crate::airdrop::coins_to_string;
 use crate::error::ContractError;
 use bech32::ToBase32;
-use cosmwasm_std::{Binary, Uint128};
+use cosmwasm_std::{Api, Binary, Coin, Env, Uint128};
 use ripemd::{Digest, Ripemd160};
 use sha2::Sha256;
 
@@ -38,6 +39,57 @@ pub fn pub_to_addr(pub_key: Binary, prefix: &str) -> Result<String, ContractErro
     Ok(addr)
 }
 
+// verify the signer of bearer airdrop signed its own address - sign(sha256({signer}))
+pub fn verify_signer(
+    api: &dyn Api,
+    action: &str,
+    signer_pub: &[u8],
+    signer_sig: &[u8],
+) -> Result<String, ContractError> {
+    // TODO: do we have to make prefix injectable?
+    let signer = pub_to_addr(Binary::from(signer_pub), "osmo")?;
+
+    let digest = sha256_digest(signer.as_bytes())?;
+
+    if !api.secp256k1_verify(&digest, signer_sig, signer_pub)? {
+        return Err(ContractError::invalid_signature(action));
+    }
+
+    Ok(signer)
+}
+
+// verify the claimer authorized to send funds of the claim to the recipient.
+// chain id and contract address are signed too, so the signature can't be replayed elsewhere
+#[allow(clippy::too_many_arguments)]
+pub fn verify_delegation(
+    api: &dyn Api,
+    env: &Env,
+    airdrop_id: u64,
+    claimer: &str,
+    recipient: &str,
+    amount: Uint128,
+    account_pub: &[u8],
+    recipient_sig: &[u8],
+) -> Result<(), ContractError> {
+    // public key must belong to the claimer
+    let (prefix, _, _) = bech32::decode(claimer).map_err(|_| ContractError::InvalidPubKey {})?;
+    if pub_to_addr(Binary::from(account_pub), &prefix)? != claimer {
+        return Err(ContractError::InvalidPubKey {});
+    }
+
+    let digest_str = format!(
+        "{}/{}/{airdrop_id}/{claimer}/{recipient}/{amount}",
+        env.block.chain_id, env.contract.address
+    );
+    let digest = sha256_digest(digest_str.as_bytes())?;
+
+    if !api.secp256k1_verify(&digest, recipient_sig, account_pub)? {
+        return Err(ContractError::invalid_signature("claim_on_behalf"));
+    }
+
+    Ok(())
+}
+
 // verify merkle proof (from https://github.com/cosmwasm/cw-tokens/blob/master/contracts/cw20-merkle-airdrop/src/contract.rs)
 pub fn verify_merkle_proof(
     root: &str,
@@ -45,9 +97,25 @@ pub fn verify_merkle_proof(
     claim_proof: &str,
     amount: Uint128,
 ) -> Result<(), ContractError> {
-    let user_input = format!("{claim_proof}:{amount}");
+    verify_merkle_leaf(root, proof, &format!("{claim_proof}:{amount}"))
+}
 
-    let hash = sha256_digest(user_input.as_bytes())?;
+// leaf of multi-denom airdrop - {claim_proof}:{amount}{denom},{amount}{denom},... (sorted by denom)
+pub fn verify_multi_denom_merkle_proof(
+    root: &str,
+    proof: Vec<String>,
+    claim_proof: &str,
+    amount: &[Coin],
+) -> Result<(), ContractError> {
+    verify_merkle_leaf(
+        root,
+        proof,
+        &format!("{claim_proof}:{}", coins_to_string(amount)),
+    )
+}
+
+fn verify_merkle_leaf(root: &str, proof: Vec<String>, leaf: &str) -> Result<(), ContractError> {
+    let hash = sha256_digest(leaf.as_bytes())?;
 
     let hash = proof.into_iter().try_fold(hash, |hash, p| {
         let mut proof_buf = [0; 32];
@@ -69,7 +137,9 @@ pub fn verify_merkle_proof(
 
 #[cfg(test)]
 mod tests {
-    use super::verify_merkle_proof;
+    use cosmwasm_std::coin;
+
+    use super::{verify_merkle_proof, verify_multi_denom_merkle_proof};
 
     #[test]
     fn test_verify_merkle_proof() {
@@ -124,4 +194,28 @@ mod tests {
         )
         .unwrap_err();
     }
+
+    #[test]
+    fn test_verify_multi_denom_merkle_proof() {
+        let root = "556cf80af5076e913ad43047ce75b914ef549f75065cd09f739545af14899214";
+        let proof = || {
+            vec![
+                "77202e11a45baa5e62006f161101880bcd48aa6fddd352d1f01b3bf435eeb267".to_string(),
+                "65698c501f586287e1158483203c6e9cf005b34cf8574b8a760ccb50a4232baa".to_string(),
+            ]
+        };
+        let account = "osmo1z7huy904a3yf3aj8mxt5z6shy7dezrlw5gduju";
+
+        verify_multi_denom_merkle_proof(root, proof(), account, &[coin(500, "ukrw")]).unwrap();
+
+        verify_multi_denom_merkle_proof(root, proof(), account, &[coin(501, "ukrw")]).unwrap_err();
+
+        verify_multi_denom_merkle_proof(
+            root,
+            proof(),
+            account,
+            &[coin(0, "uatom"), coin(500, "ukrw")],
+        )
+        .unwrap_err();
+    }
 }
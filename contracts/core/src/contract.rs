@@ -8,7 +8,11 @@ use ibcx_interface::core::{
 use osmosis_std::types::osmosis::tokenfactory::v1beta1::{MsgCreateDenom, MsgCreateDenomResponse};
 
 use crate::error::ValidationError;
-use crate::state::{Config, Fee, StreamingFee, Units, CONFIG, FEE, INDEX_UNITS, TOTAL_SUPPLY};
+use crate::state::{
+    streaming_fee_rate, AccessPolicy, Config, Fee, FeeSchedule, FeeSplits, IndexMetadata,
+    PerformanceFee, StreamingFee, Units, ACCESS_POLICY, CONFIG, FEE, INDEX_UNITS, METADATA,
+    TOTAL_SUPPLY,
+};
 use crate::StdResult;
 use crate::{error::ContractError, CONTRACT_NAME, CONTRACT_VERSION, REPLY_ID_DENOM_CREATION};
 
@@ -32,14 +36,37 @@ pub fn instantiate(
         collector: deps.api.addr_validate(&msg.fee.collector)?,
         mint_fee: msg.fee.mint_fee,
         burn_fee: msg.fee.burn_fee,
-        streaming_fee: msg.fee.streaming_fee.map(|v| StreamingFee {
+        streaming_fee: msg
+            .fee
+            .streaming_fee
+            .map(|v| -> StdResult<_> {
+                Ok(StreamingFee {
+                    rate: streaming_fee_rate(&v)?,
+                    collected: vec![],
+                    last_collected_at: env.block.time.seconds(),
+                    freeze: v.freeze,
+                    mode: v.mode.unwrap_or_default(),
+                    pending_mint: Uint128::zero(),
+                })
+            })
+            .transpose()?,
+        performance_fee: msg.fee.performance_fee.map(|v| PerformanceFee {
             rate: v.rate,
+            price_sources: v.price_sources,
+            twap_window: v.twap_window,
+            high_water_mark: v.high_water_mark.unwrap_or_default(),
             collected: vec![],
-            last_collected_at: env.block.time.seconds(),
             freeze: v.freeze,
+            last_collected_at: env.block.time.seconds(),
         }),
+        splits: FeeSplits::from_payload(deps.api, msg.fee.splits)?,
+        schedule: FeeSchedule::from_payload(deps.api, msg.fee.schedule)?,
+        grace_period: msg.fee.grace_period.unwrap_or_default(),
+        grace: None,
     };
     fee.check_rates()?;
+    fee.check_splits()?;
+    fee.check_schedule()?;
 
     // config
     let config = Config {
@@ -49,6 +76,17 @@ pub fn instantiate(
         reserve_denom: msg.reserve_denom,
     };
 
+    // access policy
+    if let Some(access_policy) = msg.access_policy {
+        let access_policy = AccessPolicy::from_payload(deps.as_ref(), access_policy)?;
+        ACCESS_POLICY.save(deps.storage, &access_policy)?;
+    }
+
+    // metadata. applied to the denom after its creation
+    if let Some(metadata) = msg.metadata {
+        METADATA.save(deps.storage, &IndexMetadata::from_payload(metadata)?)?;
+    }
+
     // apply initial state
     FEE.save(deps.storage, &fee)?;
     CONFIG.save(deps.storage, &config)?;
@@ -72,7 +110,12 @@ pub fn instantiate(
 }
 
 #[entry_point]
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
     use crate::execute;
     use ExecuteMsg::*;
 
@@ -86,9 +129,17 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             Burn { .. } => collect(deps.storage, now_in_sec)?,
             Realize {} => collect(deps.storage, now_in_sec)?,
             Rebalance(RebalanceMsg::Init { .. }) => collect(deps.storage, now_in_sec)?,
+            Rebalance(RebalanceMsg::InitByWeights { .. }) => collect(deps.storage, now_in_sec)?,
+            Rebalance(RebalanceMsg::Trigger {}) => collect(deps.storage, now_in_sec)?,
             Gov(GovMsg::UpdateFeeStrategy(..)) => collect(deps.storage, now_in_sec)?,
             _ => {}
         };
+
+        // crystallize performance fee on supply changes too, so the collector can't pick
+        // the nav the high-water mark moves at. skipped reason is reported by realize only
+        if let Mint { .. } | Burn { .. } = msg {
+            execute::collect_performance_fee(deps.branch(), now_in_sec)?;
+        }
     }
 
     match msg {
@@ -98,7 +149,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             refund_to,
         } => execute::mint(deps, env, info, amount, receiver, refund_to),
         Burn { redeem_to } => execute::burn(deps, env, info, redeem_to),
-        Realize {} => execute::realize_streaming_fee(deps, info),
+        Realize {} => execute::realize_fee(deps, env, info),
 
         Gov(msg) => execute::handle_gov_msg(deps, env, info, msg),
         Rebalance(msg) => execute::handle_rebalance_msg(deps, env, info, msg),
@@ -106,7 +157,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
 }
 
 #[entry_point]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> StdResult<Response> {
     match msg.id {
         REPLY_ID_DENOM_CREATION => {
             let reply_data = msg.result.unwrap().data.unwrap();
@@ -116,7 +167,12 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
             config.index_denom = reply.new_token_denom;
             CONFIG.save(deps.storage, &config)?;
 
-            let resp = Response::new().add_attributes(vec![
+            let mut resp = Response::new();
+            if let Some(metadata) = METADATA.may_load(deps.storage)? {
+                resp = resp.add_message(metadata.to_msg(env.contract.address, &config.index_denom));
+            }
+
+            let resp = resp.add_attributes(vec![
                 attr("method", "reply_instantiate"),
                 attr("new_denom", config.index_denom),
             ]);
@@ -173,8 +229,25 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
             amount,
             funds,
             time,
-        } => to_binary(query::simulate_mint(deps, env, amount, funds, time)),
-        SimulateBurn { amount, time } => to_binary(query::simulate_burn(deps, env, amount, time)),
+            sender,
+        } => to_binary(query::simulate_mint(deps, env, amount, funds, time, sender)),
+        SimulateBurn {
+            amount,
+            time,
+            sender,
+        } => to_binary(query::simulate_burn(deps, env, amount, time, sender)),
+
+        // access
+        GetAccessPolicy {} => to_binary(query::get_access_policy(deps)),
+        GetMetadata {} => to_binary(query::get_metadata(deps)),
+        GetStrategy {} => to_binary(query::get_strategy(deps)),
+        GetKeeperReward { keeper } => to_binary(query::get_keeper_reward(deps, keeper)),
+        CheckAccess { action, address } => to_binary(query::check_access(deps, action, address)),
+        ListAllowlist {
+            start_after,
+            limit,
+            order,
+        } => to_binary(query::list_allowlist(deps, start_after, limit, order)),
     }
 }
 
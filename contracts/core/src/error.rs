@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Decimal, StdError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -17,6 +17,15 @@ pub enum RebalanceError {
 
     #[error("unable to finalize. reason: {0}")]
     UnableToFinalize(String),
+
+    #[error("strategy not set")]
+    StrategyNotSet,
+
+    #[error("trigger condition not met. next_trigger_at:{next_trigger_at:?}, drift:{drift}")]
+    TriggerConditionNotMet {
+        next_trigger_at: Option<u64>,
+        drift: Decimal,
+    },
 }
 
 impl RebalanceError {
@@ -96,6 +105,15 @@ pub enum ContractError {
     #[error("{0}")]
     CheckedMultiplyRatioError(#[from] cosmwasm_std::CheckedMultiplyRatioError),
 
+    #[error("{0}")]
+    CheckedMultiplyFractionError(#[from] cosmwasm_std::CheckedMultiplyFractionError),
+
+    #[error("{0}")]
+    DecimalRangeExceeded(#[from] cosmwasm_std::DecimalRangeExceeded),
+
+    #[error("ibcx-math: {0}")]
+    MathError(#[from] ibcx_math::MathError),
+
     #[error("{0}")]
     PaymentError(#[from] cw_utils::PaymentError),
 
@@ -117,6 +135,9 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized,
 
+    #[error("Access denied. address:{address}, reason:{reason}")]
+    AccessDenied { address: String, reason: String },
+
     #[error("Unknown reply id {id:?}")]
     UnknownReplyId { id: u64 },
 
@@ -1,20 +1,40 @@
-use cosmwasm_std::{attr, BankMsg, CosmosMsg, DepsMut, MessageInfo, Response, Storage};
+use cosmwasm_std::{
+    attr, coin, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, Storage,
+    Uint128,
+};
+use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgMint;
 
 use crate::{
-    assert_sender,
-    state::{FEE, INDEX_UNITS, TOTAL_SUPPLY},
+    error::ContractError,
+    state::{CONFIG, FEE, INDEX_UNITS, TOTAL_SUPPLY},
 };
 
 use crate::StdResult;
 
+pub fn make_fee_send_msgs(distributed: Vec<(Addr, Vec<Coin>)>) -> Vec<CosmosMsg> {
+    distributed
+        .into_iter()
+        .filter(|(_, amount)| !amount.is_empty())
+        .map(|(recipient, amount)| {
+            BankMsg::Send {
+                to_address: recipient.into_string(),
+                amount,
+            }
+            .into()
+        })
+        .collect()
+}
+
 pub fn collect_streaming_fee(storage: &mut dyn Storage, now_in_sec: u64) -> StdResult<()> {
     let mut fee = FEE.load(storage)?;
     let total_supply = TOTAL_SUPPLY.load(storage)?;
 
     if let Some(streaming_fee) = fee.streaming_fee.as_mut() {
         let index_units = INDEX_UNITS.load(storage)?;
-        let (new_index_units, _) = streaming_fee.collect(index_units, now_in_sec, total_supply)?;
+        let (new_index_units, new_total_supply) =
+            streaming_fee.collect_with_supply(index_units, now_in_sec, total_supply)?;
         INDEX_UNITS.save(storage, &new_index_units)?;
+        TOTAL_SUPPLY.save(storage, &new_total_supply)?;
     }
 
     FEE.save(storage, &fee)?;
@@ -22,28 +42,92 @@ pub fn collect_streaming_fee(storage: &mut dyn Storage, now_in_sec: u64) -> StdR
     Ok(())
 }
 
-pub fn realize_streaming_fee(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+// performance fee requires nav from the chain. if nav can't be priced (e.g. missing price source
+// of a denom added by rebalance), collection is skipped and the reason is returned instead of
+// failing, so it never blocks mint, burn or realization of other fees
+pub fn collect_performance_fee(deps: DepsMut, now_in_sec: u64) -> StdResult<Option<String>> {
+    let mut fee = FEE.load(deps.storage)?;
+
+    let performance_fee = match fee.performance_fee.as_mut() {
+        Some(v) if !v.freeze => v,
+        _ => return Ok(None),
+    };
+
+    let config = CONFIG.load(deps.storage)?;
+    let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
+    let index_units = INDEX_UNITS.load(deps.storage)?;
+
+    let nav = match performance_fee.query_nav(
+        &deps.querier,
+        &index_units,
+        &config.reserve_denom,
+        now_in_sec,
+    ) {
+        Ok(nav) => nav,
+        Err(err) => return Ok(Some(err.to_string())),
+    };
+    let (new_index_units, _) =
+        performance_fee.collect(index_units, nav, now_in_sec, total_supply)?;
+
+    INDEX_UNITS.save(deps.storage, &new_index_units)?;
+    FEE.save(deps.storage, &fee)?;
+
+    Ok(None)
+}
+
+pub fn realize_fee(mut deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    if !FEE.load(deps.storage)?.can_realize(&info.sender) {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let performance_fee_skipped = collect_performance_fee(deps.branch(), env.block.time.seconds())?;
+
     let mut fee = FEE.load(deps.storage)?;
-    assert_sender(&fee.collector, &info.sender)?;
 
     let mut msgs: Vec<CosmosMsg> = vec![];
 
-    // collect streaming fee
-    if let Some(streaming_fee) = fee.streaming_fee.as_mut() {
-        msgs.push(
-            BankMsg::Send {
-                to_address: fee.collector.to_string(),
-                amount: streaming_fee.collected.clone(),
-            }
-            .into(),
-        );
+    // distribute streaming fee to recipients
+    if let Some(streaming_fee) = fee.streaming_fee.clone() {
+        let mut collected = streaming_fee.collected;
+
+        // mint accrued index tokens first
+        if !streaming_fee.pending_mint.is_zero() {
+            let config = CONFIG.load(deps.storage)?;
+            let minted = coin(streaming_fee.pending_mint.u128(), config.index_denom);
+
+            msgs.push(
+                MsgMint {
+                    sender: env.contract.address.to_string(),
+                    mint_to_address: env.contract.address.to_string(),
+                    amount: Some(minted.clone().into()),
+                }
+                .into(),
+            );
+            collected.push(minted);
+        }
 
+        let distributed = fee.distribute(&fee.splits.streaming, &collected)?;
+        msgs.extend(make_fee_send_msgs(distributed));
+
+        let streaming_fee = fee.streaming_fee.as_mut().unwrap();
         streaming_fee.collected = vec![];
+        streaming_fee.pending_mint = Uint128::zero();
+    }
+
+    // distribute performance fee to recipients
+    if let Some(collected) = fee.performance_fee.as_ref().map(|v| v.collected.clone()) {
+        let distributed = fee.distribute(&fee.splits.performance, &collected)?;
+        msgs.extend(make_fee_send_msgs(distributed));
+
+        fee.performance_fee.as_mut().unwrap().collected = vec![];
     }
 
     FEE.save(deps.storage, &fee)?;
 
-    let attrs = vec![attr("method", "realize"), attr("executor", info.sender)];
+    let mut attrs = vec![attr("method", "realize"), attr("executor", info.sender)];
+    if let Some(reason) = performance_fee_skipped {
+        attrs.push(attr("performance_fee_skipped", reason));
+    }
 
     Ok(Response::new().add_messages(msgs).add_attributes(attrs))
 }
@@ -54,17 +138,22 @@ mod tests {
 
     use cosmwasm_std::{
         attr, coin,
-        testing::{mock_info, MockStorage},
+        testing::{mock_env, mock_info, MockStorage},
         Addr, BankMsg, Decimal, StdError, SubMsg, Uint128,
     };
+    use ibcx_interface::core::StreamingFeeMode;
+    use osmosis_std::types::osmosis::tokenfactory::v1beta1::MsgMint;
 
     use crate::{
         error::ContractError,
-        state::{Fee, StreamingFee, Units, FEE, INDEX_UNITS, TOTAL_SUPPLY},
+        state::{
+            tests::StateBuilder, Config, Fee, FeeSplits, PerformanceFee, StreamingFee, Units, FEE,
+            INDEX_UNITS, TOTAL_SUPPLY,
+        },
         test::mock_dependencies,
     };
 
-    use super::{collect_streaming_fee, realize_streaming_fee};
+    use super::{collect_performance_fee, collect_streaming_fee, realize_fee};
 
     #[test]
     fn test_collect_streaming_fee() {
@@ -130,7 +219,120 @@ mod tests {
     }
 
     #[test]
-    fn test_realize_streaming_fee() {
+    fn test_collect_performance_fee() {
+        let mut deps = mock_dependencies();
+        deps.querier
+            .stargate
+            .register_arithmetic_twap(vec![(1, "2")]);
+
+        StateBuilder::default()
+            .with_config(Config {
+                reserve_denom: "uosmo".to_string(),
+                ..Default::default()
+            })
+            .with_fee(Fee {
+                collector: Addr::unchecked("collector"),
+                performance_fee: Some(PerformanceFee {
+                    rate: Decimal::percent(20),
+                    price_sources: vec![("uatom".to_string(), 1)],
+                    twap_window: 3600,
+                    high_water_mark: Decimal::percent(200),
+                    ..Default::default()
+                }),
+                splits: FeeSplits {
+                    performance: vec![(Addr::unchecked("treasury"), Decimal::one())],
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .add_index_unit("uatom", "1.0")
+            .add_index_unit("uosmo", "1.0")
+            .with_total_supply(1000)
+            .build(deps.as_mut().storage);
+
+        // nav 3.0 -> fee 0.2 (20% of 1.0)
+        collect_performance_fee(deps.as_mut(), 100).unwrap();
+
+        let performance_fee = FEE
+            .load(deps.as_ref().storage)
+            .unwrap()
+            .performance_fee
+            .unwrap();
+        assert_eq!(performance_fee.high_water_mark, Decimal::percent(280));
+        assert_eq!(
+            performance_fee.collected,
+            vec![coin(66, "uatom"), coin(66, "uosmo")]
+        );
+
+        // realize sends crystallized fee to recipients of performance split
+        let res = realize_fee(deps.as_mut(), mock_env(), mock_info("collector", &[])).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "treasury".to_string(),
+                amount: vec![coin(66, "uatom"), coin(66, "uosmo")],
+            })]
+        );
+    }
+
+    #[test]
+    fn test_realize_fee_with_unpriced_nav() {
+        let mut deps = mock_dependencies();
+        deps.querier
+            .stargate
+            .register_arithmetic_twap(vec![(1, "2")]);
+
+        // ujpy is added by rebalance and has no price source
+        StateBuilder::default()
+            .with_config(Config {
+                reserve_denom: "uosmo".to_string(),
+                ..Default::default()
+            })
+            .with_fee(Fee {
+                collector: Addr::unchecked("collector"),
+                streaming_fee: Some(StreamingFee {
+                    collected: vec![coin(100, "uatom")],
+                    ..Default::default()
+                }),
+                performance_fee: Some(PerformanceFee {
+                    rate: Decimal::percent(20),
+                    price_sources: vec![("uatom".to_string(), 1)],
+                    twap_window: 3600,
+                    high_water_mark: Decimal::percent(200),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .add_index_unit("uatom", "1.0")
+            .add_index_unit("ujpy", "1.0")
+            .with_total_supply(1000)
+            .build(deps.as_mut().storage);
+
+        // streaming fee is still realized
+        let res = realize_fee(deps.as_mut(), mock_env(), mock_info("collector", &[])).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: "collector".to_string(),
+                amount: vec![coin(100, "uatom")],
+            })]
+        );
+        assert!(res
+            .attributes
+            .iter()
+            .any(|v| v.key == "performance_fee_skipped" && v.value.contains("ujpy")));
+
+        let performance_fee = FEE
+            .load(deps.as_ref().storage)
+            .unwrap()
+            .performance_fee
+            .unwrap();
+        assert_eq!(performance_fee.high_water_mark, Decimal::percent(200));
+        assert!(performance_fee.collected.is_empty());
+    }
+
+    #[test]
+    fn test_realize_fee() {
         let mut deps = mock_dependencies();
 
         let expected_collected = [("uatom", 10000u128), ("uosmo", 12345u128)]
@@ -185,7 +387,7 @@ mod tests {
             )
             .unwrap();
 
-            let res = realize_streaming_fee(deps.as_mut(), mock_info(sender, &[]));
+            let res = realize_fee(deps.as_mut(), mock_env(), mock_info(sender, &[]));
             assert_eq!(res.map(|v| (v.messages, v.attributes)), expected);
 
             if streaming_fee.is_some() {
@@ -200,4 +402,126 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_realize_fee_with_splits() {
+        let mut deps = mock_dependencies();
+
+        FEE.save(
+            deps.as_mut().storage,
+            &Fee {
+                collector: Addr::unchecked("collector"),
+                streaming_fee: Some(StreamingFee {
+                    collected: vec![coin(1000, "uatom"), coin(10, "uosmo")],
+                    ..Default::default()
+                }),
+                splits: FeeSplits {
+                    streaming: vec![
+                        (Addr::unchecked("treasury"), Decimal::percent(70)),
+                        (Addr::unchecked("frontend"), Decimal::percent(30)),
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let err = realize_fee(deps.as_mut(), mock_env(), mock_info("user", &[])).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+
+        // any of recipients can realize
+        let res = realize_fee(deps.as_mut(), mock_env(), mock_info("frontend", &[])).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(BankMsg::Send {
+                    to_address: "treasury".to_string(),
+                    amount: vec![coin(700, "uatom"), coin(7, "uosmo")],
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: "frontend".to_string(),
+                    amount: vec![coin(300, "uatom"), coin(3, "uosmo")],
+                }),
+            ]
+        );
+        assert_eq!(
+            FEE.load(deps.as_ref().storage)
+                .unwrap()
+                .streaming_fee
+                .unwrap()
+                .collected,
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_realize_fee_mint_mode() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        StateBuilder::default()
+            .with_config(Config {
+                index_denom: "uibcx".to_string(),
+                ..Default::default()
+            })
+            .with_fee(Fee {
+                collector: Addr::unchecked("collector"),
+                streaming_fee: Some(StreamingFee {
+                    rate: Decimal::percent(1),
+                    mode: StreamingFeeMode::Mint,
+                    last_collected_at: env.block.time.seconds(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .add_index_unit("uatom", "10000")
+            .with_total_supply(1000)
+            .build(deps.as_mut().storage);
+
+        // 1.1046221254 -> 1000 * 0.1046 / (1 - 0.1046) = 116.8
+        collect_streaming_fee(deps.as_mut().storage, env.block.time.seconds() + 10).unwrap();
+
+        let streaming_fee = FEE
+            .load(deps.as_ref().storage)
+            .unwrap()
+            .streaming_fee
+            .unwrap();
+        assert!(streaming_fee.collected.is_empty());
+        assert_eq!(streaming_fee.pending_mint, Uint128::new(116));
+        assert_eq!(
+            TOTAL_SUPPLY.load(deps.as_ref().storage).unwrap(),
+            Uint128::new(1116)
+        );
+
+        let res = realize_fee(deps.as_mut(), env.clone(), mock_info("collector", &[])).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![
+                SubMsg::new(MsgMint {
+                    sender: env.contract.address.to_string(),
+                    mint_to_address: env.contract.address.to_string(),
+                    amount: Some(coin(116, "uibcx").into()),
+                }),
+                SubMsg::new(BankMsg::Send {
+                    to_address: "collector".to_string(),
+                    amount: vec![coin(116, "uibcx")],
+                }),
+            ]
+        );
+
+        // total supply already includes minted tokens
+        assert_eq!(
+            FEE.load(deps.as_ref().storage)
+                .unwrap()
+                .streaming_fee
+                .unwrap()
+                .pending_mint,
+            Uint128::zero()
+        );
+        assert_eq!(
+            TOTAL_SUPPLY.load(deps.as_ref().storage).unwrap(),
+            Uint128::new(1116)
+        );
+    }
 }
@@ -0,0 +1,190 @@
+use cosmwasm_std::{attr, DepsMut, Empty, MessageInfo, Response};
+use ibcx_interface::core::AccessPolicyPayload;
+
+use crate::{
+    state::{AccessPolicy, ACCESS_POLICY, ALLOWLIST, CONFIG},
+    StdResult,
+};
+
+pub fn update_access_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_policy: Option<AccessPolicyPayload>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    config.check_gov(&info.sender)?;
+
+    let new_policy = new_policy
+        .map(|v| AccessPolicy::from_payload(deps.as_ref(), v))
+        .transpose()?;
+
+    let policy_str = match &new_policy {
+        Some(AccessPolicy::Allowlist) => "allowlist".to_string(),
+        Some(AccessPolicy::Gate(contract)) => format!("gate:{contract}"),
+        None => "none".to_string(),
+    };
+
+    match new_policy {
+        Some(v) => ACCESS_POLICY.save(deps.storage, &v)?,
+        None => ACCESS_POLICY.remove(deps.storage),
+    }
+
+    // response
+    let attrs = vec![
+        attr("method", "gov::update_access_policy"),
+        attr("executor", info.sender),
+        attr("policy", policy_str),
+    ];
+
+    let resp = Response::new().add_attributes(attrs);
+
+    Ok(resp)
+}
+
+pub fn update_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    config.check_gov(&info.sender)?;
+
+    for address in add.iter() {
+        ALLOWLIST.save(deps.storage, &deps.api.addr_validate(address)?, &Empty {})?;
+    }
+
+    for address in remove.iter() {
+        ALLOWLIST.remove(deps.storage, &deps.api.addr_validate(address)?);
+    }
+
+    // response
+    let attrs = vec![
+        attr("method", "gov::update_allowlist"),
+        attr("executor", info.sender),
+        attr("added", add.join(",")),
+        attr("removed", remove.join(",")),
+    ];
+
+    let resp = Response::new().add_attributes(attrs);
+
+    Ok(resp)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{attr, testing::mock_info, Addr};
+    use ibcx_interface::core::AccessPolicyPayload;
+
+    use crate::{
+        error::ContractError,
+        state::{tests::mock_config, AccessPolicy, ACCESS_POLICY, ALLOWLIST, CONFIG},
+        test::mock_dependencies,
+    };
+
+    use super::{update_access_policy, update_allowlist};
+
+    #[test]
+    fn test_update_access_policy() {
+        let mut deps = mock_dependencies();
+
+        CONFIG.save(deps.as_mut().storage, &mock_config()).unwrap();
+
+        let cases = [
+            (
+                "user",
+                Some(AccessPolicyPayload::Allowlist {}),
+                Err(ContractError::Unauthorized),
+                None,
+            ),
+            (
+                "gov",
+                Some(AccessPolicyPayload::Allowlist {}),
+                Ok(vec![
+                    attr("method", "gov::update_access_policy"),
+                    attr("executor", "gov"),
+                    attr("policy", "allowlist"),
+                ]),
+                Some(AccessPolicy::Allowlist),
+            ),
+            (
+                "gov",
+                Some(AccessPolicyPayload::Gate {
+                    contract: "gate".to_string(),
+                }),
+                Ok(vec![
+                    attr("method", "gov::update_access_policy"),
+                    attr("executor", "gov"),
+                    attr("policy", "gate:gate"),
+                ]),
+                Some(AccessPolicy::Gate(Addr::unchecked("gate"))),
+            ),
+            (
+                "gov",
+                None,
+                Ok(vec![
+                    attr("method", "gov::update_access_policy"),
+                    attr("executor", "gov"),
+                    attr("policy", "none"),
+                ]),
+                None,
+            ),
+        ];
+
+        for (sender, policy, expected, stored) in cases {
+            let res = update_access_policy(deps.as_mut(), mock_info(sender, &[]), policy);
+            assert_eq!(res.map(|v| v.attributes), expected);
+            assert_eq!(
+                ACCESS_POLICY.may_load(deps.as_ref().storage).unwrap(),
+                stored
+            );
+        }
+    }
+
+    #[test]
+    fn test_update_allowlist() {
+        let mut deps = mock_dependencies();
+
+        CONFIG.save(deps.as_mut().storage, &mock_config()).unwrap();
+
+        let err = update_allowlist(
+            deps.as_mut(),
+            mock_info("user", &[]),
+            vec!["alice".to_string()],
+            vec![],
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+
+        let res = update_allowlist(
+            deps.as_mut(),
+            mock_info("gov", &[]),
+            vec!["alice".to_string(), "bob".to_string()],
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("method", "gov::update_allowlist"),
+                attr("executor", "gov"),
+                attr("added", "alice,bob"),
+                attr("removed", ""),
+            ]
+        );
+
+        update_allowlist(
+            deps.as_mut(),
+            mock_info("gov", &[]),
+            vec![],
+            vec!["alice".to_string()],
+        )
+        .unwrap();
+
+        let storage = deps.as_ref().storage;
+        assert!(!ALLOWLIST.has(storage, &Addr::unchecked("alice")));
+        assert!(ALLOWLIST.has(storage, &Addr::unchecked("bob")));
+    }
+}
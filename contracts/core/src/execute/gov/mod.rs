@@ -1,4 +1,6 @@
+mod access;
 mod pause;
+mod strategy;
 mod update;
 
 use cosmwasm_std::{DepsMut, Response};
@@ -18,6 +20,11 @@ pub fn handle_msg(deps: DepsMut, env: Env, info: MessageInfo, msg: GovMsg) -> St
         AcceptGov {} => update::accept_gov(deps, info),
         RevokeGov {} => update::revoke_gov(deps, info),
         UpdateFeeStrategy(new_fee) => update::update_fee(deps, env, info, new_fee),
+        UpdateAccessPolicy(new_policy) => access::update_access_policy(deps, info, new_policy),
+        UpdateMetadata(new_metadata) => update::update_metadata(deps, env, info, new_metadata),
+        UpdateStrategy(new_strategy) => strategy::update_strategy(deps, env, info, new_strategy),
+        UpdateKeeperReward(new_reward) => update::update_keeper_reward(deps, info, new_reward),
+        UpdateAllowlist { add, remove } => access::update_allowlist(deps, info, add, remove),
         UpdateRebalanceManager(new_manager) => {
             update::update_rebalance_manager(deps, info, new_manager)
         }
@@ -27,6 +34,15 @@ pub fn handle_msg(deps: DepsMut, env: Env, info: MessageInfo, msg: GovMsg) -> St
             routes,
             cooldown,
             max_trade_amount,
-        } => update::update_trade_info(deps, info, denom, routes, cooldown, max_trade_amount),
+            max_slippage,
+        } => update::update_trade_info(
+            deps,
+            info,
+            denom,
+            routes,
+            cooldown,
+            max_trade_amount,
+            max_slippage,
+        ),
     }
 }
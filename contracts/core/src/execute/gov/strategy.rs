@@ -0,0 +1,111 @@
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+use ibcx_interface::core::StrategyPayload;
+
+use crate::{
+    state::{Strategy, CONFIG, STRATEGY},
+    StdResult,
+};
+
+pub fn update_strategy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_strategy: Option<StrategyPayload>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    config.check_gov(&info.sender)?;
+
+    // interval starts from the update
+    let new_strategy = new_strategy
+        .map(|v| Strategy::from_payload(deps.as_ref(), v, env.block.time.seconds()))
+        .transpose()?;
+
+    let strategy_str = match &new_strategy {
+        Some(v) => v.contract.to_string(),
+        None => "none".to_string(),
+    };
+
+    match new_strategy {
+        Some(v) => STRATEGY.save(deps.storage, &v)?,
+        None => STRATEGY.remove(deps.storage),
+    }
+
+    // response
+    let attrs = vec![
+        attr("method", "gov::update_strategy"),
+        attr("executor", info.sender),
+        attr("strategy", strategy_str),
+    ];
+
+    let resp = Response::new().add_attributes(attrs);
+
+    Ok(resp)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        attr,
+        testing::{mock_env, mock_info},
+    };
+    use ibcx_interface::core::{RebalancePriceSource, StrategyPayload};
+
+    use crate::{
+        error::ContractError,
+        state::{tests::mock_config, CONFIG, STRATEGY},
+        test::mock_dependencies,
+    };
+
+    use super::update_strategy;
+
+    #[test]
+    fn test_update_strategy() {
+        let mut deps = mock_dependencies();
+
+        CONFIG.save(deps.as_mut().storage, &mock_config()).unwrap();
+
+        let payload = StrategyPayload {
+            contract: "strategy".to_string(),
+            interval: 86400,
+            drift_threshold: None,
+            price_source: RebalancePriceSource::Twap {
+                sources: vec![("uatom".to_string(), 1)],
+                window: 3600,
+            },
+            manager: Some("manager".to_string()),
+        };
+
+        let err = update_strategy(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("abuser", &[]),
+            Some(payload.clone()),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+
+        let res = update_strategy(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gov", &[]),
+            Some(payload.clone()),
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("method", "gov::update_strategy"),
+                attr("executor", "gov"),
+                attr("strategy", "strategy"),
+            ]
+        );
+
+        let strategy = STRATEGY.load(deps.as_ref().storage).unwrap();
+        assert_eq!(strategy.to_payload(), payload);
+        assert_eq!(strategy.last_triggered_at, mock_env().block.time.seconds());
+
+        update_strategy(deps.as_mut(), mock_env(), mock_info("gov", &[]), None).unwrap();
+        assert!(STRATEGY.may_load(deps.as_ref().storage).unwrap().is_none());
+    }
+}
@@ -1,12 +1,16 @@
-use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response, StdError, Uint128};
-use ibcx_interface::{core::FeePayload, types::SwapRoutes};
+use cosmwasm_std::{attr, Decimal, DepsMut, Env, MessageInfo, Response, StdError, Uint128};
+use ibcx_interface::{
+    core::{FeePayload, IndexMetadataPayload, KeeperRewardPayload},
+    types::SwapRoutes,
+};
 
 use crate::{
     assert_sender,
-    error::RebalanceError,
+    error::{RebalanceError, ValidationError},
     state::{
-        Config, Rebalance, StreamingFee, TradeInfo, CONFIG, FEE, PENDING_GOV, REBALANCE,
-        TRADE_INFOS,
+        streaming_fee_rate, Config, FeeSchedule, FeeSplits, IndexMetadata, KeeperReward,
+        PerformanceFee, Rebalance, StreamingFee, TradeInfo, CONFIG, FEE, KEEPER_REWARD, METADATA,
+        PENDING_GOV, REBALANCE, TRADE_INFOS,
     },
     StdResult,
 };
@@ -84,16 +88,59 @@ pub fn update_fee(
     // update
     let mut fee = FEE.load(deps.storage)?;
 
+    // burn fee in effect before the change
+    let prev_burn_fee = fee.burn_fee;
+    let prev_burn_tiers = fee.schedule.burn_tiers.clone();
+
     fee.collector = deps.api.addr_validate(&new_fee.collector)?;
     fee.mint_fee = new_fee.mint_fee;
     fee.burn_fee = new_fee.burn_fee;
-    fee.streaming_fee = new_fee.streaming_fee.map(|v| StreamingFee {
+    // keeps uncollected streaming fee and index tokens pending to be minted
+    let prev_streaming_fee = fee.streaming_fee.take().unwrap_or_default();
+    if new_fee.streaming_fee.is_none()
+        && (!prev_streaming_fee.collected.is_empty() || !prev_streaming_fee.pending_mint.is_zero())
+    {
+        return Err(
+            ValidationError::invalid_fee("streaming_fee", "realize collected fee first").into(),
+        );
+    }
+    fee.streaming_fee = new_fee
+        .streaming_fee
+        .map(|v| -> StdResult<_> {
+            Ok(StreamingFee {
+                rate: streaming_fee_rate(&v)?,
+                collected: prev_streaming_fee.collected,
+                last_collected_at: env.block.time.seconds(),
+                freeze: v.freeze,
+                mode: v.mode.unwrap_or_default(),
+                pending_mint: prev_streaming_fee.pending_mint,
+            })
+        })
+        .transpose()?;
+    // keeps previous high-water mark and uncollected performance fee unless overridden
+    let prev_performance_fee = fee.performance_fee.take().unwrap_or_default();
+    fee.performance_fee = new_fee.performance_fee.map(|v| PerformanceFee {
         rate: v.rate,
-        collected: vec![],
-        last_collected_at: env.block.time.seconds(),
+        price_sources: v.price_sources,
+        twap_window: v.twap_window,
+        high_water_mark: v
+            .high_water_mark
+            .unwrap_or(prev_performance_fee.high_water_mark),
+        collected: prev_performance_fee.collected,
         freeze: v.freeze,
+        last_collected_at: prev_performance_fee.last_collected_at,
     });
+    fee.splits = FeeSplits::from_payload(deps.api, new_fee.splits)?;
+    fee.schedule = FeeSchedule::from_payload(deps.api, new_fee.schedule)?;
+    // window opened by this change follows the grace period in effect before it
+    fee.start_grace(prev_burn_fee, prev_burn_tiers, env.block.time.seconds());
+    fee.grace_period = new_fee.grace_period.unwrap_or(fee.grace_period);
     fee.check_rates()?;
+    fee.check_splits()?;
+    fee.check_schedule()?;
+    if let Some(reward) = KEEPER_REWARD.may_load(deps.storage)? {
+        reward.check_fee(&fee)?;
+    }
 
     FEE.save(deps.storage, &fee)?;
 
@@ -145,6 +192,43 @@ pub fn update_rebalance_manager(
     Ok(resp)
 }
 
+pub fn update_keeper_reward(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_reward: Option<KeeperRewardPayload>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    config.check_gov(&info.sender)?;
+
+    let new_reward = new_reward.map(KeeperReward::from_payload).transpose()?;
+    if let Some(reward) = new_reward.as_ref() {
+        reward.check_fee(&FEE.load(deps.storage)?)?;
+    }
+
+    let reward_str = match &new_reward {
+        Some(KeeperReward::TradeShare(share)) => format!("trade_share:{share}"),
+        Some(KeeperReward::Bounty(bounty)) => format!("bounty:{bounty}"),
+        None => "none".to_string(),
+    };
+
+    match new_reward {
+        Some(v) => KEEPER_REWARD.save(deps.storage, &v)?,
+        None => KEEPER_REWARD.remove(deps.storage),
+    }
+
+    // response
+    let attrs = vec![
+        attr("method", "gov::update_keeper_reward"),
+        attr("executor", info.sender),
+        attr("new_reward", reward_str),
+    ];
+
+    let resp = Response::new().add_attributes(attrs);
+
+    Ok(resp)
+}
+
 pub fn update_reserve_denom(
     deps: DepsMut,
     info: MessageInfo,
@@ -177,6 +261,34 @@ pub fn update_reserve_denom(
     Ok(resp)
 }
 
+pub fn update_metadata(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_metadata: IndexMetadataPayload,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    config.check_gov(&info.sender)?;
+
+    let metadata = IndexMetadata::from_payload(new_metadata)?;
+    METADATA.save(deps.storage, &metadata)?;
+
+    // response
+    let attrs = vec![
+        attr("method", "gov::update_metadata"),
+        attr("executor", info.sender),
+        attr("name", &metadata.name),
+        attr("symbol", &metadata.symbol),
+    ];
+
+    let resp = Response::new()
+        .add_message(metadata.to_msg(env.contract.address, &config.index_denom))
+        .add_attributes(attrs);
+
+    Ok(resp)
+}
+
 pub fn update_trade_info(
     deps: DepsMut,
     info: MessageInfo,
@@ -184,15 +296,21 @@ pub fn update_trade_info(
     routes: SwapRoutes,
     cooldown: u64,
     max_trade_amount: Uint128,
+    max_slippage: Option<Decimal>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
 
     config.check_gov(&info.sender)?;
 
+    if max_slippage.is_some_and(|v| Decimal::one() <= v) {
+        return Err(ValidationError::invalid_config("max_slippage", "must be less than 1").into());
+    }
+
     let trade_info = TradeInfo {
         routes: routes.clone(),
         cooldown,
         max_trade_amount,
+        max_slippage,
         last_traded_at: None,
     };
 
@@ -206,6 +324,10 @@ pub fn update_trade_info(
         attr("routes", format!("{routes:?}")),
         attr("cooldown", cooldown.to_string()),
         attr("max_trade_amount", max_trade_amount.to_string()),
+        attr(
+            "max_slippage",
+            max_slippage.map_or("none".to_string(), |v| v.to_string()),
+        ),
     ];
 
     let resp = Response::new().add_attributes(attrs);
@@ -218,23 +340,27 @@ mod tests {
     use std::str::FromStr;
 
     use cosmwasm_std::{
-        attr,
+        attr, coin,
         testing::{mock_env, mock_info},
-        Addr, Decimal, Timestamp,
+        Addr, Decimal, SubMsg, Timestamp, Uint128,
+    };
+    use ibcx_interface::core::{
+        FeePayload, IndexMetadataPayload, KeeperRewardPayload, StreamingFeeMode,
+        StreamingFeePayload,
     };
-    use ibcx_interface::core::{FeePayload, StreamingFeePayload};
 
     use crate::{
-        error::{ContractError, RebalanceError},
+        error::{ContractError, RebalanceError, ValidationError},
         execute::gov::update::update_fee,
         state::{
             tests::{mock_config, StateBuilder},
-            Config, Fee, Rebalance, StreamingFee, CONFIG, FEE, REBALANCE,
+            Config, Fee, FeeGrace, KeeperReward, Rebalance, StreamingFee, CONFIG, FEE,
+            KEEPER_REWARD, METADATA, REBALANCE,
         },
         test::mock_dependencies,
     };
 
-    use super::{update_gov, update_rebalance_manager};
+    use super::{update_gov, update_keeper_reward, update_metadata, update_rebalance_manager};
 
     #[test]
     fn test_update_gov() {
@@ -327,8 +453,10 @@ mod tests {
                 None,
                 FeePayload {
                     streaming_fee: Some(StreamingFeePayload {
-                        rate: Decimal::from_str("0.000000021979553").unwrap(),
+                        rate: Some(Decimal::from_str("0.000000021979553").unwrap()),
+                        apr: None,
                         freeze: false,
+                        mode: None,
                     }),
                     ..fee_base
                 },
@@ -386,10 +514,12 @@ mod tests {
                         .unwrap(),
                     fee.streaming_fee
                         .map(|v| StreamingFee {
-                            rate: v.rate,
+                            rate: v.rate.unwrap(),
                             collected: vec![],
                             last_collected_at: std_time,
                             freeze: v.freeze,
+                            mode: v.mode.unwrap_or_default(),
+                            pending_mint: Uint128::zero(),
                         })
                         .unwrap(),
                 ),
@@ -398,6 +528,159 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_update_fee_grace() {
+        let mut env = mock_env();
+        let now = env.block.time.seconds();
+        let mut deps = mock_dependencies();
+
+        FEE.save(
+            deps.as_mut().storage,
+            &Fee {
+                burn_fee: Some(Decimal::percent(1)),
+                grace_period: 100,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        CONFIG.save(deps.as_mut().storage, &mock_config()).unwrap();
+
+        let payload = |burn_fee: u64| FeePayload {
+            collector: "collector".to_string(),
+            burn_fee: Some(Decimal::percent(burn_fee)),
+            grace_period: Some(100),
+            ..Default::default()
+        };
+
+        // keeps previous burn fee for grace period
+        update_fee(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("gov", &[]),
+            payload(5),
+        )
+        .unwrap();
+
+        let fee = FEE.load(deps.as_ref().storage).unwrap();
+        assert_eq!(
+            fee.grace,
+            Some(FeeGrace {
+                burn_fee: Some(Decimal::percent(1)),
+                burn_tiers: vec![],
+                expires_at: now + 100,
+            })
+        );
+
+        let amount = Uint128::new(1000);
+        assert_eq!(
+            fee.calc_burn_fee(None, amount, now + 99).unwrap(),
+            Some(Uint128::new(10))
+        );
+        assert_eq!(
+            fee.calc_burn_fee(None, amount, now + 100).unwrap(),
+            Some(Uint128::new(50))
+        );
+
+        // consecutive increase keeps the fee of active window and extends it
+        env.block.time = Timestamp::from_seconds(now + 50);
+        update_fee(deps.as_mut(), env, mock_info("gov", &[]), payload(10)).unwrap();
+
+        let fee = FEE.load(deps.as_ref().storage).unwrap();
+        assert_eq!(
+            fee.grace,
+            Some(FeeGrace {
+                burn_fee: Some(Decimal::percent(1)),
+                burn_tiers: vec![],
+                expires_at: now + 150,
+            })
+        );
+        assert_eq!(
+            fee.calc_burn_fee(None, amount, now + 149).unwrap(),
+            Some(Uint128::new(10))
+        );
+        assert_eq!(
+            fee.calc_burn_fee(None, amount, now + 150).unwrap(),
+            Some(Uint128::new(100))
+        );
+    }
+
+    #[test]
+    fn test_update_fee_grace_period_change() {
+        let env = mock_env();
+        let now = env.block.time.seconds();
+        let mut deps = mock_dependencies();
+
+        FEE.save(
+            deps.as_mut().storage,
+            &Fee {
+                burn_fee: Some(Decimal::percent(1)),
+                grace_period: 100,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        CONFIG.save(deps.as_mut().storage, &mock_config()).unwrap();
+
+        let payload = |burn_fee: u64, grace_period: Option<u64>| FeePayload {
+            collector: "collector".to_string(),
+            burn_fee: Some(Decimal::percent(burn_fee)),
+            grace_period,
+            ..Default::default()
+        };
+
+        // disabling grace with the fee increase doesn't skip the window of the previous period
+        update_fee(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("gov", &[]),
+            payload(5, Some(0)),
+        )
+        .unwrap();
+
+        let fee = FEE.load(deps.as_ref().storage).unwrap();
+        assert_eq!(fee.grace_period, 0);
+        assert_eq!(
+            fee.grace,
+            Some(FeeGrace {
+                burn_fee: Some(Decimal::percent(1)),
+                burn_tiers: vec![],
+                expires_at: now + 100,
+            })
+        );
+
+        // raising grace with the fee increase doesn't stretch the window of this change
+        let mut fee = FEE.load(deps.as_ref().storage).unwrap();
+        fee.grace_period = 100;
+        fee.grace = None;
+        FEE.save(deps.as_mut().storage, &fee).unwrap();
+
+        update_fee(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("gov", &[]),
+            payload(10, Some(1000)),
+        )
+        .unwrap();
+
+        let fee = FEE.load(deps.as_ref().storage).unwrap();
+        assert_eq!(fee.grace_period, 1000);
+        assert_eq!(
+            fee.grace,
+            Some(FeeGrace {
+                burn_fee: Some(Decimal::percent(5)),
+                burn_tiers: vec![],
+                expires_at: now + 100,
+            })
+        );
+
+        // omitting grace period keeps the current one
+        update_fee(deps.as_mut(), env, mock_info("gov", &[]), payload(10, None)).unwrap();
+
+        let fee = FEE.load(deps.as_ref().storage).unwrap();
+        assert_eq!(fee.grace_period, 1000);
+        assert_eq!(fee.grace.unwrap().expires_at, now + 1000);
+    }
+
     #[test]
     fn test_update_rebalance_manager() {
         let mut deps = mock_dependencies();
@@ -447,4 +730,132 @@ mod tests {
             Some(Addr::unchecked("manager"))
         );
     }
+
+    #[test]
+    fn test_update_keeper_reward() {
+        let mut deps = mock_dependencies();
+
+        CONFIG.save(deps.as_mut().storage, &mock_config()).unwrap();
+        FEE.save(
+            deps.as_mut().storage,
+            &Fee {
+                streaming_fee: Some(StreamingFee::default()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let err = update_keeper_reward(deps.as_mut(), mock_info("abuser", &[]), None).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+
+        let cases = [
+            (
+                Some(KeeperRewardPayload::TradeShare(Decimal::permille(5))),
+                "trade_share:0.005",
+            ),
+            (
+                Some(KeeperRewardPayload::Bounty(coin(100, "uosmo"))),
+                "bounty:100uosmo",
+            ),
+            (None, "none"),
+        ];
+
+        for (payload, expected) in cases {
+            let res = update_keeper_reward(deps.as_mut(), mock_info("gov", &[]), payload.clone())
+                .unwrap();
+            assert_eq!(
+                res.attributes,
+                vec![
+                    attr("method", "gov::update_keeper_reward"),
+                    attr("executor", "gov"),
+                    attr("new_reward", expected),
+                ]
+            );
+            assert_eq!(
+                KEEPER_REWARD.may_load(deps.as_ref().storage).unwrap(),
+                payload.map(|v| KeeperReward::from_payload(v).unwrap())
+            );
+        }
+
+        // bounty can't be funded by the streaming fee in mint mode
+        let bounty = KeeperRewardPayload::Bounty(coin(100, "uosmo"));
+        let mint_mode = FeePayload {
+            collector: "collector".to_string(),
+            streaming_fee: Some(StreamingFeePayload {
+                rate: Some(Decimal::from_str("0.000000001").unwrap()),
+                mode: Some(StreamingFeeMode::Mint),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let err = ContractError::from(ValidationError::invalid_config(
+            "keeper_reward",
+            "bounty requires streaming fee in units mode",
+        ));
+
+        update_keeper_reward(deps.as_mut(), mock_info("gov", &[]), Some(bounty.clone())).unwrap();
+        let res = update_fee(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gov", &[]),
+            mint_mode.clone(),
+        );
+        assert_eq!(res.unwrap_err(), err);
+
+        update_keeper_reward(deps.as_mut(), mock_info("gov", &[]), None).unwrap();
+        update_fee(deps.as_mut(), mock_env(), mock_info("gov", &[]), mint_mode).unwrap();
+        let res = update_keeper_reward(deps.as_mut(), mock_info("gov", &[]), Some(bounty));
+        assert_eq!(res.unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_update_metadata() {
+        let mut deps = mock_dependencies();
+
+        CONFIG.save(deps.as_mut().storage, &mock_config()).unwrap();
+
+        let payload = IndexMetadataPayload {
+            name: "Interchain Index".to_string(),
+            symbol: "IBCX".to_string(),
+            description: None,
+            methodology_url: None,
+            logo_uri: None,
+            decimals: None,
+        };
+
+        let err = update_metadata(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("abuser", &[]),
+            payload.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized);
+
+        let res = update_metadata(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gov", &[]),
+            payload.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![
+                attr("method", "gov::update_metadata"),
+                attr("executor", "gov"),
+                attr("name", "Interchain Index"),
+                attr("symbol", "IBCX"),
+            ]
+        );
+
+        let metadata = METADATA.load(deps.as_ref().storage).unwrap();
+        assert_eq!(metadata.decimals, 6);
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(
+                metadata.to_msg(mock_env().contract.address, "uibcx")
+            )]
+        );
+    }
 }
@@ -3,7 +3,7 @@ mod gov;
 mod rebalance;
 mod token;
 
-pub use fee::{collect_streaming_fee, realize_streaming_fee};
+pub use fee::{collect_performance_fee, collect_streaming_fee, realize_fee};
 pub use gov::handle_msg as handle_gov_msg;
 pub use rebalance::handle_msg as handle_rebalance_msg;
 pub use token::{burn, mint};
@@ -7,19 +7,29 @@ use crate::{
     StdResult,
 };
 
-fn unfreeze_streaming_fee(storage: &mut dyn Storage, now_in_sec: u64) -> StdResult<()> {
+fn unfreeze_fees(storage: &mut dyn Storage, now_in_sec: u64) -> StdResult<()> {
     let mut fee = FEE.load(storage)?;
     if let Some(streaming_fee) = fee.streaming_fee.as_mut() {
         streaming_fee.freeze = false;
         streaming_fee.last_collected_at = now_in_sec;
     }
+    // keeps the high-water mark. nav changes made by rebalance are not charged until
+    // it grows above the previous peak
+    if let Some(performance_fee) = fee.performance_fee.as_mut() {
+        performance_fee.freeze = false;
+    }
+    // burn is blocked while rebalancing. counts the grace window again from here
+    let grace_expires_at = now_in_sec + fee.grace_period;
+    if let Some(grace) = fee.grace.as_mut() {
+        grace.expires_at = grace.expires_at.max(grace_expires_at);
+    }
     FEE.save(storage, &fee)?;
 
     Ok(())
 }
 
 pub fn finalize(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
-    unfreeze_streaming_fee(deps.storage, env.block.time.seconds())?;
+    unfreeze_fees(deps.storage, env.block.time.seconds())?;
 
     let rebalance = match REBALANCE.may_load(deps.storage)? {
         Some(v) => v,
@@ -76,21 +86,54 @@ mod tests {
     use cosmwasm_std::{
         attr,
         testing::{mock_env, mock_info, MockStorage},
-        Addr,
+        Addr, Decimal,
     };
     use rstest::rstest;
 
     use crate::{
         error::{ContractError, RebalanceError},
-        state::{Fee, Rebalance, StreamingFee, Units, FEE, INDEX_UNITS, REBALANCE, RESERVE_UNITS},
+        state::{
+            Fee, FeeGrace, PerformanceFee, Rebalance, StreamingFee, Units, FEE, INDEX_UNITS,
+            REBALANCE, RESERVE_UNITS,
+        },
         test::mock_dependencies,
         StdResult,
     };
 
-    use super::{finalize, unfreeze_streaming_fee};
+    use super::{finalize, unfreeze_fees};
+
+    #[test]
+    fn test_unfreeze_fees_extends_grace() {
+        let mut storage = MockStorage::new();
+
+        let grace = FeeGrace {
+            burn_fee: None,
+            burn_tiers: vec![],
+            expires_at: 50,
+        };
+
+        FEE.save(
+            &mut storage,
+            &Fee {
+                grace_period: 100,
+                grace: Some(grace.clone()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        unfreeze_fees(&mut storage, 30).unwrap();
+        assert_eq!(
+            FEE.load(&storage).unwrap().grace,
+            Some(FeeGrace {
+                expires_at: 130,
+                ..grace
+            })
+        );
+    }
 
     #[test]
-    fn test_unfreeze_streaming_fee() {
+    fn test_unfreeze_fees() {
         let mut storage = MockStorage::new();
 
         FEE.save(
@@ -102,7 +145,7 @@ mod tests {
         )
         .unwrap();
 
-        unfreeze_streaming_fee(&mut storage, 30).unwrap();
+        unfreeze_fees(&mut storage, 30).unwrap();
         assert!(FEE.load(&storage).unwrap().streaming_fee.is_none());
 
         FEE.save(
@@ -113,16 +156,26 @@ mod tests {
                     last_collected_at: 10,
                     ..Default::default()
                 }),
+                performance_fee: Some(PerformanceFee {
+                    freeze: true,
+                    high_water_mark: Decimal::percent(150),
+                    ..Default::default()
+                }),
                 ..Default::default()
             },
         )
         .unwrap();
 
-        unfreeze_streaming_fee(&mut storage, 12345).unwrap();
+        unfreeze_fees(&mut storage, 12345).unwrap();
 
-        let streaming_fee = FEE.load(&storage).unwrap().streaming_fee.unwrap();
+        let fee = FEE.load(&storage).unwrap();
+        let streaming_fee = fee.streaming_fee.unwrap();
         assert!(!streaming_fee.freeze);
         assert_eq!(streaming_fee.last_collected_at, 12345);
+
+        let performance_fee = fee.performance_fee.unwrap();
+        assert!(!performance_fee.freeze);
+        assert_eq!(performance_fee.high_water_mark, Decimal::percent(150));
     }
 
     #[rstest]
@@ -1,22 +1,116 @@
-use cosmwasm_std::{attr, Decimal, DepsMut, MessageInfo, Response, Storage};
+use std::{collections::BTreeSet, str::FromStr};
+
+use cosmwasm_std::{
+    attr, coin, Decimal, Deps, DepsMut, Env, MessageInfo, Response, Storage, Uint128,
+};
+use ibcx_interface::core::RebalancePriceSource;
+use osmosis_std::{shim::Timestamp, types::osmosis::twap::v1beta1::TwapQuerier};
 
 use crate::{
-    error::RebalanceError,
-    state::{Rebalance, Units, CONFIG, FEE, INDEX_UNITS, REBALANCE, RESERVE_UNITS},
+    error::{ContractError, RebalanceError, ValidationError},
+    state::{
+        check_price_source, weights_to_units, Rebalance, Units, CONFIG, FEE, INDEX_UNITS,
+        REBALANCE, RESERVE_UNITS, TOTAL_SUPPLY, TRADE_INFOS,
+    },
     StdResult,
 };
 
-fn freeze_streaming_fee(storage: &mut dyn Storage) -> StdResult<()> {
+fn freeze_fees(storage: &mut dyn Storage, now_in_sec: u64) -> StdResult<()> {
     FEE.update(storage, |mut v| {
         if let Some(streaming_fee) = v.streaming_fee.as_mut() {
             streaming_fee.freeze = true;
         }
+        if let Some(performance_fee) = v.performance_fee.as_mut() {
+            performance_fee.freeze = true;
+        }
+        // holders who disagree with the rebalance can exit before any later fee change.
+        // active window keeps its fee, otherwise the current burn fee is locked in
+        let burn_tiers = v.schedule.burn_tiers.clone();
+        v.start_grace(v.burn_fee, burn_tiers, now_in_sec);
         StdResult::Ok(v)
     })?;
 
     Ok(())
 }
 
+// every non-reserve denom must be tradable through a registered route.
+// each route is probed with the amount it has to carry (up to its max trade amount) so that
+// the rebalance cannot get stuck, without failing on routes too shallow for a full size trade.
+// inflation amounts are estimated from the reserve expected out of the deflation
+fn validate_routes(
+    deps: Deps,
+    env: &Env,
+    rebalance: &Rebalance,
+    index_units: &Units,
+    reserve_denom: &str,
+) -> StdResult<()> {
+    let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
+
+    let probe = |field: &str, denom_in: &str, denom_out: &str, amount: Uint128| -> StdResult<_> {
+        let invalid = |reason: &str| -> ContractError {
+            ValidationError::invalid_rebalance(
+                field,
+                format!("{reason}: {denom_in} -> {denom_out}"),
+            )
+            .into()
+        };
+
+        let trade_info = TRADE_INFOS
+            .may_load(deps.storage, (denom_in, denom_out))?
+            .ok_or_else(|| invalid("missing trade info"))?;
+        if trade_info.routes.0.is_empty() {
+            return Err(invalid("empty routes"));
+        }
+        if trade_info.max_trade_amount.is_zero() {
+            return Err(invalid("zero max trade amount"));
+        }
+
+        let amount_in = amount.min(trade_info.max_trade_amount);
+        if amount_in.is_zero() {
+            return Ok(Uint128::zero());
+        }
+
+        let amount_out = trade_info
+            .routes
+            .sim_swap_exact_in(
+                &deps.querier,
+                env.contract.address.as_str(),
+                coin(amount_in.u128(), denom_in),
+            )
+            .map_err(|e| invalid(&format!("route simulation failed ({e})")))?;
+        if amount_out.is_zero() {
+            return Err(invalid("zero simulated amount"));
+        }
+
+        // extrapolates output of the whole amount
+        Ok(amount_out.multiply_ratio(amount, amount_in))
+    };
+
+    let mut reserve = Uint128::zero();
+    for (denom, target_unit) in rebalance.deflation.iter() {
+        let (_, current_unit) = index_units.get_key(denom).cloned().unwrap_or_default();
+        let amount = current_unit.checked_sub(*target_unit)? * total_supply;
+
+        let amount_out = match denom == reserve_denom {
+            true => amount,
+            false => probe("deflation", denom, reserve_denom, amount)?,
+        };
+        reserve = reserve.checked_add(amount_out)?;
+    }
+
+    let total_weight = rebalance.inflation.iter().map(|(_, v)| *v).sum::<Decimal>();
+    for (denom, weight) in rebalance.inflation.iter() {
+        if denom == reserve_denom || total_weight.is_zero() {
+            continue;
+        }
+
+        let amount = reserve * (*weight / total_weight);
+        probe("inflation", reserve_denom, denom, amount)?;
+    }
+
+    Ok(())
+}
+
 // initialize the rebalance
 // deflation: target unit of each denom to decrease
 // inflation: weight of each denom to distribute
@@ -32,21 +126,16 @@ fn freeze_streaming_fee(storage: &mut dyn Storage) -> StdResult<()> {
 //=========================================
 pub fn init(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     manager: Option<String>,
     deflation: Vec<(String, Decimal)>,
     inflation: Vec<(String, Decimal)>,
 ) -> StdResult<Response> {
-    freeze_streaming_fee(deps.storage)?;
-
     let config = CONFIG.load(deps.storage)?;
 
     config.check_gov(&info.sender)?;
 
-    if REBALANCE.may_load(deps.storage)?.is_some() {
-        return Err(RebalanceError::OnRebalancing.into());
-    }
-
     // make new rebalance
     let rebalance = Rebalance {
         manager: manager
@@ -57,44 +146,210 @@ pub fn init(
         inflation: inflation.into(),
     };
 
+    start(deps, &env, &config.reserve_denom, rebalance)?;
+
+    // response
+    let attrs = vec![
+        attr("method", "rebalance::init"),
+        attr("executor", info.sender),
+        attr("manager", manager.as_deref().unwrap_or("none")),
+    ];
+
+    let resp = Response::new().add_attributes(attrs);
+
+    Ok(resp)
+}
+
+// validates the rebalance against current units and routes, then locks the index in rebalancing mode
+pub(super) fn start(
+    deps: DepsMut,
+    env: &Env,
+    reserve_denom: &str,
+    rebalance: Rebalance,
+) -> StdResult<()> {
+    freeze_fees(deps.storage, env.block.time.seconds())?;
+
+    if REBALANCE.may_load(deps.storage)?.is_some() {
+        return Err(RebalanceError::OnRebalancing.into());
+    }
+
     // fetch current units and validate new rebalance
     let index_units = INDEX_UNITS.load(deps.storage)?;
 
-    rebalance.validate(index_units)?;
+    rebalance.validate(index_units.clone())?;
+    validate_routes(deps.as_ref(), env, &rebalance, &index_units, reserve_denom)?;
 
     // save
     REBALANCE.save(deps.storage, &rebalance)?;
     RESERVE_UNITS.save(deps.storage, &Units::default())?;
 
-    // response
+    Ok(())
+}
+
+fn query_price(
+    deps: Deps,
+    env: &Env,
+    source: &RebalancePriceSource,
+    denom: &str,
+    reserve_denom: &str,
+) -> StdResult<Decimal> {
+    if denom == reserve_denom {
+        return Ok(Decimal::one());
+    }
+
+    let missing = |reason: &str| -> ContractError {
+        ValidationError::invalid_rebalance("price_source", format!("{reason}: {denom}")).into()
+    };
+
+    match source {
+        RebalancePriceSource::Twap { sources, window } => {
+            let (_, pool_id) = sources
+                .iter()
+                .find(|(v, _)| v == denom)
+                .ok_or_else(|| missing("missing price source"))?;
+
+            let start_time = Timestamp {
+                seconds: env.block.time.seconds().saturating_sub(*window) as i64,
+                nanos: 0,
+            };
+
+            let resp = TwapQuerier::new(&deps.querier).arithmetic_twap_to_now(
+                *pool_id,
+                denom.to_string(),
+                reserve_denom.to_string(),
+                Some(start_time),
+            )?;
+
+            Ok(Decimal::from_str(&resp.arithmetic_twap)?)
+        }
+        RebalancePriceSource::Simulation { probe_amount } => {
+            let sender = env.contract.address.as_str();
+
+            // sell the denom through its deflation route, or buy it through its inflation route
+            if let Some(trade_info) = TRADE_INFOS.may_load(deps.storage, (denom, reserve_denom))? {
+                let amount_out = trade_info.routes.sim_swap_exact_in(
+                    &deps.querier,
+                    sender,
+                    coin(probe_amount.u128(), denom),
+                )?;
+
+                Ok(Decimal::checked_from_ratio(amount_out, *probe_amount)?)
+            } else if let Some(trade_info) =
+                TRADE_INFOS.may_load(deps.storage, (reserve_denom, denom))?
+            {
+                let amount_out = trade_info.routes.sim_swap_exact_in(
+                    &deps.querier,
+                    sender,
+                    coin(probe_amount.u128(), reserve_denom),
+                )?;
+
+                Ok(Decimal::checked_from_ratio(*probe_amount, amount_out)?)
+            } else {
+                Err(missing("missing trade info"))
+            }
+        }
+    }
+}
+
+// prices of each denom in the reserve denom
+pub(super) fn query_prices<'a>(
+    deps: Deps,
+    env: &Env,
+    source: &RebalancePriceSource,
+    denoms: impl Iterator<Item = &'a str>,
+    reserve_denom: &str,
+) -> StdResult<Units> {
+    denoms
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|denom| {
+            let price = query_price(deps, env, source, denom, reserve_denom)?;
+            Ok((denom.to_string(), price))
+        })
+        .collect()
+}
+
+// initialize the rebalance from target portfolio weights.
+// deflation and inflation are derived from the prices of the given source
+pub fn init_by_weights(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    manager: Option<String>,
+    targets: Vec<(String, Decimal)>,
+    price_source: RebalancePriceSource,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    config.check_gov(&info.sender)?;
+
+    check_price_source(&price_source)?;
+
+    let index_units = INDEX_UNITS.load(deps.storage)?;
+    let targets: Units = targets.into();
+
+    let denoms = index_units
+        .iter()
+        .chain(targets.iter())
+        .map(|v| v.0.as_str());
+    let prices = query_prices(
+        deps.as_ref(),
+        &env,
+        &price_source,
+        denoms,
+        &config.reserve_denom,
+    )?;
+
+    let (deflation, inflation) = weights_to_units(&index_units, &targets, &prices)?;
+
     let attrs = vec![
-        attr("method", "rebalance::init"),
-        attr("executor", info.sender),
-        attr("manager", manager.as_deref().unwrap_or("none")),
+        attr("targets", targets.prettify()),
+        attr("deflation", deflation.prettify()),
+        attr("inflation", inflation.prettify()),
     ];
 
-    let resp = Response::new().add_attributes(attrs);
+    let resp = init(deps, env, info, manager, deflation.into(), inflation.into())?;
 
-    Ok(resp)
+    Ok(resp.add_attributes(attrs))
 }
 
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{attr, testing::mock_info, Addr};
+    use cosmwasm_std::{
+        attr,
+        testing::{mock_env, mock_info},
+        to_json_binary, Addr, ContractResult, Decimal, DepsMut, Uint128,
+    };
+    use osmosis_std::types::osmosis::poolmanager::v1beta1::{
+        EstimateSwapExactAmountInRequest, EstimateSwapExactAmountInResponse,
+    };
+
+    use ibcx_interface::{
+        core::RebalancePriceSource,
+        types::{SwapRoute, SwapRoutes},
+    };
 
     use crate::{
-        error::RebalanceError,
+        error::{ContractError, RebalanceError, ValidationError},
         state::{
-            Config, Fee, Rebalance, StreamingFee, Units, CONFIG, FEE, INDEX_UNITS, REBALANCE,
-            RESERVE_UNITS,
+            Config, Fee, FeeGrace, PerformanceFee, Rebalance, StreamingFee, TradeInfo, Units,
+            CONFIG, FEE, INDEX_UNITS, REBALANCE, RESERVE_UNITS, TOTAL_SUPPLY, TRADE_INFOS,
         },
-        test::mock_dependencies,
+        test::{mock::StargateQuerier, mock_dependencies},
     };
 
-    use super::{freeze_streaming_fee, init};
+    use super::{freeze_fees, init, init_by_weights};
+
+    fn mock_trade_info(denom_out: &str, max_trade_amount: u128) -> TradeInfo {
+        TradeInfo {
+            routes: SwapRoutes(vec![SwapRoute::new(1, denom_out)]),
+            max_trade_amount: Uint128::new(max_trade_amount),
+            ..Default::default()
+        }
+    }
 
     #[test]
-    fn test_freeze_streaming_fee() {
+    fn test_freeze_fees() {
         let mut deps = mock_dependencies();
 
         FEE.save(
@@ -104,19 +359,48 @@ mod tests {
                     freeze: false,
                     ..Default::default()
                 }),
+                performance_fee: Some(PerformanceFee {
+                    freeze: false,
+                    ..Default::default()
+                }),
+                burn_fee: Some(Decimal::percent(1)),
+                grace_period: 100,
+                grace: Some(FeeGrace {
+                    burn_fee: Some(Decimal::percent(2)),
+                    burn_tiers: vec![],
+                    expires_at: 50,
+                }),
                 ..Default::default()
             },
         )
         .unwrap();
 
-        freeze_streaming_fee(deps.as_mut().storage).unwrap();
+        freeze_fees(deps.as_mut().storage, 10).unwrap();
+
+        let fee = FEE.load(deps.as_ref().storage).unwrap();
+        assert!(fee.streaming_fee.unwrap().freeze);
+        assert!(fee.performance_fee.unwrap().freeze);
+        // active grace window keeps its fee
+        assert_eq!(
+            fee.grace,
+            Some(FeeGrace {
+                burn_fee: Some(Decimal::percent(2)),
+                burn_tiers: vec![],
+                expires_at: 110,
+            })
+        );
+
+        // otherwise opened with the current burn fee
+        freeze_fees(deps.as_mut().storage, 200).unwrap();
 
-        assert!(
-            FEE.load(deps.as_ref().storage)
-                .unwrap()
-                .streaming_fee
-                .unwrap()
-                .freeze
+        let fee = FEE.load(deps.as_ref().storage).unwrap();
+        assert_eq!(
+            fee.grace,
+            Some(FeeGrace {
+                burn_fee: Some(Decimal::percent(1)),
+                burn_tiers: vec![],
+                expires_at: 300,
+            })
         );
     }
 
@@ -124,17 +408,30 @@ mod tests {
     fn test_init() {
         let mut deps = mock_dependencies();
 
+        deps.querier.stargate.register_sim_swap_exact_in("1");
+
         FEE.save(deps.as_mut().storage, &Fee::default()).unwrap();
         CONFIG
             .save(
                 deps.as_mut().storage,
                 &Config {
                     gov: Addr::unchecked("gov"),
+                    reserve_denom: "uosmo".to_string(),
                     ..Default::default()
                 },
             )
             .unwrap();
+        TRADE_INFOS
+            .save(
+                deps.as_mut().storage,
+                ("uatom", "uosmo"),
+                &mock_trade_info("uosmo", 1000),
+            )
+            .unwrap();
 
+        TOTAL_SUPPLY
+            .save(deps.as_mut().storage, &Uint128::new(1000))
+            .unwrap();
         INDEX_UNITS
             .save(deps.as_mut().storage, &vec![("uatom", "1.1")].into())
             .unwrap();
@@ -173,6 +470,7 @@ mod tests {
 
             let res = init(
                 deps.as_mut(),
+                mock_env(),
                 mock_info(sender, &[]),
                 manager.map(|v| v.to_string()),
                 Units::from(vec![("uatom", "0.9")]).into(),
@@ -196,4 +494,330 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_init_by_weights_twap() {
+        let mut deps = mock_dependencies();
+
+        deps.querier
+            .stargate
+            .register_arithmetic_twap(vec![(1, "10"), (2, "1"), (3, "3")]);
+        deps.querier.stargate.register_sim_swap_exact_in("1");
+
+        FEE.save(deps.as_mut().storage, &Fee::default()).unwrap();
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config {
+                    gov: Addr::unchecked("gov"),
+                    reserve_denom: "uosmo".to_string(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        TOTAL_SUPPLY
+            .save(deps.as_mut().storage, &Uint128::new(1000))
+            .unwrap();
+        INDEX_UNITS
+            .save(
+                deps.as_mut().storage,
+                &vec![("uatom", "2"), ("ujuno", "10")].into(),
+            )
+            .unwrap();
+        for (denom_in, denom_out) in [("uatom", "uosmo"), ("ujuno", "uosmo"), ("uosmo", "uion")] {
+            TRADE_INFOS
+                .save(
+                    deps.as_mut().storage,
+                    (denom_in, denom_out),
+                    &mock_trade_info(denom_out, 1000),
+                )
+                .unwrap();
+        }
+
+        let targets: Vec<(String, Decimal)> =
+            Units::from(vec![("uatom", "0.5"), ("uosmo", "0.2"), ("uion", "0.3")]).into();
+        let source = |sources: Vec<(&str, u64)>, window: u64| RebalancePriceSource::Twap {
+            sources: sources
+                .into_iter()
+                .map(|(denom, pool_id)| (denom.to_string(), pool_id))
+                .collect(),
+            window,
+        };
+        let all_sources = vec![("uatom", 1), ("ujuno", 2), ("uion", 3)];
+
+        let cases = [
+            (
+                "abuser",
+                source(all_sources.clone(), 3600),
+                ContractError::Unauthorized,
+            ),
+            (
+                "gov",
+                source(all_sources.clone(), 0),
+                ValidationError::invalid_rebalance("price_source", "zero twap window").into(),
+            ),
+            (
+                "gov",
+                source(vec![("uatom", 1), ("ujuno", 2)], 3600),
+                ValidationError::invalid_rebalance("price_source", "missing price source: uion")
+                    .into(),
+            ),
+        ];
+
+        for (sender, price_source, expected) in cases {
+            let err = init_by_weights(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(sender, &[]),
+                None,
+                targets.clone(),
+                price_source,
+            )
+            .unwrap_err();
+            assert_eq!(err, expected);
+        }
+
+        // nav = 2 * 10 + 10 * 1 = 30
+        let res = init_by_weights(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gov", &[]),
+            None,
+            targets,
+            source(all_sources, 3600),
+        )
+        .unwrap();
+        assert_eq!(
+            res.attributes[3..],
+            vec![
+                attr(
+                    "targets",
+                    r#"[("uatom","0.5"),("uosmo","0.2"),("uion","0.3")]"#
+                ),
+                attr("deflation", r#"[("uatom","1.5"),("ujuno","0")]"#),
+                attr("inflation", r#"[("uosmo","6"),("uion","9")]"#),
+            ]
+        );
+        assert_eq!(
+            REBALANCE.load(deps.as_ref().storage).unwrap(),
+            Rebalance {
+                manager: None,
+                deflation: Units::from(vec![("uatom", "1.5"), ("ujuno", "0")]),
+                inflation: Units::from(vec![("uosmo", "6"), ("uion", "9")]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_init_by_weights_simulation() {
+        let mut deps = mock_dependencies();
+
+        deps.querier.stargate.register_sim_swap_exact_in("2");
+
+        FEE.save(deps.as_mut().storage, &Fee::default()).unwrap();
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config {
+                    gov: Addr::unchecked("gov"),
+                    reserve_denom: "uosmo".to_string(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        TOTAL_SUPPLY
+            .save(deps.as_mut().storage, &Uint128::new(1000))
+            .unwrap();
+        INDEX_UNITS
+            .save(deps.as_mut().storage, &vec![("uatom", "2")].into())
+            .unwrap();
+
+        TRADE_INFOS
+            .save(
+                deps.as_mut().storage,
+                ("uatom", "uosmo"),
+                &mock_trade_info("uosmo", 1000),
+            )
+            .unwrap();
+
+        let targets: Vec<(String, Decimal)> =
+            Units::from(vec![("uatom", "0.5"), ("uion", "0.5")]).into();
+        let price_source = RebalancePriceSource::Simulation {
+            probe_amount: Uint128::new(1000),
+        };
+
+        let err = init_by_weights(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gov", &[]),
+            None,
+            targets.clone(),
+            price_source.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ValidationError::invalid_rebalance("price_source", "missing trade info: uion").into()
+        );
+
+        // buys 2 uion with 1 uosmo
+        TRADE_INFOS
+            .save(
+                deps.as_mut().storage,
+                ("uosmo", "uion"),
+                &mock_trade_info("uion", 1000),
+            )
+            .unwrap();
+
+        // nav = 2 * 2 = 4
+        init_by_weights(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gov", &[]),
+            None,
+            targets,
+            price_source,
+        )
+        .unwrap();
+        assert_eq!(
+            REBALANCE.load(deps.as_ref().storage).unwrap(),
+            Rebalance {
+                manager: None,
+                deflation: Units::from(vec![("uatom", "1")]),
+                inflation: Units::from(vec![("uion", "2")]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_init_validate_routes() {
+        let mut deps = mock_dependencies();
+
+        FEE.save(deps.as_mut().storage, &Fee::default()).unwrap();
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config {
+                    gov: Addr::unchecked("gov"),
+                    reserve_denom: "uosmo".to_string(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        TOTAL_SUPPLY
+            .save(deps.as_mut().storage, &Uint128::new(1000))
+            .unwrap();
+        INDEX_UNITS
+            .save(
+                deps.as_mut().storage,
+                &vec![("uatom", "1"), ("uosmo", "1")].into(),
+            )
+            .unwrap();
+
+        let init_rebalance = |deps: DepsMut| {
+            init(
+                deps,
+                mock_env(),
+                mock_info("gov", &[]),
+                None,
+                Units::from(vec![("uatom", "0.5")]).into(),
+                // reserve denom needs no route
+                Units::from(vec![("ujuno", "1"), ("uosmo", "1")]).into(),
+            )
+        };
+        let invalid = |field: &str, reason: &str| -> ContractError {
+            ValidationError::invalid_rebalance(field, reason).into()
+        };
+
+        // deflation route
+        assert_eq!(
+            init_rebalance(deps.as_mut()).unwrap_err(),
+            invalid("deflation", "missing trade info: uatom -> uosmo")
+        );
+
+        TRADE_INFOS
+            .save(
+                deps.as_mut().storage,
+                ("uatom", "uosmo"),
+                &mock_trade_info("uosmo", 0),
+            )
+            .unwrap();
+        assert_eq!(
+            init_rebalance(deps.as_mut()).unwrap_err(),
+            invalid("deflation", "zero max trade amount: uatom -> uosmo")
+        );
+
+        TRADE_INFOS
+            .save(
+                deps.as_mut().storage,
+                ("uatom", "uosmo"),
+                &mock_trade_info("uosmo", 1000),
+            )
+            .unwrap();
+        deps.querier.stargate.register_sim_swap_exact_in("0");
+        assert_eq!(
+            init_rebalance(deps.as_mut()).unwrap_err(),
+            invalid("deflation", "zero simulated amount: uatom -> uosmo")
+        );
+
+        // inflation route for the new constituent
+        deps.querier.stargate.register_sim_swap_exact_in("1");
+        assert_eq!(
+            init_rebalance(deps.as_mut()).unwrap_err(),
+            invalid("inflation", "missing trade info: uosmo -> ujuno")
+        );
+        assert!(REBALANCE.may_load(deps.as_ref().storage).unwrap().is_none());
+
+        TRADE_INFOS
+            .save(
+                deps.as_mut().storage,
+                ("uosmo", "ujuno"),
+                &mock_trade_info("ujuno", 1000),
+            )
+            .unwrap();
+
+        // routes are probed with the amount to trade, not the max trade amount.
+        // uatom: 500 (0.5 * 1000), ujuno: 250 (half of the reserve out of uatom)
+        for (denom_in, denom_out) in [("uatom", "uosmo"), ("uosmo", "ujuno")] {
+            TRADE_INFOS
+                .save(
+                    deps.as_mut().storage,
+                    (denom_in, denom_out),
+                    &mock_trade_info(denom_out, 1_000_000),
+                )
+                .unwrap();
+        }
+
+        let register_shallow_pool = |stargate: &mut StargateQuerier, liquidity: u128| {
+            stargate.register_raw(
+                "/osmosis.poolmanager.v1beta1.Query/EstimateSwapExactAmountIn",
+                move |req| {
+                    let req: EstimateSwapExactAmountInRequest = req.clone().try_into().unwrap();
+                    let amount = req.token_in.trim_end_matches(char::is_alphabetic);
+                    if liquidity < amount.parse().unwrap() {
+                        return ContractResult::Err("insufficient liquidity".to_string());
+                    }
+
+                    to_json_binary(&EstimateSwapExactAmountInResponse {
+                        token_out_amount: amount.to_string(),
+                    })
+                    .into()
+                },
+            );
+        };
+
+        register_shallow_pool(&mut deps.querier.stargate, 499);
+        let err = init_rebalance(deps.as_mut()).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::ValidationError(ValidationError::InvalidRebalance { field, reason })
+                if field == "deflation"
+                    && reason.starts_with("route simulation failed")
+                    && reason.ends_with("uatom -> uosmo")
+        ));
+
+        register_shallow_pool(&mut deps.querier.stargate, 500);
+        init_rebalance(deps.as_mut()).unwrap();
+        assert!(REBALANCE.may_load(deps.as_ref().storage).unwrap().is_some());
+    }
 }
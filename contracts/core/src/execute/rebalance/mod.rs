@@ -1,6 +1,7 @@
 mod finalize;
 mod init;
 mod trade;
+mod trigger;
 
 use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
 use ibcx_interface::core::RebalanceMsg;
@@ -8,8 +9,9 @@ use ibcx_interface::core::RebalanceMsg;
 use crate::StdResult;
 
 use finalize::finalize;
-use init::init;
+use init::{init, init_by_weights};
 use trade::trade;
+use trigger::trigger;
 
 pub fn handle_msg(
     deps: DepsMut,
@@ -24,7 +26,13 @@ pub fn handle_msg(
             manager,
             deflation,
             inflation,
-        } => init(deps, info, manager, deflation, inflation),
+        } => init(deps, env, info, manager, deflation, inflation),
+        InitByWeights {
+            manager,
+            targets,
+            price_source,
+        } => init_by_weights(deps, env, info, manager, targets, price_source),
+        Trigger {} => trigger(deps, env, info),
         Trade(msg) => trade(deps, env, info, msg),
         Finalize {} => finalize(deps, env, info),
     }
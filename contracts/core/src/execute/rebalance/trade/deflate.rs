@@ -1,4 +1,4 @@
-use cosmwasm_std::{attr, coin, Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{attr, coin, BankMsg, Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
 
 use crate::{
     error::RebalanceError,
@@ -6,7 +6,7 @@ use crate::{
     StdResult,
 };
 
-use super::load_units;
+use super::{load_units, pay_keeper_reward};
 
 fn deflate_index_unit(
     index_units: &mut Units,
@@ -120,9 +120,25 @@ pub fn deflate(
         return Err(RebalanceError::trade_error("deflate", "over slippage tolerance").into());
     }
 
+    if rebalance.manager.is_none() {
+        let max_slippage = trade_info.max_slippage("deflate")?;
+        if sim_amount_in.mul_ceil(Decimal::one() + max_slippage) < max_amount_in {
+            return Err(RebalanceError::trade_error("deflate", "over slippage bound").into());
+        }
+    }
+
+    // keeper reward is taken from the output
+    let (keeper_reward, amount_out_left) = pay_keeper_reward(
+        deps.storage,
+        &rebalance,
+        &info.sender,
+        coin(amount_out.u128(), &config.reserve_denom),
+        Decimal::checked_from_ratio(amount_out, trade_info.max_trade_amount)?,
+    )?;
+
     // deduct & expand stored units
     let index_deduct_unit = Decimal::checked_from_ratio(sim_amount_in, total_supply)?;
-    let reserve_expand_unit = Decimal::checked_from_ratio(amount_out, total_supply)?;
+    let reserve_expand_unit = Decimal::checked_from_ratio(amount_out_left, total_supply)?;
 
     // deduct index unit
     let mut index_units = index_units;
@@ -151,16 +167,25 @@ pub fn deflate(
         sim_amount_in,
     );
 
-    let attrs = vec![
+    let mut attrs = vec![
         attr("method", "deflate"),
-        attr("executor", info.sender),
+        attr("executor", info.sender.as_str()),
         attr("denom", target_denom),
         attr("amount_in", sim_amount_in.to_string()),
         attr("amount_out", amount_out.to_string()),
         attr("is_reserve", "false"),
     ];
 
-    Ok(Response::new().add_message(swap_msg).add_attributes(attrs))
+    let mut resp = Response::new().add_message(swap_msg);
+    if let Some(reward) = keeper_reward {
+        attrs.push(attr("keeper_reward", reward.to_string()));
+        resp = resp.add_message(BankMsg::Send {
+            to_address: info.sender.into_string(),
+            amount: vec![reward],
+        });
+    }
+
+    Ok(resp.add_attributes(attrs))
 }
 
 #[cfg(test)]
@@ -168,7 +193,7 @@ mod tests {
     use cosmwasm_std::{
         attr,
         testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR},
-        Addr, Attribute, SubMsg, Timestamp,
+        Addr, Attribute, Decimal, SubMsg, Timestamp,
     };
     use ibcx_interface::types::SwapRoutes;
 
@@ -283,6 +308,7 @@ mod tests {
                     routes: routes.clone(),
                     cooldown,
                     max_trade_amount: max_trade_amount.into(),
+                    max_slippage: Some(Decimal::percent(1)),
                     last_traded_at: Some(std_time - cooldown),
                 },
             );
@@ -326,6 +352,13 @@ mod tests {
                 max_amount_gap * 2 - 1,
                 Err(RebalanceError::trade_error("deflate", "over slippage tolerance").into()),
             ),
+            (
+                "manager",
+                std_time,
+                max_amount_gap,
+                max_amount_gap * 2 * 101 / 100 + 1,
+                Err(RebalanceError::trade_error("deflate", "over slippage bound").into()),
+            ),
         ];
 
         for (sender, time_in_sec, amount_out, max_amount_in, expected) in cases {
@@ -379,5 +412,32 @@ mod tests {
                 Err(err) => assert_eq!(err, expected.unwrap_err()),
             }
         }
+
+        // keepers can't trade through a route without slippage bound
+        builder
+            .add_trade_info(
+                "uatom",
+                "uosmo",
+                TradeInfo {
+                    routes,
+                    max_trade_amount: max_trade_amount.into(),
+                    ..Default::default()
+                },
+            )
+            .build(deps.as_mut().storage);
+
+        let err = deflate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("keeper", &[]),
+            "uatom".to_string(),
+            max_amount_gap.into(),
+            (max_amount_gap * 2).into(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            RebalanceError::trade_error("deflate", "no slippage bound for keepers").into()
+        );
     }
 }
@@ -1,12 +1,12 @@
-use cosmwasm_std::{attr, coin, Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{attr, coin, BankMsg, Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
 
 use crate::{
     error::RebalanceError,
-    state::{CONFIG, INDEX_UNITS, RESERVE_UNITS, TRADE_INFOS},
+    state::{CONFIG, INDEX_UNITS, REBALANCE, RESERVE_UNITS, TRADE_INFOS},
     StdResult,
 };
 
-use super::load_units;
+use super::{load_units, pay_keeper_reward};
 
 // in the case of reserve denom, we can directly inflate the unit
 pub fn inflate_reserve(
@@ -52,6 +52,7 @@ pub fn inflate(
 ) -> StdResult<Response> {
     // state loader
     let config = CONFIG.load(deps.storage)?;
+    let rebalance = REBALANCE.load(deps.storage)?;
     let trade_info = TRADE_INFOS.load(deps.storage, (&config.reserve_denom, &target_denom))?;
 
     let (index_units, reserve_units, total_supply) = load_units(deps.storage)?;
@@ -79,9 +80,25 @@ pub fn inflate(
         return Err(RebalanceError::trade_error("inflate", "over slippage allowance").into());
     }
 
+    if rebalance.manager.is_none() {
+        let max_slippage = trade_info.max_slippage("inflate")?;
+        if min_amount_out < sim_amount_out.mul_floor(Decimal::one() - max_slippage) {
+            return Err(RebalanceError::trade_error("inflate", "over slippage bound").into());
+        }
+    }
+
+    // keeper reward is taken from the output
+    let (keeper_reward, amount_out_left) = pay_keeper_reward(
+        deps.storage,
+        &rebalance,
+        &info.sender,
+        coin(sim_amount_out.u128(), &target_denom),
+        Decimal::checked_from_ratio(amount_in, trade_info.max_trade_amount)?,
+    )?;
+
     // deduct & expand stored units
     let reserve_deduct_unit = Decimal::checked_from_ratio(amount_in, total_supply)?;
-    let index_expand_unit = Decimal::checked_from_ratio(sim_amount_out, total_supply)?;
+    let index_expand_unit = Decimal::checked_from_ratio(amount_out_left, total_supply)?;
 
     // expand index unit
     let mut index_units = index_units;
@@ -109,16 +126,25 @@ pub fn inflate(
         sim_amount_out,
     );
 
-    let attrs = vec![
+    let mut attrs = vec![
         attr("method", "inflate"),
-        attr("executor", info.sender),
+        attr("executor", info.sender.as_str()),
         attr("denom", target_denom),
         attr("amount_in", amount_in.to_string()),
         attr("amount_out", sim_amount_out.to_string()),
         attr("is_reserve", "false"),
     ];
 
-    Ok(Response::new().add_message(swap_msg).add_attributes(attrs))
+    let mut resp = Response::new().add_message(swap_msg);
+    if let Some(reward) = keeper_reward {
+        attrs.push(attr("keeper_reward", reward.to_string()));
+        resp = resp.add_message(BankMsg::Send {
+            to_address: info.sender.into_string(),
+            amount: vec![reward],
+        });
+    }
+
+    Ok(resp.add_attributes(attrs))
 }
 
 #[cfg(test)]
@@ -126,13 +152,16 @@ mod tests {
     use cosmwasm_std::{
         attr,
         testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR},
-        Addr, Attribute, SubMsg, Timestamp,
+        Addr, Attribute, Decimal, SubMsg, Timestamp,
     };
     use ibcx_interface::types::SwapRoutes;
 
     use crate::{
         error::RebalanceError,
-        state::{tests::StateBuilder, Config, TradeInfo, INDEX_UNITS, RESERVE_UNITS, TRADE_INFOS},
+        state::{
+            tests::StateBuilder, Config, Rebalance, TradeInfo, INDEX_UNITS, RESERVE_UNITS,
+            TRADE_INFOS,
+        },
         test::mock_dependencies,
     };
 
@@ -215,6 +244,7 @@ mod tests {
             .with_total_supply(10000)
             .empty_index_units()
             .add_reserve_unit("ukrw", "1.0")
+            .with_rebalance(Rebalance::default())
             .add_trade_info(
                 "uosmo",
                 "ukrw",
@@ -222,6 +252,7 @@ mod tests {
                     routes: routes.clone(),
                     cooldown,
                     max_trade_amount: max_trade_amount.into(),
+                    max_slippage: Some(Decimal::percent(1)),
                     last_traded_at: Some(std_time - cooldown),
                 },
             );
@@ -255,6 +286,13 @@ mod tests {
                 max_reserve_amount / 2 + 1,
                 Err(RebalanceError::trade_error("inflate", "over slippage allowance").into()),
             ),
+            (
+                "manager",
+                std_time,
+                max_reserve_amount,
+                max_reserve_amount / 2 * 99 / 100 - 1,
+                Err(RebalanceError::trade_error("inflate", "over slippage bound").into()),
+            ),
         ];
 
         for (sender, time_in_sec, amount_in, min_amount_out, expected) in cases {
@@ -1,7 +1,7 @@
 mod deflate;
 mod inflate;
 
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Storage, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, DepsMut, Env, MessageInfo, Response, Storage, Uint128};
 use ibcx_interface::core::RebalanceTradeMsg;
 
 use deflate::{deflate, deflate_reserve};
@@ -10,7 +10,10 @@ use inflate::{inflate, inflate_reserve};
 use crate::{
     assert_sender,
     error::RebalanceError,
-    state::{Units, CONFIG, INDEX_UNITS, REBALANCE, RESERVE_UNITS, TOTAL_SUPPLY},
+    state::{
+        record_keeper_reward, Rebalance, Units, CONFIG, FEE, INDEX_UNITS, KEEPER_REWARD, REBALANCE,
+        RESERVE_UNITS, TOTAL_SUPPLY,
+    },
     StdResult,
 };
 
@@ -22,6 +25,35 @@ pub fn load_units(storage: &dyn Storage) -> StdResult<(Units, Units, Uint128)> {
     Ok((index_units, reserve_units, total_supply))
 }
 
+// pays keeper reward to the executor if the rebalance has no manager.
+// trade size is the traded amount over the max trade amount of the route.
+// returns the reward and the trade output left for the index
+pub fn pay_keeper_reward(
+    storage: &mut dyn Storage,
+    rebalance: &Rebalance,
+    keeper: &Addr,
+    output: Coin,
+    trade_size: Decimal,
+) -> StdResult<(Option<Coin>, Uint128)> {
+    let reward = match rebalance.manager {
+        Some(_) => None,
+        None => KEEPER_REWARD.may_load(storage)?,
+    };
+    let Some(reward) = reward else {
+        return Ok((None, output.amount));
+    };
+
+    let mut fee = FEE.load(storage)?;
+    let (paid, remains) = reward.take(&mut fee, output, trade_size)?;
+    FEE.save(storage, &fee)?;
+
+    if let Some(paid) = paid.as_ref() {
+        record_keeper_reward(storage, keeper, paid)?;
+    }
+
+    Ok((paid, remains))
+}
+
 // deflate / inflate the target denom
 pub fn trade(
     deps: DepsMut,
@@ -69,14 +101,19 @@ pub fn trade(
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{
+        attr, coin, coins,
         testing::{mock_env, mock_info},
-        Addr, StdError, Uint128,
+        Addr, BankMsg, Decimal, StdError, SubMsg, Uint128,
     };
     use ibcx_interface::core::RebalanceTradeMsg;
 
     use crate::{
         error::{ContractError, RebalanceError},
-        state::{Rebalance, REBALANCE},
+        state::{
+            tests::StateBuilder, Config, Fee, KeeperReward, Rebalance, StreamingFee, TradeInfo,
+            FEE, KEEPER_REWARD, KEEPER_REWARDS_PAID, REBALANCE, RESERVE_UNITS,
+            TOTAL_KEEPER_REWARDS_PAID,
+        },
         test::mock_dependencies,
     };
 
@@ -139,4 +176,192 @@ mod tests {
             assert_eq!(res, expected);
         }
     }
+
+    #[test]
+    fn test_trade_keeper_reward() {
+        let mut deps = mock_dependencies();
+
+        // 1 : 2
+        deps.querier.stargate.register_sim_swap_exact_out("0.5");
+
+        let builder = StateBuilder::default()
+            .with_config(Config {
+                reserve_denom: "uosmo".to_string(),
+                ..Default::default()
+            })
+            .with_fee(Fee::default())
+            .with_total_supply(10000)
+            .add_index_unit("uatom", "1.0")
+            .empty_reserve_units()
+            .add_trade_info(
+                "uatom",
+                "uosmo",
+                TradeInfo {
+                    routes: vec![(0, "uosmo")].into(),
+                    max_trade_amount: Uint128::new(2000),
+                    max_slippage: Some(Decimal::percent(1)),
+                    ..Default::default()
+                },
+            );
+        let rebalance = Rebalance {
+            deflation: vec![("uatom", "0.8")].into(),
+            inflation: vec![("ukrw", "1")].into(),
+            ..Default::default()
+        };
+
+        let msg = RebalanceTradeMsg::Deflate {
+            target_denom: "uatom".to_string(),
+            amount_out: Uint128::new(1000),
+            max_amount_in: Uint128::new(2000),
+        };
+
+        KEEPER_REWARD
+            .save(
+                deps.as_mut().storage,
+                &KeeperReward::TradeShare("0.01".parse().unwrap()),
+            )
+            .unwrap();
+
+        // not paid to the manager
+        builder
+            .clone()
+            .with_rebalance(Rebalance {
+                manager: Some(Addr::unchecked("manager")),
+                ..rebalance.clone()
+            })
+            .build(deps.as_mut().storage);
+
+        let res = trade(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("manager", &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert_eq!(
+            RESERVE_UNITS.load(deps.as_ref().storage).unwrap(),
+            vec![("ukrw", "0.1")].into()
+        );
+
+        // paid to the keeper out of the trade output
+        builder
+            .clone()
+            .with_rebalance(rebalance)
+            .build(deps.as_mut().storage);
+
+        let res = trade(deps.as_mut(), mock_env(), mock_info("keeper", &[]), msg).unwrap();
+        assert_eq!(
+            res.attributes.last(),
+            Some(&attr("keeper_reward", "10uosmo"))
+        );
+        assert_eq!(
+            res.messages[1],
+            SubMsg::new(BankMsg::Send {
+                to_address: "keeper".to_string(),
+                amount: coins(10, "uosmo"),
+            })
+        );
+        assert_eq!(
+            RESERVE_UNITS.load(deps.as_ref().storage).unwrap(),
+            vec![("ukrw", "0.099")].into()
+        );
+
+        // bounty out of the collected streaming fee
+        deps.querier.stargate.register_sim_swap_exact_in("0.5");
+
+        StateBuilder::default()
+            .with_fee(Fee {
+                streaming_fee: Some(StreamingFee {
+                    collected: coins(150, "uosmo"),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .add_reserve_unit("ukrw", "1.0")
+            .add_trade_info(
+                "uosmo",
+                "ukrw",
+                TradeInfo {
+                    routes: vec![(0, "ukrw")].into(),
+                    max_trade_amount: Uint128::new(1000),
+                    max_slippage: Some(Decimal::percent(1)),
+                    ..Default::default()
+                },
+            )
+            .with_rebalance(Rebalance::default())
+            .build(deps.as_mut().storage);
+        KEEPER_REWARD
+            .save(
+                deps.as_mut().storage,
+                &KeeperReward::Bounty(coin(100, "uosmo")),
+            )
+            .unwrap();
+
+        // bounty is scaled by the trade size, so dust trades can't drain the collected fee
+        for _ in 0..10 {
+            let res = trade(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("keeper", &[]),
+                RebalanceTradeMsg::Inflate {
+                    target_denom: "ukrw".to_string(),
+                    amount_in: Uint128::new(9),
+                    min_amount_out: Uint128::new(4),
+                },
+            )
+            .unwrap();
+            assert_eq!(res.messages.len(), 1);
+        }
+        assert_eq!(
+            FEE.load(deps.as_ref().storage)
+                .unwrap()
+                .streaming_fee
+                .unwrap()
+                .collected,
+            coins(150, "uosmo")
+        );
+
+        for expected in [Some("100uosmo"), Some("50uosmo"), None] {
+            let res = trade(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("keeper", &[]),
+                RebalanceTradeMsg::Inflate {
+                    target_denom: "ukrw".to_string(),
+                    amount_in: Uint128::new(1000),
+                    min_amount_out: Uint128::new(500),
+                },
+            )
+            .unwrap();
+
+            match expected {
+                Some(reward) => {
+                    assert_eq!(res.attributes.last(), Some(&attr("keeper_reward", reward)))
+                }
+                None => assert_eq!(res.messages.len(), 1),
+            }
+        }
+
+        assert_eq!(
+            FEE.load(deps.as_ref().storage)
+                .unwrap()
+                .streaming_fee
+                .unwrap()
+                .collected,
+            coins(0, "uosmo")
+        );
+        assert_eq!(
+            KEEPER_REWARDS_PAID
+                .load(deps.as_ref().storage, &Addr::unchecked("keeper"))
+                .unwrap(),
+            coins(160, "uosmo")
+        );
+        assert_eq!(
+            TOTAL_KEEPER_REWARDS_PAID
+                .load(deps.as_ref().storage)
+                .unwrap(),
+            coins(160, "uosmo")
+        );
+    }
 }
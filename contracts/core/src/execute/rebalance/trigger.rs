@@ -0,0 +1,246 @@
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+use crate::{
+    error::RebalanceError,
+    state::{calc_drift, weights_to_units, Rebalance, CONFIG, INDEX_UNITS, REBALANCE, STRATEGY},
+    StdResult,
+};
+
+use super::init::{query_prices, start};
+
+// starts a rebalance with the targets of the strategy if the interval has elapsed
+// or the portfolio has drifted from the targets beyond the threshold
+pub fn trigger(mut deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut strategy = STRATEGY
+        .may_load(deps.storage)?
+        .ok_or(RebalanceError::StrategyNotSet)?;
+
+    if REBALANCE.may_load(deps.storage)?.is_some() {
+        return Err(RebalanceError::OnRebalancing.into());
+    }
+
+    let now_in_sec = env.block.time.seconds();
+    let index_units = INDEX_UNITS.load(deps.storage)?;
+
+    let targets = strategy.query_targets(deps.as_ref(), &index_units, &config.reserve_denom)?;
+
+    let denoms = index_units
+        .iter()
+        .chain(targets.iter())
+        .map(|v| v.0.as_str());
+    let prices = query_prices(
+        deps.as_ref(),
+        &env,
+        &strategy.price_source,
+        denoms,
+        &config.reserve_denom,
+    )?;
+
+    let drift = calc_drift(&index_units, &targets, &prices)?;
+
+    let reason = if strategy.interval_elapsed(now_in_sec) {
+        "interval"
+    } else if strategy.drift_exceeded(drift) {
+        "drift"
+    } else {
+        return Err(RebalanceError::TriggerConditionNotMet {
+            next_trigger_at: (strategy.interval != 0)
+                .then(|| strategy.last_triggered_at + strategy.interval),
+            drift,
+        }
+        .into());
+    };
+
+    let (deflation, inflation) = weights_to_units(&index_units, &targets, &prices)?;
+
+    let attrs = vec![
+        attr("method", "rebalance::trigger"),
+        attr("executor", info.sender),
+        attr("strategy", strategy.contract.as_str()),
+        attr("reason", reason),
+        attr("drift", drift.to_string()),
+        attr("deflation", deflation.prettify()),
+        attr("inflation", inflation.prettify()),
+    ];
+
+    let rebalance = Rebalance {
+        manager: strategy.manager.clone(),
+        deflation,
+        inflation,
+    };
+
+    strategy.last_triggered_at = now_in_sec;
+    STRATEGY.save(deps.storage, &strategy)?;
+
+    start(deps.branch(), &env, &config.reserve_denom, rebalance)?;
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        attr,
+        testing::{mock_env, mock_info},
+        to_json_binary, Addr, ContractResult, Decimal, SystemResult, Uint128, WasmQuery,
+    };
+    use ibcx_interface::{
+        core::{GetTargetsResponse, RebalancePriceSource},
+        types::{SwapRoute, SwapRoutes},
+    };
+
+    use crate::{
+        error::{ContractError, RebalanceError},
+        state::{
+            Config, Fee, Rebalance, Strategy, TradeInfo, Units, CONFIG, FEE, INDEX_UNITS,
+            REBALANCE, STRATEGY, TOTAL_SUPPLY, TRADE_INFOS,
+        },
+        test::mock_dependencies,
+    };
+
+    use super::trigger;
+
+    fn mock_strategy(interval: u64, drift_threshold: Option<&str>, last: u64) -> Strategy {
+        Strategy {
+            contract: Addr::unchecked("strategy"),
+            interval,
+            drift_threshold: drift_threshold.map(|v| v.parse().unwrap()),
+            price_source: RebalancePriceSource::Twap {
+                sources: vec![("uatom".to_string(), 1), ("ujuno".to_string(), 2)],
+                window: 3600,
+            },
+            manager: Some(Addr::unchecked("manager")),
+            last_triggered_at: last,
+        }
+    }
+
+    #[test]
+    fn test_trigger() {
+        let now = mock_env().block.time.seconds();
+        let mut deps = mock_dependencies();
+
+        deps.querier
+            .stargate
+            .register_arithmetic_twap(vec![(1, "10"), (2, "1")]);
+        deps.querier.stargate.register_sim_swap_exact_in("1");
+
+        // current weights: uatom 2/3, ujuno 1/3
+        deps.querier.mq.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, .. } if contract_addr == "strategy" => {
+                let resp = GetTargetsResponse {
+                    targets: Units::from(vec![("uatom", "0.5"), ("ujuno", "0.5")]).into(),
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&resp).unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+
+        FEE.save(deps.as_mut().storage, &Fee::default()).unwrap();
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config {
+                    gov: Addr::unchecked("gov"),
+                    reserve_denom: "uosmo".to_string(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        TOTAL_SUPPLY
+            .save(deps.as_mut().storage, &Uint128::new(1000))
+            .unwrap();
+        INDEX_UNITS
+            .save(
+                deps.as_mut().storage,
+                &vec![("uatom", "2"), ("ujuno", "10")].into(),
+            )
+            .unwrap();
+        for (denom_in, denom_out) in [("uatom", "uosmo"), ("uosmo", "ujuno")] {
+            TRADE_INFOS
+                .save(
+                    deps.as_mut().storage,
+                    (denom_in, denom_out),
+                    &TradeInfo {
+                        routes: SwapRoutes(vec![SwapRoute::new(1, denom_out)]),
+                        max_trade_amount: Uint128::new(1000),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+        }
+
+        let err = trigger(deps.as_mut(), mock_env(), mock_info("anyone", &[])).unwrap_err();
+        assert_eq!(err, RebalanceError::StrategyNotSet.into());
+
+        // drift = 2/3 - 1/2
+        let drift: Decimal = "0.166666666666666667".parse().unwrap();
+
+        let cases = [
+            (
+                mock_strategy(3600, Some("0.2"), now - 100),
+                Err(RebalanceError::TriggerConditionNotMet {
+                    next_trigger_at: Some(now + 3500),
+                    drift,
+                }
+                .into()),
+            ),
+            (
+                mock_strategy(0, Some("0.2"), now - 100),
+                Err(RebalanceError::TriggerConditionNotMet {
+                    next_trigger_at: None,
+                    drift,
+                }
+                .into()),
+            ),
+            (mock_strategy(3600, Some("0.2"), now - 3600), Ok("interval")),
+            (mock_strategy(3600, Some("0.1"), now - 100), Ok("drift")),
+        ];
+
+        for (strategy, expected) in cases {
+            REBALANCE.remove(deps.as_mut().storage);
+            STRATEGY.save(deps.as_mut().storage, &strategy).unwrap();
+
+            let res = trigger(deps.as_mut(), mock_env(), mock_info("anyone", &[]));
+            let reason: Result<_, ContractError> = expected;
+
+            match reason {
+                Ok(reason) => {
+                    let res = res.unwrap();
+                    assert_eq!(
+                        res.attributes,
+                        vec![
+                            attr("method", "rebalance::trigger"),
+                            attr("executor", "anyone"),
+                            attr("strategy", "strategy"),
+                            attr("reason", reason),
+                            attr("drift", drift.to_string()),
+                            attr("deflation", r#"[("uatom","1.5")]"#),
+                            attr("inflation", r#"[("ujuno","5")]"#),
+                        ]
+                    );
+                    assert_eq!(
+                        REBALANCE.load(deps.as_ref().storage).unwrap(),
+                        Rebalance {
+                            manager: Some(Addr::unchecked("manager")),
+                            deflation: vec![("uatom", "1.5")].into(),
+                            inflation: vec![("ujuno", "5")].into(),
+                        }
+                    );
+                    assert_eq!(
+                        STRATEGY
+                            .load(deps.as_ref().storage)
+                            .unwrap()
+                            .last_triggered_at,
+                        now
+                    );
+                }
+                Err(err) => assert_eq!(res.unwrap_err(), err),
+            }
+        }
+
+        // already on rebalancing
+        let err = trigger(deps.as_mut(), mock_env(), mock_info("anyone", &[])).unwrap_err();
+        assert_eq!(err, RebalanceError::OnRebalancing.into());
+    }
+}
@@ -2,11 +2,13 @@ use cosmwasm_std::{
     attr, coin, coins, Addr, Api, Attribute, BankMsg, CosmosMsg, DepsMut, Env, MessageInfo,
     Response, Uint128,
 };
+use ibcx_interface::core::AccessAction;
 use osmosis_std::types::osmosis::tokenfactory::v1beta1::{MsgBurn, MsgMint};
 
 use crate::{
     error::RebalanceError,
-    state::{CONFIG, FEE, INDEX_UNITS, REBALANCE, TOTAL_SUPPLY},
+    execute::fee::make_fee_send_msgs,
+    state::{ACCESS_POLICY, CONFIG, FEE, INDEX_UNITS, REBALANCE, TOTAL_SUPPLY},
 };
 
 use crate::StdResult;
@@ -52,6 +54,15 @@ pub fn mint(
     let receiver = unwrap_addr(deps.api, receiver, &info.sender)?;
     let refund_to = unwrap_addr(deps.api, refund_to, &info.sender)?;
 
+    // access policy
+    if let Some(policy) = ACCESS_POLICY.may_load(deps.storage)? {
+        policy.assert_allowed(
+            deps.as_ref(),
+            AccessAction::Mint,
+            &[&info.sender, &receiver, &refund_to],
+        )?;
+    }
+
     // state loader
     let fee = FEE.load(deps.storage)?;
     let index_units = INDEX_UNITS.load(deps.storage)?;
@@ -60,7 +71,7 @@ pub fn mint(
     // calculate
     let refund = index_units.calc_refund_amount(info.funds, amount)?;
 
-    let mint_fee = fee.mint_fee.map(|v| (v * amount).max(Uint128::one()));
+    let mint_fee = fee.calc_mint_fee(Some(&info.sender), amount)?;
     let mint_send = amount.checked_sub(mint_fee.unwrap_or_default())?;
 
     // state applier
@@ -75,11 +86,15 @@ pub fn mint(
         amount: Some(coin(amount.u128(), &config.index_denom).into()),
     };
 
-    // send fee to collector
-    let fee_send_msg = mint_fee.map(|v| BankMsg::Send {
-        to_address: fee.collector.into_string(),
-        amount: coins(v.u128(), &config.index_denom),
-    });
+    // send fee to collectors
+    let fee_send_msgs = match mint_fee {
+        Some(v) => {
+            let distributed =
+                fee.distribute(&fee.splits.mint, &coins(v.u128(), &config.index_denom))?;
+            make_fee_send_msgs(distributed)
+        }
+        None => vec![],
+    };
 
     // send 100 - fee to minter
     let mint_send_msg = BankMsg::Send {
@@ -92,16 +107,13 @@ pub fn mint(
         amount: refund,
     };
 
-    // filter out None - if mint fee not set
-    let msgs = [
-        Some(mint_msg.into()),
-        fee_send_msg.map(|v| v.into()),
-        Some(mint_send_msg.into()),
-        Some(refund_send_msg.into()),
+    // fee_send_msgs is empty - if mint fee not set
+    let msgs: Vec<CosmosMsg> = [
+        vec![mint_msg.into()],
+        fee_send_msgs,
+        vec![mint_send_msg.into(), refund_send_msg.into()],
     ]
-    .into_iter()
-    .flatten()
-    .collect::<Vec<CosmosMsg>>();
+    .concat();
 
     let attrs = mint_event(info.sender, receiver, refund_to, amount);
 
@@ -139,6 +151,15 @@ pub fn burn(
     // addresses
     let redeem_to = unwrap_addr(deps.api, redeem_to, &info.sender)?;
 
+    // access policy
+    if let Some(policy) = ACCESS_POLICY.may_load(deps.storage)? {
+        policy.assert_allowed(
+            deps.as_ref(),
+            AccessAction::Burn,
+            &[&info.sender, &redeem_to],
+        )?;
+    }
+
     // state loader
     let config = CONFIG.load(deps.storage)?;
     let fee = FEE.load(deps.storage)?;
@@ -147,7 +168,7 @@ pub fn burn(
 
     // calculate
     let received = cw_utils::must_pay(&info, &config.index_denom)?;
-    let burn_fee = fee.burn_fee.map(|v| v * received);
+    let burn_fee = fee.calc_burn_fee(Some(&info.sender), received, env.block.time.seconds())?;
     let burn_amount = received.checked_sub(burn_fee.unwrap_or_default())?;
     let mut burn_send_amount = index_units.calc_require_amount(burn_amount);
     burn_send_amount.sort_by(|a, b| a.denom.cmp(&b.denom));
@@ -164,11 +185,15 @@ pub fn burn(
         amount: Some(coin(burn_amount.u128(), &config.index_denom).into()),
     };
 
-    // send fee to collector
-    let fee_send_msg = burn_fee.map(|v| BankMsg::Send {
-        to_address: fee.collector.into_string(),
-        amount: coins(v.u128(), &config.index_denom),
-    });
+    // send fee to collectors
+    let fee_send_msgs = match burn_fee {
+        Some(v) => {
+            let distributed =
+                fee.distribute(&fee.splits.burn, &coins(v.u128(), &config.index_denom))?;
+            make_fee_send_msgs(distributed)
+        }
+        None => vec![],
+    };
 
     // send (100 - fee) * units to burner
     let burn_send_msg = BankMsg::Send {
@@ -176,15 +201,13 @@ pub fn burn(
         amount: burn_send_amount,
     };
 
-    // filter out None - if mint fee not set
-    let msgs = [
-        Some(burn_msg.into()),
-        fee_send_msg.map(|v| v.into()),
-        Some(burn_send_msg.into()),
+    // fee_send_msgs is empty - if burn fee not set
+    let msgs: Vec<CosmosMsg> = [
+        vec![burn_msg.into()],
+        fee_send_msgs,
+        vec![burn_send_msg.into()],
     ]
-    .into_iter()
-    .flatten()
-    .collect::<Vec<CosmosMsg>>();
+    .concat();
 
     let attrs = burn_event(info.sender, redeem_to, burn_amount);
 
@@ -202,11 +225,16 @@ mod tests {
         testing::{mock_dependencies_with_balances, mock_env, mock_info, MOCK_CONTRACT_ADDR},
         Addr, BankMsg, Coin, CosmosMsg, Decimal, SubMsg, Uint128,
     };
+    use ibcx_interface::core::FeeTier;
     use osmosis_std::types::osmosis::tokenfactory::v1beta1::{MsgBurn, MsgMint};
 
     use crate::{
+        error::ContractError,
         execute::token::{burn_event, mint_event},
-        state::{tests::StateBuilder, Config, Fee, INDEX_UNITS, TOTAL_SUPPLY},
+        state::{
+            tests::StateBuilder, AccessPolicy, Config, Fee, FeeSchedule, FeeSplits, INDEX_UNITS,
+            TOTAL_SUPPLY,
+        },
     };
 
     use super::mint;
@@ -451,6 +479,162 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mint_burn_with_fee_splits() {
+        let env = mock_env();
+        let mut deps = mock_dependencies_with_balances(&[]);
+
+        StateBuilder::default()
+            .add_index_unit("uatom", "1.0")
+            .with_config(Config {
+                index_denom: "uibcx".to_string(),
+                ..Default::default()
+            })
+            .with_fee(Fee {
+                collector: Addr::unchecked("collector"),
+                mint_fee: Some(Decimal::from_str("0.1").unwrap()),
+                burn_fee: Some(Decimal::from_str("0.2").unwrap()),
+                splits: FeeSplits {
+                    mint: vec![
+                        (Addr::unchecked("treasury"), Decimal::percent(50)),
+                        (Addr::unchecked("methodologist"), Decimal::percent(30)),
+                        (Addr::unchecked("frontend"), Decimal::percent(20)),
+                    ],
+                    burn: vec![
+                        (Addr::unchecked("treasury"), Decimal::percent(70)),
+                        (Addr::unchecked("frontend"), Decimal::percent(30)),
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .with_total_supply(10e6 as u128)
+            .build(deps.as_mut().storage);
+
+        let amount = 100u128.into();
+        let index_units = INDEX_UNITS.load(deps.as_ref().storage).unwrap();
+        let index_funds = index_units.calc_require_amount(amount);
+
+        let mint_resp = mint(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("minter", &index_funds),
+            amount,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            mint_resp.messages[1..4],
+            [("treasury", 5), ("methodologist", 3), ("frontend", 2)]
+                .into_iter()
+                .map(|(to, fee)| SubMsg::new(BankMsg::Send {
+                    to_address: to.to_string(),
+                    amount: coins(fee, "uibcx"),
+                }))
+                .collect::<Vec<_>>()
+        );
+
+        let burn_resp = super::burn(
+            deps.as_mut(),
+            env,
+            mock_info("burner", &coins(amount.u128(), "uibcx")),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            burn_resp.messages[1..3],
+            [("treasury", 14), ("frontend", 6)]
+                .into_iter()
+                .map(|(to, fee)| SubMsg::new(BankMsg::Send {
+                    to_address: to.to_string(),
+                    amount: coins(fee, "uibcx"),
+                }))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_mint_burn_with_fee_schedule() {
+        let env = mock_env();
+        let mut deps = mock_dependencies_with_balances(&[]);
+
+        StateBuilder::default()
+            .add_index_unit("uatom", "1.0")
+            .with_config(Config {
+                index_denom: "uibcx".to_string(),
+                ..Default::default()
+            })
+            .with_fee(Fee {
+                collector: Addr::unchecked("collector"),
+                mint_fee: Some(Decimal::percent(1)),
+                burn_fee: Some(Decimal::percent(1)),
+                schedule: FeeSchedule {
+                    mint_tiers: vec![FeeTier {
+                        min_amount: Uint128::new(1000),
+                        rate: Decimal::from_str("0.005").unwrap(),
+                    }],
+                    discounts: vec![(Addr::unchecked("periphery"), Decimal::one())],
+                    min_fee: Some(Uint128::new(5)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .with_total_supply(10e6 as u128)
+            .build(deps.as_mut().storage);
+
+        let index_units = INDEX_UNITS.load(deps.as_ref().storage).unwrap();
+
+        let cases = [
+            // min fee
+            ("minter", 100u128, Some(5u128)),
+            // base rate
+            ("minter", 900, Some(9)),
+            // tier rate
+            ("minter", 2000, Some(10)),
+            // waived
+            ("periphery", 2000, None),
+        ];
+
+        for (sender, amount, fee) in cases {
+            let amount = Uint128::new(amount);
+            let index_funds = index_units.calc_require_amount(amount);
+
+            let mint_resp = mint(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(sender, &index_funds),
+                amount,
+                None,
+                None,
+            )
+            .unwrap();
+            assert_mint_resp_msgs(
+                mint_resp.messages,
+                (MOCK_CONTRACT_ADDR, amount.u128()),
+                fee.map(|v| ("collector", v)),
+                (sender, amount.u128() - fee.unwrap_or_default()),
+                (sender, &[]),
+            );
+        }
+
+        // tiers are separated by action
+        let burn_resp = super::burn(
+            deps.as_mut(),
+            env,
+            mock_info("burner", &coins(2000, "uibcx")),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            burn_resp.messages[1],
+            SubMsg::new(BankMsg::Send {
+                to_address: "collector".to_string(),
+                amount: coins(20, "uibcx"),
+            })
+        );
+    }
+
     #[test]
     fn test_burn_without_fee() {
         let env = mock_env();
@@ -493,4 +677,106 @@ mod tests {
             Uint128::from((10e6 as u128) - 100),
         );
     }
+
+    #[test]
+    fn test_mint_burn_with_allowlist() {
+        let env = mock_env();
+        let mut deps = mock_dependencies_with_balances(&[]);
+
+        StateBuilder::default()
+            .add_index_unit("uatom", "1.0")
+            .with_config(Config {
+                index_denom: "uibcx".to_string(),
+                ..Default::default()
+            })
+            .with_fee(Fee::default())
+            .with_total_supply(10e6 as u128)
+            .with_access_policy(AccessPolicy::Allowlist)
+            .add_allowlist("minter")
+            .add_allowlist("burner")
+            .build(deps.as_mut().storage);
+
+        let amount = Uint128::new(100);
+        let index_units = INDEX_UNITS.load(deps.as_ref().storage).unwrap();
+        let index_funds = index_units.calc_require_amount(amount);
+
+        let cases = [
+            (None, None, Ok(())),
+            (
+                Some("receiver"),
+                None,
+                Err(ContractError::AccessDenied {
+                    address: "receiver".to_string(),
+                    reason: "not in allowlist".to_string(),
+                }),
+            ),
+            (
+                None,
+                Some("refund_to"),
+                Err(ContractError::AccessDenied {
+                    address: "refund_to".to_string(),
+                    reason: "not in allowlist".to_string(),
+                }),
+            ),
+        ];
+
+        for (recv, rfnd, expected) in cases {
+            let res = mint(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("minter", &index_funds),
+                amount,
+                recv.map(|v| v.to_string()),
+                rfnd.map(|v| v.to_string()),
+            );
+            assert_eq!(res.map(|_| ()), expected);
+        }
+
+        let err = mint(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("stranger", &index_funds),
+            amount,
+            Some("minter".to_string()),
+            Some("minter".to_string()),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::AccessDenied {
+                address: "stranger".to_string(),
+                reason: "not in allowlist".to_string(),
+            }
+        );
+
+        let burn_cases = [
+            ("burner", None, Ok(())),
+            (
+                "burner",
+                Some("redeem_to"),
+                Err(ContractError::AccessDenied {
+                    address: "redeem_to".to_string(),
+                    reason: "not in allowlist".to_string(),
+                }),
+            ),
+            (
+                "stranger",
+                Some("burner"),
+                Err(ContractError::AccessDenied {
+                    address: "stranger".to_string(),
+                    reason: "not in allowlist".to_string(),
+                }),
+            ),
+        ];
+
+        for (sender, redeem_to, expected) in burn_cases {
+            let res = super::burn(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(sender, &coins(amount.u128(), "uibcx")),
+                redeem_to.map(|v| v.to_string()),
+            );
+            assert_eq!(res.map(|_| ()), expected);
+        }
+    }
 }
@@ -1,8 +1,10 @@
-use cosmwasm_std::{Coin, Deps, Env, Timestamp, Uint128};
+use cosmwasm_std::{coin, Addr, Coin, Deps, Env, Timestamp, Uint128};
 use ibcx_interface::{
     core::{
-        GetConfigResponse, GetFeeResponse, GetPortfolioResponse, GetRebalanceResponse,
-        GetTradeInfoResponse, ListTradeInfoResponse, PausedResponse, RebalancePayload,
+        AccessAction, CheckAccessResponse, GetAccessPolicyResponse, GetConfigResponse,
+        GetFeeResponse, GetKeeperRewardResponse, GetMetadataResponse, GetPortfolioResponse,
+        GetRebalanceResponse, GetStrategyResponse, GetTradeInfoResponse, ListAllowlistResponse,
+        ListTradeInfoResponse, PausedResponse, PerformanceFeeResponse, RebalancePayload,
         SimulateBurnResponse, SimulateMintResponse, StreamingFeeResponse, TradeInfoPayload,
     },
     range_option,
@@ -12,8 +14,9 @@ use ibcx_interface::{
 use crate::{
     error::ContractError,
     state::{
-        Config, PauseInfo, TradeInfo, CONFIG, FEE, INDEX_UNITS, PENDING_GOV, REBALANCE,
-        TOTAL_SUPPLY, TRADE_INFOS,
+        Config, PauseInfo, TradeInfo, ACCESS_POLICY, ALLOWLIST, CONFIG, FEE, INDEX_UNITS,
+        KEEPER_REWARD, KEEPER_REWARDS_PAID, METADATA, PENDING_GOV, REBALANCE, STRATEGY,
+        TOTAL_KEEPER_REWARDS_PAID, TOTAL_SUPPLY, TRADE_INFOS,
     },
     StdResult,
 };
@@ -71,16 +74,60 @@ pub fn get_fee(deps: Deps, env: Env, time: Option<u64>) -> StdResult<GetFeeRespo
         streaming_fee.collect(index_units, time_in_sec, total_supply)?;
     }
 
+    let streaming_fee = fee
+        .streaming_fee
+        .as_ref()
+        .map(|v| -> StdResult<_> {
+            // index tokens pending to be minted are distributed along with the collected fee
+            let mut accrued = v.collected.clone();
+            if !v.pending_mint.is_zero() {
+                let Config { index_denom, .. } = CONFIG.load(deps.storage)?;
+                accrued.push(coin(v.pending_mint.u128(), index_denom));
+            }
+
+            Ok(StreamingFeeResponse {
+                rate: v.rate,
+                apr: v.apr()?,
+                collected: v.collected.clone(),
+                accruals: fee.distribute(&fee.splits.streaming, &accrued)?,
+                freeze: v.freeze,
+                last_collected_at: v.last_collected_at,
+                mode: v.mode.clone(),
+                pending_mint: v.pending_mint,
+            })
+        })
+        .transpose()?;
+
+    // performance fee is crystallized on realize. reports stored state only
+    let performance_fee = fee
+        .performance_fee
+        .as_ref()
+        .map(|v| -> StdResult<_> {
+            Ok(PerformanceFeeResponse {
+                rate: v.rate,
+                price_sources: v.price_sources.clone(),
+                twap_window: v.twap_window,
+                high_water_mark: v.high_water_mark,
+                collected: v.collected.clone(),
+                accruals: fee.distribute(&fee.splits.performance, &v.collected)?,
+                freeze: v.freeze,
+                last_collected_at: v.last_collected_at,
+            })
+        })
+        .transpose()?;
+
+    let grace = fee.active_grace(time_in_sec).map(|v| v.to_response());
+
     Ok(GetFeeResponse {
         collector: fee.collector,
         mint_fee: fee.mint_fee,
         burn_fee: fee.burn_fee,
-        streaming_fee: fee.streaming_fee.map(|v| StreamingFeeResponse {
-            rate: v.rate,
-            collected: v.collected,
-            freeze: v.freeze,
-            last_collected_at: v.last_collected_at,
-        }),
+        streaming_fee,
+        performance_fee,
+        splits: fee.splits.to_response(),
+        schedule: fee.schedule.to_response(),
+        grace_period: fee.grace_period,
+        grace,
     })
 }
 
@@ -92,13 +139,11 @@ pub fn get_portfolio(deps: Deps, env: Env, time: Option<u64>) -> StdResult<GetPo
     let fee = FEE.load(deps.storage)?;
 
     let index_units = INDEX_UNITS.load(deps.storage)?;
-    let index_units = fee
+    let (index_units, total_supply) = fee
         .streaming_fee
-        .map(|mut v| -> StdResult<_> {
-            Ok(v.collect(index_units.clone(), time_in_sec, total_supply)?.0)
-        })
+        .map(|mut v| v.collect_with_supply(index_units.clone(), time_in_sec, total_supply))
         .transpose()?
-        .unwrap_or(index_units);
+        .unwrap_or((index_units, total_supply));
 
     Ok(GetPortfolioResponse {
         total_supply,
@@ -126,6 +171,7 @@ fn conv_trade_info(denom_in: String, denom_out: String, trade_info: TradeInfo) -
         routes: trade_info.routes,
         cooldown: trade_info.cooldown,
         max_trade_amount: trade_info.max_trade_amount,
+        max_slippage: trade_info.max_slippage,
         last_traded_at: trade_info.last_traded_at,
     }
 }
@@ -170,9 +216,11 @@ pub fn simulate_mint(
     amount: Uint128,
     funds: Option<Vec<Coin>>,
     time: Option<u64>,
+    sender: Option<String>,
 ) -> Result<SimulateMintResponse, ContractError> {
     let now_in_sec = env.block.time.seconds();
     let time_in_sec = time.unwrap_or(now_in_sec);
+    let sender = sender.map(|v| deps.api.addr_validate(&v)).transpose()?;
 
     let fee = FEE.load(deps.storage)?;
     let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
@@ -180,6 +228,7 @@ pub fn simulate_mint(
     let index_units = INDEX_UNITS.load(deps.storage)?;
     let index_units = fee
         .streaming_fee
+        .clone()
         .map(|mut v| -> StdResult<_> {
             Ok(v.collect(index_units.clone(), time_in_sec, total_supply)?.0)
         })
@@ -188,11 +237,14 @@ pub fn simulate_mint(
 
     let spent = index_units.calc_require_amount(amount);
     let refund = index_units.calc_refund_amount(funds.unwrap_or_else(|| spent.clone()), amount)?;
-    let mint_fee = fee.mint_fee.map(|v| v * amount);
-    let mint_send = amount.checked_sub(mint_fee.unwrap_or_default())?;
+    let mint_fee = fee
+        .calc_mint_fee(sender.as_ref(), amount)?
+        .unwrap_or_default();
+    let mint_send = amount.checked_sub(mint_fee)?;
 
     Ok(SimulateMintResponse {
         mint_amount: mint_send,
+        fee_amount: mint_fee,
         refund_amount: refund,
         fund_spent: spent,
     })
@@ -203,9 +255,11 @@ pub fn simulate_burn(
     env: Env,
     amount: Uint128,
     time: Option<u64>,
+    sender: Option<String>,
 ) -> Result<SimulateBurnResponse, ContractError> {
     let now_in_sec = env.block.time.seconds();
     let time_in_sec = time.unwrap_or(now_in_sec);
+    let sender = sender.map(|v| deps.api.addr_validate(&v)).transpose()?;
 
     let fee = FEE.load(deps.storage)?;
     let total_supply = TOTAL_SUPPLY.load(deps.storage)?;
@@ -213,18 +267,151 @@ pub fn simulate_burn(
     let index_units = INDEX_UNITS.load(deps.storage)?;
     let index_units = fee
         .streaming_fee
+        .clone()
         .map(|mut v| -> StdResult<_> {
             Ok(v.collect(index_units.clone(), time_in_sec, total_supply)?.0)
         })
         .transpose()?
         .unwrap_or(index_units);
 
-    let burn_fee = fee.burn_fee.map(|v| v * amount);
-    let burn_amount = amount.checked_sub(burn_fee.unwrap_or_default())?;
+    let burn_fee = fee
+        .calc_burn_fee(sender.as_ref(), amount, time_in_sec)?
+        .unwrap_or_default();
+    let burn_amount = amount.checked_sub(burn_fee)?;
     let burn_send_amount = index_units.calc_require_amount(burn_amount);
 
     Ok(SimulateBurnResponse {
         burn_amount, // recognize user to burn entire amount
+        fee_amount: burn_fee,
         redeem_amount: burn_send_amount,
     })
 }
+
+pub fn get_access_policy(deps: Deps) -> StdResult<GetAccessPolicyResponse> {
+    let policy = ACCESS_POLICY.may_load(deps.storage)?;
+
+    Ok(GetAccessPolicyResponse {
+        policy: policy.map(|v| v.to_payload()),
+    })
+}
+
+pub fn get_metadata(deps: Deps) -> StdResult<GetMetadataResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let metadata = METADATA.may_load(deps.storage)?;
+
+    Ok(GetMetadataResponse {
+        index_denom: config.index_denom,
+        metadata: metadata.map(|v| v.to_payload()),
+    })
+}
+
+pub fn get_strategy(deps: Deps) -> StdResult<GetStrategyResponse> {
+    let strategy = STRATEGY.may_load(deps.storage)?;
+
+    Ok(GetStrategyResponse {
+        strategy: strategy.as_ref().map(|v| v.to_payload()),
+        last_triggered_at: strategy.map(|v| v.last_triggered_at),
+    })
+}
+
+pub fn get_keeper_reward(deps: Deps, keeper: Option<String>) -> StdResult<GetKeeperRewardResponse> {
+    let reward = KEEPER_REWARD.may_load(deps.storage)?;
+    let total_paid = TOTAL_KEEPER_REWARDS_PAID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let keeper_paid = match keeper {
+        Some(keeper) => KEEPER_REWARDS_PAID
+            .may_load(deps.storage, &deps.api.addr_validate(&keeper)?)?
+            .unwrap_or_default(),
+        None => vec![],
+    };
+
+    Ok(GetKeeperRewardResponse {
+        reward: reward.map(|v| v.to_payload()),
+        total_paid,
+        keeper_paid,
+    })
+}
+
+pub fn check_access(
+    deps: Deps,
+    action: AccessAction,
+    address: String,
+) -> StdResult<CheckAccessResponse> {
+    let address = deps.api.addr_validate(&address)?;
+
+    match ACCESS_POLICY.may_load(deps.storage)? {
+        Some(policy) => policy.check(deps, action, &address),
+        None => Ok(CheckAccessResponse {
+            allowed: true,
+            reason: None,
+        }),
+    }
+}
+
+pub fn list_allowlist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    order: Option<RangeOrder>,
+) -> StdResult<ListAllowlistResponse> {
+    let start_after = start_after
+        .map(|v| deps.api.addr_validate(&v))
+        .transpose()?;
+    let ((min, max), limit, order) = range_option(start_after.as_ref(), limit, order)?;
+
+    Ok(ListAllowlistResponse(
+        ALLOWLIST
+            .keys(deps.storage, min, max, order)
+            .take(limit)
+            .collect::<Result<Vec<Addr>, _>>()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{testing::mock_env, Decimal, StdError};
+
+    use crate::{
+        error::ContractError,
+        state::{tests::StateBuilder, Fee, StreamingFee},
+        test::mock_dependencies,
+    };
+
+    use super::{get_fee, get_portfolio};
+
+    #[test]
+    fn test_query_before_last_collection() {
+        let mut deps = mock_dependencies();
+        let now = mock_env().block.time.seconds();
+
+        StateBuilder::default()
+            .with_fee(Fee {
+                streaming_fee: Some(StreamingFee {
+                    rate: Decimal::from_ratio(1u128, 1_000_000_000u128),
+                    last_collected_at: now,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .with_total_supply(10000)
+            .add_index_unit("uatom", "1.0")
+            .build(deps.as_mut().storage);
+
+        let err = ContractError::from(StdError::generic_err(format!(
+            "time {} is before the last streaming fee collection {now}",
+            now - 1
+        )));
+        assert_eq!(
+            get_fee(deps.as_ref(), mock_env(), Some(now - 1)).unwrap_err(),
+            err
+        );
+        assert_eq!(
+            get_portfolio(deps.as_ref(), mock_env(), Some(now - 1)).unwrap_err(),
+            err
+        );
+
+        get_fee(deps.as_ref(), mock_env(), Some(now)).unwrap();
+        get_portfolio(deps.as_ref(), mock_env(), Some(now + 1)).unwrap();
+    }
+}
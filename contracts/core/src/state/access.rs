@@ -0,0 +1,184 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, Deps, QueryRequest, WasmQuery};
+use ibcx_interface::core::{AccessAction, AccessPolicyPayload, CheckAccessResponse, GateQueryMsg};
+
+use crate::{error::ContractError, StdResult};
+
+use super::ALLOWLIST;
+
+#[cw_serde]
+pub enum AccessPolicy {
+    Allowlist,
+    Gate(Addr),
+}
+
+impl AccessPolicy {
+    pub fn from_payload(deps: Deps, payload: AccessPolicyPayload) -> StdResult<Self> {
+        Ok(match payload {
+            AccessPolicyPayload::Allowlist {} => AccessPolicy::Allowlist,
+            AccessPolicyPayload::Gate { contract } => {
+                AccessPolicy::Gate(deps.api.addr_validate(&contract)?)
+            }
+        })
+    }
+
+    pub fn to_payload(&self) -> AccessPolicyPayload {
+        match self {
+            AccessPolicy::Allowlist => AccessPolicyPayload::Allowlist {},
+            AccessPolicy::Gate(contract) => AccessPolicyPayload::Gate {
+                contract: contract.to_string(),
+            },
+        }
+    }
+
+    pub fn check(
+        &self,
+        deps: Deps,
+        action: AccessAction,
+        address: &Addr,
+    ) -> StdResult<CheckAccessResponse> {
+        match self {
+            AccessPolicy::Allowlist => {
+                let allowed = ALLOWLIST.has(deps.storage, address);
+
+                Ok(CheckAccessResponse {
+                    allowed,
+                    reason: (!allowed).then(|| "not in allowlist".to_string()),
+                })
+            }
+            AccessPolicy::Gate(contract) => {
+                Ok(deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                    contract_addr: contract.to_string(),
+                    msg: to_json_binary(&GateQueryMsg::CheckAccess {
+                        action,
+                        address: address.to_string(),
+                    })?,
+                }))?)
+            }
+        }
+    }
+
+    pub fn assert_allowed(
+        &self,
+        deps: Deps,
+        action: AccessAction,
+        addresses: &[&Addr],
+    ) -> StdResult<()> {
+        let mut checked: Vec<&Addr> = vec![];
+
+        for address in addresses {
+            if checked.contains(address) {
+                continue;
+            }
+
+            let resp = self.check(deps, action.clone(), address)?;
+            if !resp.allowed {
+                return Err(ContractError::AccessDenied {
+                    address: address.to_string(),
+                    reason: resp.reason.unwrap_or_else(|| "unknown".to_string()),
+                });
+            }
+
+            checked.push(address);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        from_json, to_json_binary, Addr, ContractResult, Empty, SystemResult, WasmQuery,
+    };
+    use ibcx_interface::core::{AccessAction, CheckAccessResponse, GateQueryMsg};
+
+    use crate::{error::ContractError, state::ALLOWLIST, test::mock_dependencies};
+
+    use super::AccessPolicy;
+
+    #[test]
+    fn test_allowlist() {
+        let mut deps = mock_dependencies();
+
+        ALLOWLIST
+            .save(deps.as_mut().storage, &Addr::unchecked("alice"), &Empty {})
+            .unwrap();
+
+        let policy = AccessPolicy::Allowlist;
+
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        let cases = [
+            (vec![&alice], Ok(())),
+            (vec![&alice, &alice], Ok(())),
+            (
+                vec![&alice, &bob],
+                Err(ContractError::AccessDenied {
+                    address: "bob".to_string(),
+                    reason: "not in allowlist".to_string(),
+                }),
+            ),
+        ];
+
+        for (addresses, expected) in cases {
+            assert_eq!(
+                policy.assert_allowed(deps.as_ref(), AccessAction::Mint, &addresses),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_gate() {
+        let mut deps = mock_dependencies();
+
+        deps.querier.mq.update_wasm(|req| match req {
+            WasmQuery::Smart { contract_addr, msg } => {
+                assert_eq!(contract_addr, "gate");
+
+                let GateQueryMsg::CheckAccess { action, address } = from_json(msg).unwrap();
+                let allowed = address == "alice" && action == AccessAction::Burn;
+
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&CheckAccessResponse {
+                        allowed,
+                        reason: (!allowed).then(|| "kyc required".to_string()),
+                    })
+                    .unwrap(),
+                ))
+            }
+            _ => unimplemented!(),
+        });
+
+        let policy = AccessPolicy::Gate(Addr::unchecked("gate"));
+
+        let cases = [
+            ("alice", AccessAction::Burn, Ok(())),
+            (
+                "alice",
+                AccessAction::Mint,
+                Err(ContractError::AccessDenied {
+                    address: "alice".to_string(),
+                    reason: "kyc required".to_string(),
+                }),
+            ),
+            (
+                "bob",
+                AccessAction::Burn,
+                Err(ContractError::AccessDenied {
+                    address: "bob".to_string(),
+                    reason: "kyc required".to_string(),
+                }),
+            ),
+        ];
+
+        for (address, action, expected) in cases {
+            assert_eq!(
+                policy.assert_allowed(deps.as_ref(), action, &[&Addr::unchecked(address)]),
+                expected
+            );
+        }
+    }
+}
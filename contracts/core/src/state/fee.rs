@@ -1,7 +1,15 @@
 use std::str::FromStr;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{coin, Addr, Coin, Decimal, Uint128};
+use cosmwasm_std::{
+    coin, Addr, Api, Coin, Decimal, Decimal256, QuerierWrapper, StdError, Uint128, Uint256,
+};
+use ibcx_interface::core::{
+    FeeGraceResponse, FeeSchedulePayload, FeeScheduleResponse, FeeSplitPayload, FeeSplitResponse,
+    FeeTier, StreamingFeeMode, StreamingFeePayload,
+};
+use ibcx_math::{annual_to_periodic_rate, periodic_to_annual_rate, SECONDS_PER_YEAR};
+use osmosis_std::{shim::Timestamp, types::osmosis::twap::v1beta1::TwapQuerier};
 
 use crate::{
     error::{ContractError, ValidationError},
@@ -13,6 +21,32 @@ use super::Units;
 // APR 100%
 const MAX_STREAMING_FEE_RATE: &str = "0.000000021979553";
 
+// resolves rate per second of the payload. apr is capped at 100%, and the converted rate is
+// clamped to the max rate, which is rounded from the same cap
+pub fn streaming_fee_rate(payload: &StreamingFeePayload) -> StdResult<Decimal> {
+    match (payload.rate, payload.apr) {
+        (Some(rate), None) => Ok(rate),
+        (None, Some(apr)) => {
+            if Decimal::one() < apr {
+                return Err(ValidationError::invalid_fee(
+                    "streaming_fee",
+                    format!("apr {apr} is greater than max apr 1"),
+                )
+                .into());
+            }
+
+            let max_rate = Decimal::from_str(MAX_STREAMING_FEE_RATE)?;
+            let rate: Decimal =
+                annual_to_periodic_rate(apr.into(), SECONDS_PER_YEAR)?.try_into()?;
+
+            Ok(rate.min(max_rate))
+        }
+        _ => Err(
+            ValidationError::invalid_fee("streaming_fee", "either rate or apr must be set").into(),
+        ),
+    }
+}
+
 fn rate_checker(field: &str, rate: Option<Decimal>) -> StdResult<()> {
     if rate.is_none() {
         return Ok(());
@@ -31,6 +65,202 @@ fn rate_checker(field: &str, rate: Option<Decimal>) -> StdResult<()> {
     Ok(())
 }
 
+fn split_checker(field: &str, split: &[(Addr, Decimal)]) -> StdResult<()> {
+    if split.is_empty() {
+        return Ok(());
+    }
+
+    let mut total = Decimal::zero();
+    for (i, (recipient, share)) in split.iter().enumerate() {
+        if split[..i].iter().any(|(v, _)| v == recipient) {
+            return Err(ValidationError::invalid_fee(
+                field,
+                format!("duplicate recipient {recipient}"),
+            )
+            .into());
+        }
+
+        if share.is_zero() || Decimal::one() < *share {
+            return Err(ValidationError::invalid_fee(field, format!("{share} is invalid")).into());
+        }
+
+        total = total.checked_add(*share)?;
+    }
+
+    if total != Decimal::one() {
+        return Err(ValidationError::invalid_fee(
+            field,
+            format!("sum of shares {total} must be 1"),
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct FeeSplits {
+    pub mint: Vec<(Addr, Decimal)>,
+    pub burn: Vec<(Addr, Decimal)>,
+    pub streaming: Vec<(Addr, Decimal)>,
+    pub performance: Vec<(Addr, Decimal)>,
+}
+
+impl FeeSplits {
+    pub fn from_payload(api: &dyn Api, payload: Option<FeeSplitPayload>) -> StdResult<Self> {
+        let payload = payload.unwrap_or_default();
+
+        let convert = |split: Vec<(String, Decimal)>| {
+            split
+                .into_iter()
+                .map(|(recipient, share)| Ok((api.addr_validate(&recipient)?, share)))
+                .collect::<StdResult<Vec<_>>>()
+        };
+
+        Ok(Self {
+            mint: convert(payload.mint)?,
+            burn: convert(payload.burn)?,
+            streaming: convert(payload.streaming)?,
+            performance: convert(payload.performance)?,
+        })
+    }
+
+    pub fn to_response(&self) -> FeeSplitResponse {
+        FeeSplitResponse {
+            mint: self.mint.clone(),
+            burn: self.burn.clone(),
+            streaming: self.streaming.clone(),
+            performance: self.performance.clone(),
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct FeeSchedule {
+    pub mint_tiers: Vec<FeeTier>,
+    pub burn_tiers: Vec<FeeTier>,
+    pub discounts: Vec<(Addr, Decimal)>,
+    pub min_fee: Option<Uint128>,
+}
+
+impl FeeSchedule {
+    pub fn from_payload(api: &dyn Api, payload: Option<FeeSchedulePayload>) -> StdResult<Self> {
+        let payload = payload.unwrap_or_default();
+
+        Ok(Self {
+            mint_tiers: payload.mint_tiers,
+            burn_tiers: payload.burn_tiers,
+            discounts: payload
+                .discounts
+                .into_iter()
+                .map(|(address, discount)| Ok((api.addr_validate(&address)?, discount)))
+                .collect::<StdResult<_>>()?,
+            min_fee: payload.min_fee,
+        })
+    }
+
+    pub fn to_response(&self) -> FeeScheduleResponse {
+        FeeScheduleResponse {
+            mint_tiers: self.mint_tiers.clone(),
+            burn_tiers: self.burn_tiers.clone(),
+            discounts: self.discounts.clone(),
+            min_fee: self.min_fee,
+        }
+    }
+
+    // picks rate of the largest tier not exceeding the amount. falls back to the base rate.
+    // fails if the amount can't cover the min fee
+    fn calc(
+        &self,
+        base: Option<Decimal>,
+        tiers: &[FeeTier],
+        sender: Option<&Addr>,
+        amount: Uint128,
+    ) -> StdResult<Option<Uint128>> {
+        if base.is_none() && tiers.is_empty() {
+            return Ok(None);
+        }
+
+        let rate = tiers
+            .iter()
+            .rev()
+            .find(|v| v.min_amount <= amount)
+            .map(|v| v.rate)
+            .or(base)
+            .unwrap_or_default();
+
+        let discount = sender.and_then(|s| self.discounts.iter().find(|(v, _)| v == s));
+        let rate = match discount {
+            Some((_, discount)) => rate * (Decimal::one() - *discount),
+            None => rate,
+        };
+        if rate.is_zero() {
+            return Ok(None);
+        }
+
+        let min_fee = self.min_fee.unwrap_or_default();
+        if amount < min_fee {
+            return Err(ContractError::InsufficientFunds(format!(
+                "amount {amount} is below the min fee {min_fee}"
+            )));
+        }
+
+        let fee = amount * rate;
+
+        Ok(Some(fee.max(min_fee)))
+    }
+}
+
+// burn fee kept for a while after fee changes or rebalances
+#[cw_serde]
+pub struct FeeGrace {
+    pub burn_fee: Option<Decimal>,
+    pub burn_tiers: Vec<FeeTier>,
+    pub expires_at: u64,
+}
+
+impl FeeGrace {
+    pub fn to_response(&self) -> FeeGraceResponse {
+        FeeGraceResponse {
+            burn_fee: self.burn_fee,
+            burn_tiers: self.burn_tiers.clone(),
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+fn tier_checker(field: &str, tiers: &[FeeTier]) -> StdResult<()> {
+    for (i, tier) in tiers.iter().enumerate() {
+        rate_checker(field, Some(tier.rate))?;
+
+        if i > 0 && tier.min_amount <= tiers[i - 1].min_amount {
+            return Err(ValidationError::invalid_fee(
+                field,
+                "tiers must be sorted by min_amount in ascending order",
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+// converts fee units to amounts and adds them to collected
+fn accumulate(collected: &mut Vec<Coin>, fee_units: Units, total_supply: Uint128) -> StdResult<()> {
+    for (denom, unit) in fee_units {
+        let amount = unit * total_supply;
+
+        match collected.iter_mut().find(|c| c.denom == denom) {
+            Some(origin) => origin.amount = origin.amount.checked_add(amount)?,
+            None => collected.push(coin(amount.u128(), denom)),
+        }
+    }
+
+    Ok(())
+}
+
 #[cw_serde]
 #[derive(Default)]
 pub struct StreamingFee {
@@ -38,23 +268,40 @@ pub struct StreamingFee {
     pub collected: Vec<Coin>,
     pub freeze: bool,
     pub last_collected_at: u64,
+    #[serde(default)]
+    pub mode: StreamingFeeMode,
+    // index tokens accrued in mint mode. already counted in total supply
+    #[serde(default)]
+    pub pending_mint: Uint128,
 }
 
 impl StreamingFee {
+    pub fn apr(&self) -> StdResult<Decimal> {
+        Ok(periodic_to_annual_rate(self.rate.into(), SECONDS_PER_YEAR)?.try_into()?)
+    }
+
     pub fn collect(
         &mut self,
         index_units: Units,
         now_in_sec: u64,
         total_supply: Uint128,
     ) -> Result<(Units, Option<Vec<Coin>>), ContractError> {
-        let delta = now_in_sec - self.last_collected_at;
+        // queries can be made for any time
+        let delta = now_in_sec
+            .checked_sub(self.last_collected_at)
+            .ok_or_else(|| {
+                StdError::generic_err(format!(
+                    "time {now_in_sec} is before the last streaming fee collection {}",
+                    self.last_collected_at
+                ))
+            })?;
         if self.freeze || delta == 0 {
             return Ok((index_units, None)); // not collected
         }
 
-        let rate = (Decimal::one() + self.rate)
-            .checked_pow(delta as u32)?
-            .checked_sub(Decimal::one())?;
+        // (1 + rate)^delta - 1. capped to the whole units, so it never overflows for any delta
+        let fee_rate = ibcx_math::compound_fee_rate(self.rate.into(), delta)?;
+        let rate = Decimal::try_from(fee_rate)?;
 
         // calculate new units with fee and amount of collect fees
         let (new_units, collected): (Units, Units) = index_units
@@ -69,19 +316,23 @@ impl StreamingFee {
             .into_iter()
             .unzip();
 
-        // apply to self.collected
-        for (denom, amount) in collected
-            .into_iter()
-            .map(|(denom, unit)| (denom, unit * total_supply))
-            .collect::<Vec<_>>()
-        {
-            match self.collected.iter().position(|c| c.denom == denom) {
-                Some(i) => {
-                    let origin = self.collected.get_mut(i).unwrap();
-
-                    origin.amount = origin.amount.checked_add(amount)?;
-                }
-                None => self.collected.push(coin(amount.u128(), denom)),
+        match self.mode {
+            // apply to self.collected
+            StreamingFeeMode::Units => accumulate(&mut self.collected, collected, total_supply)?,
+            // units are diluted by the same ratio,
+            // so the tokens to mint are backed by underlying assets left in the contract.
+            // saturates at the supply limit once the whole units are taken
+            StreamingFeeMode::Mint => {
+                let headroom = Uint128::MAX - total_supply;
+                let minted = Decimal256::one()
+                    .checked_sub(fee_rate)
+                    .ok()
+                    .filter(|left| !left.is_zero())
+                    .and_then(|left| fee_rate.checked_div(left).ok())
+                    .and_then(|ratio| Uint256::from(total_supply).checked_mul_floor(ratio).ok())
+                    .and_then(|minted| Uint128::try_from(minted).ok())
+                    .map_or(headroom, |minted| minted.min(headroom));
+                self.pending_mint = self.pending_mint.checked_add(minted)?;
             }
         }
 
@@ -89,6 +340,127 @@ impl StreamingFee {
 
         Ok((new_units, Some(self.collected.clone())))
     }
+
+    // same as collect, but returns total supply including index tokens accrued in mint mode
+    pub fn collect_with_supply(
+        &mut self,
+        index_units: Units,
+        now_in_sec: u64,
+        total_supply: Uint128,
+    ) -> Result<(Units, Uint128), ContractError> {
+        let pending_mint = self.pending_mint;
+        let (new_units, _) = self.collect(index_units, now_in_sec, total_supply)?;
+        let minted = self.pending_mint.checked_sub(pending_mint)?;
+
+        Ok((new_units, total_supply.checked_add(minted)?))
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct PerformanceFee {
+    pub rate: Decimal,
+    pub price_sources: Vec<(String, u64)>,
+    pub twap_window: u64,
+    // nav per index unit in the reserve denom. zero means not initialized yet
+    pub high_water_mark: Decimal,
+    pub collected: Vec<Coin>,
+    pub freeze: bool,
+    pub last_collected_at: u64,
+}
+
+impl PerformanceFee {
+    // values index units in the reserve denom using arithmetic twap of each price source
+    pub fn query_nav(
+        &self,
+        querier: &QuerierWrapper,
+        index_units: &Units,
+        reserve_denom: &str,
+        now_in_sec: u64,
+    ) -> StdResult<Decimal> {
+        let client = TwapQuerier::new(querier);
+        let start_time = Timestamp {
+            seconds: now_in_sec.saturating_sub(self.twap_window) as i64,
+            nanos: 0,
+        };
+
+        let mut nav = Decimal::zero();
+        for (denom, unit) in index_units.iter() {
+            let price = if denom == reserve_denom {
+                Decimal::one()
+            } else {
+                let (_, pool_id) = self
+                    .price_sources
+                    .iter()
+                    .find(|(v, _)| v == denom)
+                    .ok_or_else(|| {
+                        ValidationError::invalid_fee(
+                            "performance_fee",
+                            format!("missing price source: {denom}"),
+                        )
+                    })?;
+
+                let resp = client.arithmetic_twap_to_now(
+                    *pool_id,
+                    denom.clone(),
+                    reserve_denom.to_string(),
+                    Some(start_time.clone()),
+                )?;
+
+                Decimal::from_str(&resp.arithmetic_twap)?
+            };
+
+            nav = nav.checked_add(unit.checked_mul(price)?)?;
+        }
+
+        Ok(nav)
+    }
+
+    pub fn collect(
+        &mut self,
+        index_units: Units,
+        nav: Decimal,
+        now_in_sec: u64,
+        total_supply: Uint128,
+    ) -> Result<(Units, Option<Vec<Coin>>), ContractError> {
+        if self.freeze || nav.is_zero() {
+            return Ok((index_units, None)); // not collected
+        }
+
+        // first observation only sets the high-water mark
+        if self.high_water_mark.is_zero() {
+            self.high_water_mark = nav;
+            self.last_collected_at = now_in_sec;
+            return Ok((index_units, None));
+        }
+
+        if nav <= self.high_water_mark {
+            return Ok((index_units, None)); // not collected
+        }
+
+        // charge portion of nav growth, and take it out of each unit proportionally
+        let fee_nav = (nav - self.high_water_mark).checked_mul(self.rate)?;
+        let ratio = fee_nav.checked_div(nav)?;
+
+        let (new_units, collected): (Units, Units) = index_units
+            .into_iter()
+            .map(|(denom, unit)| {
+                let fee_unit = unit.checked_mul(ratio)?;
+                let new_unit = unit.checked_sub(fee_unit)?;
+
+                Ok(((denom.clone(), new_unit), (denom, fee_unit)))
+            })
+            .collect::<Result<Vec<_>, ContractError>>()?
+            .into_iter()
+            .unzip();
+
+        accumulate(&mut self.collected, collected, total_supply)?;
+
+        self.high_water_mark = nav.checked_sub(fee_nav)?;
+        self.last_collected_at = now_in_sec;
+
+        Ok((new_units, Some(self.collected.clone())))
+    }
 }
 
 #[cw_serde]
@@ -98,6 +470,16 @@ pub struct Fee {
     pub mint_fee: Option<Decimal>,
     pub burn_fee: Option<Decimal>,
     pub streaming_fee: Option<StreamingFee>,
+    pub performance_fee: Option<PerformanceFee>,
+    // empty split means the whole fee goes to the collector
+    #[serde(default)]
+    pub splits: FeeSplits,
+    #[serde(default)]
+    pub schedule: FeeSchedule,
+    #[serde(default)]
+    pub grace_period: u64,
+    #[serde(default)]
+    pub grace: Option<FeeGrace>,
 }
 
 impl Fee {
@@ -105,6 +487,10 @@ impl Fee {
         rate_checker("mint_fee", self.mint_fee)?;
         rate_checker("burn_fee", self.burn_fee)?;
         rate_checker("streaming_fee", self.streaming_fee.as_ref().map(|v| v.rate))?;
+        rate_checker(
+            "performance_fee",
+            self.performance_fee.as_ref().map(|v| v.rate),
+        )?;
 
         if let Some(streaming_fee) = &self.streaming_fee {
             let max = Decimal::from_str(MAX_STREAMING_FEE_RATE)?;
@@ -118,8 +504,173 @@ impl Fee {
             }
         }
 
+        if let Some(performance_fee) = &self.performance_fee {
+            if performance_fee.twap_window == 0 {
+                return Err(
+                    ValidationError::invalid_fee("performance_fee", "zero twap window").into(),
+                );
+            }
+
+            let sources = &performance_fee.price_sources;
+            for (i, (denom, _)) in sources.iter().enumerate() {
+                if sources[..i].iter().any(|(v, _)| v == denom) {
+                    return Err(ValidationError::invalid_fee(
+                        "performance_fee",
+                        format!("duplicate price source: {denom}"),
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn check_schedule(&self) -> StdResult<()> {
+        tier_checker("schedule.mint_tiers", &self.schedule.mint_tiers)?;
+        tier_checker("schedule.burn_tiers", &self.schedule.burn_tiers)?;
+
+        let discounts = &self.schedule.discounts;
+        for (i, (address, discount)) in discounts.iter().enumerate() {
+            if discounts[..i].iter().any(|(v, _)| v == address) {
+                return Err(ValidationError::invalid_fee(
+                    "schedule.discounts",
+                    format!("duplicate address {address}"),
+                )
+                .into());
+            }
+
+            if discount.is_zero() || Decimal::one() < *discount {
+                return Err(ValidationError::invalid_fee(
+                    "schedule.discounts",
+                    format!("{discount} is invalid"),
+                )
+                .into());
+            }
+        }
+
+        if self.schedule.min_fee == Some(Uint128::zero()) {
+            return Err(ValidationError::invalid_fee("schedule.min_fee", "zero min fee").into());
+        }
+
         Ok(())
     }
+
+    // fee in index tokens for minting the amount. none if no fee applies
+    pub fn calc_mint_fee(
+        &self,
+        sender: Option<&Addr>,
+        amount: Uint128,
+    ) -> StdResult<Option<Uint128>> {
+        self.schedule
+            .calc(self.mint_fee, &self.schedule.mint_tiers, sender, amount)
+    }
+
+    // fee in index tokens for burning the amount. none if no fee applies.
+    // the fee of active grace window is used if it's lower than the current one
+    pub fn calc_burn_fee(
+        &self,
+        sender: Option<&Addr>,
+        amount: Uint128,
+        now_in_sec: u64,
+    ) -> StdResult<Option<Uint128>> {
+        let burn_fee =
+            self.schedule
+                .calc(self.burn_fee, &self.schedule.burn_tiers, sender, amount)?;
+
+        match self.active_grace(now_in_sec) {
+            Some(grace) => {
+                let grace_fee =
+                    self.schedule
+                        .calc(grace.burn_fee, &grace.burn_tiers, sender, amount)?;
+                Ok(burn_fee.zip(grace_fee).map(|(a, b)| a.min(b)))
+            }
+            None => Ok(burn_fee),
+        }
+    }
+
+    pub fn active_grace(&self, now_in_sec: u64) -> Option<&FeeGrace> {
+        self.grace.as_ref().filter(|v| now_in_sec < v.expires_at)
+    }
+
+    // opens grace window with given burn fee. an active window keeps its fee and gets extended,
+    // so consecutive changes can't cut the protection short
+    pub fn start_grace(
+        &mut self,
+        burn_fee: Option<Decimal>,
+        burn_tiers: Vec<FeeTier>,
+        now_in_sec: u64,
+    ) {
+        if self.grace_period == 0 {
+            return;
+        }
+
+        let expires_at = now_in_sec + self.grace_period;
+        match self.grace.as_mut().filter(|v| now_in_sec < v.expires_at) {
+            Some(grace) => grace.expires_at = expires_at,
+            None => {
+                self.grace = Some(FeeGrace {
+                    burn_fee,
+                    burn_tiers,
+                    expires_at,
+                })
+            }
+        }
+    }
+
+    pub fn check_splits(&self) -> StdResult<()> {
+        split_checker("splits.mint", &self.splits.mint)?;
+        split_checker("splits.burn", &self.splits.burn)?;
+        split_checker("splits.streaming", &self.splits.streaming)?;
+        split_checker("splits.performance", &self.splits.performance)?;
+
+        Ok(())
+    }
+
+    // distributes funds by given split. the last recipient takes the remainder to avoid dust
+    pub fn distribute(
+        &self,
+        split: &[(Addr, Decimal)],
+        funds: &[Coin],
+    ) -> StdResult<Vec<(Addr, Vec<Coin>)>> {
+        let Some(((last, _), rest)) = split.split_last() else {
+            return Ok(vec![(self.collector.clone(), funds.to_vec())]);
+        };
+
+        let mut remains = funds.to_vec();
+        let mut distributed = vec![];
+
+        for (recipient, share) in rest {
+            let mut amount = vec![];
+            for (fund, remain) in funds.iter().zip(remains.iter_mut()) {
+                let portion = fund.amount * *share;
+                remain.amount = remain.amount.checked_sub(portion)?;
+                amount.push(coin(portion.u128(), &fund.denom));
+            }
+            distributed.push((recipient.clone(), amount));
+        }
+        distributed.push((last.clone(), remains));
+
+        Ok(distributed
+            .into_iter()
+            .map(|(recipient, amount)| {
+                let amount = amount.into_iter().filter(|v| !v.amount.is_zero());
+                (recipient, amount.collect::<Vec<_>>())
+            })
+            .filter(|(_, amount)| !amount.is_empty())
+            .collect())
+    }
+
+    // collector and recipients of accrued fees can realize them
+    pub fn can_realize(&self, addr: &Addr) -> bool {
+        self.collector == *addr
+            || self
+                .splits
+                .streaming
+                .iter()
+                .chain(self.splits.performance.iter())
+                .any(|(v, _)| v == addr)
+    }
 }
 
 impl Default for Fee {
@@ -129,6 +680,11 @@ impl Default for Fee {
             mint_fee: Default::default(),
             burn_fee: Default::default(),
             streaming_fee: Default::default(),
+            performance_fee: Default::default(),
+            splits: Default::default(),
+            schedule: Default::default(),
+            grace_period: Default::default(),
+            grace: Default::default(),
         }
     }
 }
@@ -137,11 +693,30 @@ impl Default for Fee {
 pub mod tests {
     use std::str::FromStr;
 
-    use cosmwasm_std::{Decimal, Uint128};
+    use cosmwasm_std::{coin, Addr, Decimal, QuerierWrapper, Uint128};
+    use ibcx_interface::core::{FeeTier, StreamingFeeMode, StreamingFeePayload};
 
-    use crate::{error::ValidationError, state::Units};
+    use crate::{
+        error::{ContractError, ValidationError},
+        state::Units,
+        test::mock_dependencies,
+    };
 
-    use super::{Fee, StreamingFee, MAX_STREAMING_FEE_RATE};
+    use super::{
+        streaming_fee_rate, Fee, FeeSchedule, FeeSplits, PerformanceFee, StreamingFee,
+        MAX_STREAMING_FEE_RATE,
+    };
+
+    fn split(v: &[(&str, &str)]) -> Vec<(Addr, Decimal)> {
+        v.iter()
+            .map(|(recipient, share)| {
+                (
+                    Addr::unchecked(*recipient),
+                    Decimal::from_str(share).unwrap(),
+                )
+            })
+            .collect()
+    }
 
     #[test]
     fn test_streaming_fee_collect() {
@@ -152,6 +727,7 @@ pub mod tests {
             collected: vec![],
             last_collected_at: 0,
             freeze: false,
+            ..Default::default()
         };
 
         let index_units: Units = vec![("uatom", "20000.0"), ("uosmo", "10000.0")].into();
@@ -199,6 +775,87 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_streaming_fee_collect_mint_mode() {
+        let percent_15 = Decimal::from_str("0.000000000047529").unwrap();
+
+        let mut streaming_fee = StreamingFee {
+            rate: percent_15,
+            mode: StreamingFeeMode::Mint,
+            ..Default::default()
+        };
+
+        let index_units: Units = vec![("uatom", "20000.0"), ("uosmo", "10000.0")].into();
+
+        let (mut new_units, total_supply) = streaming_fee
+            .collect_with_supply(index_units, 86400 * 365, 1000000u128.into())
+            .unwrap();
+
+        // units are diluted same as units mode, but nothing is collected
+        assert!(streaming_fee.collected.is_empty());
+        assert!(streaming_fee.pending_mint > Uint128::new(1500));
+        assert!(streaming_fee.pending_mint < Uint128::new(1504));
+        assert_eq!(
+            total_supply,
+            Uint128::new(1000000) + streaming_fee.pending_mint
+        );
+
+        let cases = [("uatom", "19970", "19970.1"), ("uosmo", "9985", "9985.1")];
+
+        for (denom, next_gt, next_lt) in cases {
+            let (_, next) = new_units.pop_key(denom).unwrap();
+            assert!(next < Decimal::from_str(next_lt).unwrap());
+            assert!(next > Decimal::from_str(next_gt).unwrap());
+
+            // new supply is still backed by the same amount of underlying assets
+            let before = Decimal::from_str(next_gt).unwrap() * Uint128::new(1000000);
+            assert!(next * total_supply >= before);
+        }
+
+        // pending amount accumulates until realized
+        let pending_mint = streaming_fee.pending_mint;
+        let (_, next_total_supply) = streaming_fee
+            .collect_with_supply(new_units, 86400 * 365 * 2, total_supply)
+            .unwrap();
+        assert!(streaming_fee.pending_mint > pending_mint * Uint128::new(2));
+        assert_eq!(
+            next_total_supply,
+            Uint128::new(1000000) + streaming_fee.pending_mint
+        );
+    }
+
+    #[test]
+    fn test_streaming_fee_collect_far_future() {
+        let mut streaming_fee = StreamingFee {
+            rate: Decimal::from_str(MAX_STREAMING_FEE_RATE).unwrap(),
+            ..Default::default()
+        };
+
+        let index_units: Units = vec![("uatom", "2.0"), ("uosmo", "1.0")].into();
+
+        // exceeds u32::MAX seconds. every unit is taken as fee instead of overflowing
+        let (new_units, _) = streaming_fee
+            .collect(index_units.clone(), 86400 * 365 * 1000, 100u128.into())
+            .unwrap();
+        assert_eq!(new_units, vec![("uatom", "0"), ("uosmo", "0")].into());
+        assert_eq!(
+            streaming_fee.collected,
+            vec![coin(200, "uatom"), coin(100, "uosmo")]
+        );
+
+        // mint mode saturates at the supply limit
+        let mut streaming_fee = StreamingFee {
+            rate: Decimal::from_str(MAX_STREAMING_FEE_RATE).unwrap(),
+            mode: StreamingFeeMode::Mint,
+            ..Default::default()
+        };
+        let (new_units, total_supply) = streaming_fee
+            .collect_with_supply(index_units, 86400 * 365 * 1000, 100u128.into())
+            .unwrap();
+        assert_eq!(new_units, vec![("uatom", "0"), ("uosmo", "0")].into());
+        assert_eq!(total_supply, Uint128::MAX);
+    }
+
     #[test]
     fn test_streaming_fee_collect_no_delta() {
         let mut streaming_fee = StreamingFee {
@@ -206,6 +863,7 @@ pub mod tests {
             collected: vec![],
             last_collected_at: 0,
             freeze: false,
+            ..Default::default()
         };
 
         let index_units: Units = vec![("uatom", "2.0"), ("uosmo", "1.0")].into();
@@ -224,6 +882,7 @@ pub mod tests {
             collected: vec![],
             last_collected_at: 0,
             freeze: true,
+            ..Default::default()
         };
 
         let index_units: Units = vec![("uatom", "2.0"), ("uosmo", "1.0")].into();
@@ -235,6 +894,152 @@ pub mod tests {
         assert_eq!(index_units, new_units);
     }
 
+    #[test]
+    fn test_performance_fee_query_nav() {
+        let mut deps = mock_dependencies();
+        deps.querier
+            .stargate
+            .register_arithmetic_twap(vec![(1, "2.5"), (2, "0.5")]);
+
+        let performance_fee = PerformanceFee {
+            price_sources: vec![("uatom".to_string(), 1), ("ujuno".to_string(), 2)],
+            twap_window: 3600,
+            ..Default::default()
+        };
+
+        let querier = QuerierWrapper::new(&deps.querier);
+
+        // 2 * 2.5 + 4 * 0.5 + 1.5
+        let index_units: Units = vec![("uatom", "2"), ("ujuno", "4"), ("uosmo", "1.5")].into();
+        assert_eq!(
+            performance_fee
+                .query_nav(&querier, &index_units, "uosmo", 86400)
+                .unwrap(),
+            Decimal::from_str("8.5").unwrap()
+        );
+
+        let index_units: Units = vec![("uatom", "2"), ("uusdc", "1")].into();
+        assert_eq!(
+            performance_fee
+                .query_nav(&querier, &index_units, "uosmo", 86400)
+                .unwrap_err(),
+            ContractError::from(ValidationError::invalid_fee(
+                "performance_fee",
+                "missing price source: uusdc"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_performance_fee_collect() {
+        let mut performance_fee = PerformanceFee {
+            rate: Decimal::percent(20),
+            ..Default::default()
+        };
+
+        let index_units: Units = vec![("uatom", "1.0"), ("uosmo", "2.0")].into();
+        let total_supply = Uint128::new(1000);
+
+        // first observation sets the high-water mark
+        let (new_units, collected) = performance_fee
+            .collect(index_units.clone(), Decimal::one(), 10, total_supply)
+            .unwrap();
+        assert_eq!((new_units, collected), (index_units.clone(), None));
+        assert_eq!(performance_fee.high_water_mark, Decimal::one());
+
+        // below the high-water mark
+        let (new_units, collected) = performance_fee
+            .collect(index_units.clone(), Decimal::percent(90), 20, total_supply)
+            .unwrap();
+        assert_eq!((new_units, collected), (index_units.clone(), None));
+        assert_eq!(performance_fee.high_water_mark, Decimal::one());
+
+        // frozen
+        performance_fee.freeze = true;
+        let (new_units, collected) = performance_fee
+            .collect(index_units.clone(), Decimal::percent(150), 30, total_supply)
+            .unwrap();
+        assert_eq!((new_units, collected), (index_units.clone(), None));
+        performance_fee.freeze = false;
+
+        // nav 1.25 -> fee 0.05 (20% of 0.25) -> 4% of each unit
+        let (new_units, collected) = performance_fee
+            .collect(index_units, Decimal::percent(125), 40, total_supply)
+            .unwrap();
+        assert_eq!(
+            new_units,
+            Units::from(vec![("uatom", "0.96"), ("uosmo", "1.92")])
+        );
+        assert_eq!(collected, Some(vec![coin(40, "uatom"), coin(80, "uosmo")]));
+        assert_eq!(performance_fee.high_water_mark, Decimal::percent(120));
+        assert_eq!(performance_fee.last_collected_at, 40);
+    }
+
+    #[test]
+    fn test_streaming_fee_rate() {
+        let payload = |rate: Option<&str>, apr: Option<&str>| StreamingFeePayload {
+            rate: rate.map(|v| Decimal::from_str(v).unwrap()),
+            apr: apr.map(|v| Decimal::from_str(v).unwrap()),
+            ..Default::default()
+        };
+
+        let cases = [
+            (
+                payload(Some("0.000000000047529"), None),
+                Ok(Decimal::from_str("0.000000000047529").unwrap()),
+            ),
+            (
+                payload(None, Some("0.5")),
+                Ok(Decimal::from_str("0.000000012857214317").unwrap()),
+            ),
+            (
+                payload(None, Some("0.99999999")),
+                Ok(Decimal::from_str("0.000000021979552992").unwrap()),
+            ),
+            (
+                payload(None, Some("1")),
+                Ok(Decimal::from_str(MAX_STREAMING_FEE_RATE).unwrap()),
+            ),
+            (
+                payload(None, Some("1.000000000000000001")),
+                Err(ValidationError::invalid_fee(
+                    "streaming_fee",
+                    "apr 1.000000000000000001 is greater than max apr 1",
+                )
+                .into()),
+            ),
+            (
+                payload(None, None),
+                Err(ValidationError::invalid_fee(
+                    "streaming_fee",
+                    "either rate or apr must be set",
+                )
+                .into()),
+            ),
+            (
+                payload(Some("0.000000000047529"), Some("0.5")),
+                Err(ValidationError::invalid_fee(
+                    "streaming_fee",
+                    "either rate or apr must be set",
+                )
+                .into()),
+            ),
+        ];
+
+        for (payload, expected) in cases {
+            assert_eq!(streaming_fee_rate(&payload), expected);
+        }
+
+        // converted rate reports the apr back
+        let streaming_fee = StreamingFee {
+            rate: streaming_fee_rate(&payload(None, Some("0.5"))).unwrap(),
+            ..Default::default()
+        };
+        let apr = streaming_fee.apr().unwrap();
+        assert!(apr <= Decimal::percent(50));
+        assert!(apr > Decimal::from_str("0.4999999").unwrap());
+    }
+
     #[test]
     fn test_fee_check_rates() {
         let cases = [
@@ -301,10 +1106,292 @@ pub mod tests {
                 },
                 Ok(()),
             ),
+            (
+                Fee {
+                    performance_fee: Some(PerformanceFee {
+                        rate: Decimal::percent(20),
+                        twap_window: 0,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                Err(ValidationError::invalid_fee("performance_fee", "zero twap window").into()),
+            ),
+            (
+                Fee {
+                    performance_fee: Some(PerformanceFee {
+                        rate: Decimal::percent(20),
+                        price_sources: vec![("uatom".to_string(), 1), ("uatom".to_string(), 2)],
+                        twap_window: 3600,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                Err(ValidationError::invalid_fee(
+                    "performance_fee",
+                    "duplicate price source: uatom",
+                )
+                .into()),
+            ),
+            (
+                Fee {
+                    performance_fee: Some(PerformanceFee {
+                        rate: Decimal::percent(20),
+                        price_sources: vec![("uatom".to_string(), 1)],
+                        twap_window: 3600,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                Ok(()),
+            ),
         ];
 
         for (fee, expected) in cases {
             assert_eq!(fee.check_rates(), expected);
         }
     }
+
+    fn tier(min_amount: u128, rate: &str) -> FeeTier {
+        FeeTier {
+            min_amount: Uint128::new(min_amount),
+            rate: Decimal::from_str(rate).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_fee_calc_with_schedule() {
+        let maker = Addr::unchecked("maker");
+        let periphery = Addr::unchecked("periphery");
+        let user = Addr::unchecked("user");
+
+        let fee = Fee {
+            mint_fee: Some(Decimal::percent(1)),
+            schedule: FeeSchedule {
+                mint_tiers: vec![tier(10000, "0.005"), tier(100000, "0.001")],
+                burn_tiers: vec![tier(10000, "0.02")],
+                discounts: vec![
+                    (maker.clone(), Decimal::percent(50)),
+                    (periphery.clone(), Decimal::one()),
+                ],
+                min_fee: Some(Uint128::new(3)),
+            },
+            ..Default::default()
+        };
+
+        let cases = [
+            // base rate below the first tier
+            (Some(&user), 1000, Some(10)),
+            (Some(&user), 10000, Some(50)),
+            (Some(&user), 99999, Some(499)),
+            (Some(&user), 200000, Some(200)),
+            // min fee
+            (Some(&user), 100, Some(3)),
+            (None, 100, Some(3)),
+            // discounts
+            (Some(&maker), 10000, Some(25)),
+            (Some(&periphery), 10000, None),
+        ];
+
+        for (sender, amount, expected) in cases {
+            assert_eq!(
+                fee.calc_mint_fee(sender, Uint128::new(amount)).unwrap(),
+                expected.map(Uint128::new)
+            );
+        }
+
+        // amount below the min fee
+        assert_eq!(
+            fee.calc_mint_fee(Some(&user), Uint128::new(2)),
+            Err(ContractError::InsufficientFunds(
+                "amount 2 is below the min fee 3".to_string()
+            ))
+        );
+        // no fee applies to the discounted sender
+        assert_eq!(
+            fee.calc_mint_fee(Some(&periphery), Uint128::new(2)),
+            Ok(None)
+        );
+
+        // tiers apply without base rate
+        assert_eq!(
+            fee.calc_burn_fee(Some(&user), Uint128::new(1000), 0),
+            Ok(None)
+        );
+        assert_eq!(
+            fee.calc_burn_fee(Some(&user), Uint128::new(10000), 0),
+            Ok(Some(Uint128::new(200)))
+        );
+    }
+
+    #[test]
+    fn test_fee_check_schedule() {
+        let cases = [
+            (
+                FeeSchedule {
+                    mint_tiers: vec![tier(100, "0.01"), tier(1000, "0.005")],
+                    burn_tiers: vec![tier(100, "0.01")],
+                    discounts: vec![(Addr::unchecked("maker"), Decimal::percent(50))],
+                    min_fee: Some(Uint128::one()),
+                },
+                Ok(()),
+            ),
+            (
+                FeeSchedule {
+                    mint_tiers: vec![tier(1000, "0.01"), tier(1000, "0.005")],
+                    ..Default::default()
+                },
+                Err(ValidationError::invalid_fee(
+                    "schedule.mint_tiers",
+                    "tiers must be sorted by min_amount in ascending order",
+                )
+                .into()),
+            ),
+            (
+                FeeSchedule {
+                    burn_tiers: vec![tier(100, "1.1")],
+                    ..Default::default()
+                },
+                Err(ValidationError::invalid_fee("schedule.burn_tiers", "1.1 is invalid").into()),
+            ),
+            (
+                FeeSchedule {
+                    discounts: vec![
+                        (Addr::unchecked("maker"), Decimal::percent(50)),
+                        (Addr::unchecked("maker"), Decimal::percent(20)),
+                    ],
+                    ..Default::default()
+                },
+                Err(
+                    ValidationError::invalid_fee("schedule.discounts", "duplicate address maker")
+                        .into(),
+                ),
+            ),
+            (
+                FeeSchedule {
+                    discounts: vec![(Addr::unchecked("maker"), Decimal::zero())],
+                    ..Default::default()
+                },
+                Err(ValidationError::invalid_fee("schedule.discounts", "0 is invalid").into()),
+            ),
+            (
+                FeeSchedule {
+                    min_fee: Some(Uint128::zero()),
+                    ..Default::default()
+                },
+                Err(ValidationError::invalid_fee("schedule.min_fee", "zero min fee").into()),
+            ),
+        ];
+
+        for (schedule, expected) in cases {
+            let fee = Fee {
+                schedule,
+                ..Default::default()
+            };
+            assert_eq!(fee.check_schedule(), expected);
+        }
+    }
+
+    #[test]
+    fn test_fee_check_splits() {
+        let cases = [
+            (FeeSplits::default(), Ok(())),
+            (
+                FeeSplits {
+                    mint: split(&[("treasury", "0.7"), ("frontend", "0.3")]),
+                    streaming: split(&[("treasury", "1")]),
+                    ..Default::default()
+                },
+                Ok(()),
+            ),
+            (
+                FeeSplits {
+                    burn: split(&[("treasury", "0.7"), ("frontend", "0.2")]),
+                    ..Default::default()
+                },
+                Err(
+                    ValidationError::invalid_fee("splits.burn", "sum of shares 0.9 must be 1")
+                        .into(),
+                ),
+            ),
+            (
+                FeeSplits {
+                    mint: split(&[("treasury", "0.5"), ("treasury", "0.5")]),
+                    ..Default::default()
+                },
+                Err(
+                    ValidationError::invalid_fee("splits.mint", "duplicate recipient treasury")
+                        .into(),
+                ),
+            ),
+            (
+                FeeSplits {
+                    streaming: split(&[("treasury", "1"), ("frontend", "0")]),
+                    ..Default::default()
+                },
+                Err(ValidationError::invalid_fee("splits.streaming", "0 is invalid").into()),
+            ),
+        ];
+
+        for (splits, expected) in cases {
+            let fee = Fee {
+                splits,
+                ..Default::default()
+            };
+            assert_eq!(fee.check_splits(), expected);
+        }
+    }
+
+    #[test]
+    fn test_fee_distribute() {
+        let fee = Fee {
+            collector: Addr::unchecked("collector"),
+            ..Default::default()
+        };
+
+        let funds = vec![coin(100, "uatom"), coin(7, "uosmo")];
+
+        // no split - everything goes to the collector
+        assert_eq!(
+            fee.distribute(&[], &funds).unwrap(),
+            vec![(Addr::unchecked("collector"), funds.clone())]
+        );
+
+        // last recipient takes the remainder
+        assert_eq!(
+            fee.distribute(
+                &split(&[
+                    ("treasury", "0.5"),
+                    ("methodologist", "0.3"),
+                    ("frontend", "0.2")
+                ]),
+                &funds
+            )
+            .unwrap(),
+            vec![
+                (
+                    Addr::unchecked("treasury"),
+                    vec![coin(50, "uatom"), coin(3, "uosmo")]
+                ),
+                (
+                    Addr::unchecked("methodologist"),
+                    vec![coin(30, "uatom"), coin(2, "uosmo")]
+                ),
+                (
+                    Addr::unchecked("frontend"),
+                    vec![coin(20, "uatom"), coin(2, "uosmo")]
+                ),
+            ]
+        );
+
+        // zero amounts are filtered out
+        assert_eq!(
+            fee.distribute(
+                &split(&[("treasury", "0.5"), ("frontend", "0.5")]),
+                &[coin(1, "uatom")]
+            )
+            .unwrap(),
+            vec![(Addr::unchecked("frontend"), vec![coin(1, "uatom")])]
+        );
+    }
 }
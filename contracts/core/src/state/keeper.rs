@@ -0,0 +1,318 @@
+use std::str::FromStr;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{coin, Addr, Coin, Decimal, Storage, Uint128};
+use ibcx_interface::core::{KeeperRewardPayload, StreamingFeeMode};
+
+use crate::{error::ValidationError, StdResult};
+
+use super::{Fee, KEEPER_REWARDS_PAID, TOTAL_KEEPER_REWARDS_PAID};
+
+// 5% of the trade output
+const MAX_KEEPER_SHARE: &str = "0.05";
+
+#[cw_serde]
+pub enum KeeperReward {
+    TradeShare(Decimal),
+    Bounty(Coin),
+}
+
+impl KeeperReward {
+    pub fn from_payload(payload: KeeperRewardPayload) -> StdResult<Self> {
+        let reward = match payload {
+            KeeperRewardPayload::TradeShare(share) => Self::TradeShare(share),
+            KeeperRewardPayload::Bounty(bounty) => Self::Bounty(bounty),
+        };
+        reward.check()?;
+
+        Ok(reward)
+    }
+
+    pub fn to_payload(&self) -> KeeperRewardPayload {
+        match self {
+            Self::TradeShare(share) => KeeperRewardPayload::TradeShare(*share),
+            Self::Bounty(bounty) => KeeperRewardPayload::Bounty(bounty.clone()),
+        }
+    }
+
+    pub fn check(&self) -> StdResult<()> {
+        match self {
+            Self::TradeShare(share) => {
+                let max_share = Decimal::from_str(MAX_KEEPER_SHARE)?;
+                if share.is_zero() || max_share < *share {
+                    return Err(ValidationError::invalid_config(
+                        "keeper_reward",
+                        format!("share must be in range of (0, {max_share}]"),
+                    )
+                    .into());
+                }
+            }
+            Self::Bounty(bounty) => {
+                if bounty.amount.is_zero() {
+                    return Err(
+                        ValidationError::invalid_config("keeper_reward", "zero bounty").into(),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // bounty is paid out of the collected streaming fee, which is never collected in mint mode
+    pub fn check_fee(&self, fee: &Fee) -> StdResult<()> {
+        if let Self::Bounty(_) = self {
+            let collected = fee
+                .streaming_fee
+                .as_ref()
+                .is_some_and(|v| v.mode == StreamingFeeMode::Units);
+            if !collected {
+                return Err(ValidationError::invalid_config(
+                    "keeper_reward",
+                    "bounty requires streaming fee in units mode",
+                )
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    // takes the reward of a trade. the share is deducted from the trade output, while
+    // the bounty is deducted from the collected streaming fee as much as available.
+    // bounty is scaled by the trade size (ratio to max trade amount), so dust trades earn nothing.
+    // returns the reward and the trade output left for the index
+    pub fn take(
+        &self,
+        fee: &mut Fee,
+        output: Coin,
+        trade_size: Decimal,
+    ) -> StdResult<(Option<Coin>, Uint128)> {
+        match self {
+            Self::TradeShare(share) => {
+                let reward = output.amount.mul_floor(*share);
+                let remains = output.amount.checked_sub(reward)?;
+
+                Ok((
+                    (!reward.is_zero()).then(|| coin(reward.u128(), output.denom)),
+                    remains,
+                ))
+            }
+            Self::Bounty(bounty) => {
+                let collected = fee
+                    .streaming_fee
+                    .as_mut()
+                    .and_then(|v| v.collected.iter_mut().find(|c| c.denom == bounty.denom));
+
+                let reward = match collected {
+                    Some(collected) => {
+                        let bounty = bounty.amount.mul_floor(trade_size.min(Decimal::one()));
+                        let reward = collected.amount.min(bounty);
+                        collected.amount = collected.amount.checked_sub(reward)?;
+                        reward
+                    }
+                    None => Uint128::zero(),
+                };
+
+                Ok((
+                    (!reward.is_zero()).then(|| coin(reward.u128(), &bounty.denom)),
+                    output.amount,
+                ))
+            }
+        }
+    }
+}
+
+fn add_coin(coins: &mut Vec<Coin>, amount: &Coin) -> StdResult<()> {
+    match coins.iter_mut().find(|c| c.denom == amount.denom) {
+        Some(origin) => origin.amount = origin.amount.checked_add(amount.amount)?,
+        None => coins.push(amount.clone()),
+    }
+
+    Ok(())
+}
+
+pub fn record_keeper_reward(
+    storage: &mut dyn Storage,
+    keeper: &Addr,
+    reward: &Coin,
+) -> StdResult<()> {
+    let mut paid = KEEPER_REWARDS_PAID
+        .may_load(storage, keeper)?
+        .unwrap_or_default();
+    add_coin(&mut paid, reward)?;
+    KEEPER_REWARDS_PAID.save(storage, keeper, &paid)?;
+
+    let mut total_paid = TOTAL_KEEPER_REWARDS_PAID
+        .may_load(storage)?
+        .unwrap_or_default();
+    add_coin(&mut total_paid, reward)?;
+    TOTAL_KEEPER_REWARDS_PAID.save(storage, &total_paid)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, coins, testing::MockStorage, Addr, Decimal, Uint128};
+    use ibcx_interface::core::{KeeperRewardPayload, StreamingFeeMode};
+
+    use crate::{
+        error::{ContractError, ValidationError},
+        state::{Fee, StreamingFee, KEEPER_REWARDS_PAID, TOTAL_KEEPER_REWARDS_PAID},
+    };
+
+    use super::{record_keeper_reward, KeeperReward};
+
+    #[test]
+    fn test_from_payload() {
+        let cases = [
+            (
+                KeeperRewardPayload::TradeShare("0.01".parse().unwrap()),
+                Ok(KeeperReward::TradeShare("0.01".parse().unwrap())),
+            ),
+            (
+                KeeperRewardPayload::TradeShare("0".parse().unwrap()),
+                Err(ValidationError::invalid_config(
+                    "keeper_reward",
+                    "share must be in range of (0, 0.05]",
+                )),
+            ),
+            (
+                KeeperRewardPayload::TradeShare("0.051".parse().unwrap()),
+                Err(ValidationError::invalid_config(
+                    "keeper_reward",
+                    "share must be in range of (0, 0.05]",
+                )),
+            ),
+            (
+                KeeperRewardPayload::Bounty(coin(100, "uosmo")),
+                Ok(KeeperReward::Bounty(coin(100, "uosmo"))),
+            ),
+            (
+                KeeperRewardPayload::Bounty(coin(0, "uosmo")),
+                Err(ValidationError::invalid_config(
+                    "keeper_reward",
+                    "zero bounty",
+                )),
+            ),
+        ];
+
+        for (payload, expected) in cases {
+            assert_eq!(
+                KeeperReward::from_payload(payload),
+                expected.map_err(ContractError::from)
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_fee() {
+        let fee = |mode| Fee {
+            streaming_fee: Some(StreamingFee {
+                mode,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let err = Err(ContractError::from(ValidationError::invalid_config(
+            "keeper_reward",
+            "bounty requires streaming fee in units mode",
+        )));
+
+        let bounty = KeeperReward::Bounty(coin(100, "uosmo"));
+        assert_eq!(bounty.check_fee(&fee(StreamingFeeMode::Units)), Ok(()));
+        assert_eq!(bounty.check_fee(&fee(StreamingFeeMode::Mint)), err);
+        assert_eq!(bounty.check_fee(&Fee::default()), err);
+
+        let share = KeeperReward::TradeShare("0.01".parse().unwrap());
+        assert_eq!(share.check_fee(&fee(StreamingFeeMode::Mint)), Ok(()));
+        assert_eq!(share.check_fee(&Fee::default()), Ok(()));
+    }
+
+    #[test]
+    fn test_take() {
+        let mut fee = Fee {
+            streaming_fee: Some(StreamingFee {
+                collected: vec![coin(150, "uosmo"), coin(1000, "uatom")],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let full = Decimal::one();
+
+        let reward = KeeperReward::TradeShare("0.01".parse().unwrap());
+        assert_eq!(
+            reward.take(&mut fee, coin(1050, "ujuno"), full).unwrap(),
+            (Some(coin(10, "ujuno")), Uint128::new(1040))
+        );
+        assert_eq!(
+            reward.take(&mut fee, coin(99, "ujuno"), full).unwrap(),
+            (None, Uint128::new(99))
+        );
+
+        // bounty is scaled by the trade size. dust trades don't take the collected fee
+        let reward = KeeperReward::Bounty(coin(100, "uosmo"));
+        for _ in 0..100 {
+            assert_eq!(
+                reward
+                    .take(&mut fee, coin(9, "ujuno"), Decimal::permille(9))
+                    .unwrap(),
+                (None, Uint128::new(9))
+            );
+        }
+        assert_eq!(
+            reward
+                .take(&mut fee, coin(500, "ujuno"), Decimal::percent(50))
+                .unwrap(),
+            (Some(coin(50, "uosmo")), Uint128::new(500))
+        );
+
+        // bounty is limited to the collected streaming fee
+        for expected in [Some(coin(100, "uosmo")), None] {
+            assert_eq!(
+                reward.take(&mut fee, coin(1000, "ujuno"), full).unwrap(),
+                (expected, Uint128::new(1000))
+            );
+        }
+        assert_eq!(
+            fee.streaming_fee.unwrap().collected,
+            vec![coin(0, "uosmo"), coin(1000, "uatom")]
+        );
+
+        let reward = KeeperReward::Bounty(coin(100, "uion"));
+        assert_eq!(
+            reward
+                .take(&mut Fee::default(), coin(1000, "ujuno"), full)
+                .unwrap(),
+            (None, Uint128::new(1000))
+        );
+    }
+
+    #[test]
+    fn test_record_keeper_reward() {
+        let mut storage = MockStorage::new();
+        let keeper = Addr::unchecked("keeper");
+
+        record_keeper_reward(&mut storage, &keeper, &coin(10, "uatom")).unwrap();
+        record_keeper_reward(&mut storage, &keeper, &coin(20, "uatom")).unwrap();
+        record_keeper_reward(&mut storage, &keeper, &coin(5, "uosmo")).unwrap();
+        record_keeper_reward(&mut storage, &Addr::unchecked("other"), &coin(7, "uosmo")).unwrap();
+
+        assert_eq!(
+            KEEPER_REWARDS_PAID.load(&storage, &keeper).unwrap(),
+            vec![coin(30, "uatom"), coin(5, "uosmo")]
+        );
+        assert_eq!(
+            TOTAL_KEEPER_REWARDS_PAID.load(&storage).unwrap(),
+            vec![coin(30, "uatom"), coin(12, "uosmo")]
+        );
+        assert_eq!(
+            KEEPER_REWARDS_PAID
+                .load(&storage, &Addr::unchecked("other"))
+                .unwrap(),
+            coins(7, "uosmo")
+        );
+    }
+}
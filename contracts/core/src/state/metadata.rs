@@ -0,0 +1,224 @@
+use cosmwasm_schema::cw_serde;
+use ibcx_interface::core::IndexMetadataPayload;
+use osmosis_std::types::{
+    cosmos::bank::v1beta1::{DenomUnit, Metadata},
+    osmosis::tokenfactory::v1beta1::MsgSetDenomMetadata,
+};
+
+use crate::{error::ValidationError, StdResult};
+
+pub const DEFAULT_DECIMALS: u32 = 6;
+pub const MAX_DECIMALS: u32 = 18;
+
+#[cw_serde]
+pub struct IndexMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub description: Option<String>,
+    pub methodology_url: Option<String>,
+    pub logo_uri: Option<String>,
+    pub decimals: u32,
+}
+
+// follows the denom format of cosmos-sdk bank module
+fn is_valid_denom(denom: &str) -> bool {
+    let mut chars = denom.chars();
+
+    (3..=128).contains(&denom.len())
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+}
+
+impl IndexMetadata {
+    pub fn from_payload(payload: IndexMetadataPayload) -> StdResult<Self> {
+        let metadata = Self {
+            name: payload.name,
+            symbol: payload.symbol,
+            description: payload.description,
+            methodology_url: payload.methodology_url,
+            logo_uri: payload.logo_uri,
+            decimals: payload.decimals.unwrap_or(DEFAULT_DECIMALS),
+        };
+        metadata.check()?;
+
+        Ok(metadata)
+    }
+
+    pub fn to_payload(&self) -> IndexMetadataPayload {
+        IndexMetadataPayload {
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            description: self.description.clone(),
+            methodology_url: self.methodology_url.clone(),
+            logo_uri: self.logo_uri.clone(),
+            decimals: Some(self.decimals),
+        }
+    }
+
+    pub fn check(&self) -> StdResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(ValidationError::invalid_config("metadata.name", "empty name").into());
+        }
+
+        if !is_valid_denom(&self.symbol) {
+            return Err(ValidationError::invalid_config(
+                "metadata.symbol",
+                format!("{} is not a valid display denom", self.symbol),
+            )
+            .into());
+        }
+
+        if self.decimals > MAX_DECIMALS {
+            return Err(ValidationError::invalid_config(
+                "metadata.decimals",
+                format!("must be less than or equal to {MAX_DECIMALS}"),
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    pub fn to_bank_metadata(&self, index_denom: &str) -> Metadata {
+        let mut denom_units = vec![DenomUnit {
+            denom: index_denom.to_string(),
+            exponent: 0,
+            aliases: vec![],
+        }];
+
+        // display denom must be one of the denom units
+        let display = match self.decimals {
+            0 => index_denom.to_string(),
+            decimals => {
+                denom_units.push(DenomUnit {
+                    denom: self.symbol.clone(),
+                    exponent: decimals,
+                    aliases: vec![],
+                });
+                self.symbol.clone()
+            }
+        };
+
+        Metadata {
+            description: self.description.clone().unwrap_or_default(),
+            denom_units,
+            base: index_denom.to_string(),
+            display,
+            name: self.name.clone(),
+            symbol: self.symbol.clone(),
+            uri: self.logo_uri.clone().unwrap_or_default(),
+            uri_hash: Default::default(),
+        }
+    }
+
+    pub fn to_msg(&self, sender: impl Into<String>, index_denom: &str) -> MsgSetDenomMetadata {
+        MsgSetDenomMetadata {
+            sender: sender.into(),
+            metadata: Some(self.to_bank_metadata(index_denom)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibcx_interface::core::IndexMetadataPayload;
+    use osmosis_std::types::cosmos::bank::v1beta1::DenomUnit;
+
+    use crate::error::{ContractError, ValidationError};
+
+    use super::IndexMetadata;
+
+    fn payload(symbol: &str, decimals: Option<u32>) -> IndexMetadataPayload {
+        IndexMetadataPayload {
+            name: "Interchain Index".to_string(),
+            symbol: symbol.to_string(),
+            description: Some("top cosmos assets".to_string()),
+            methodology_url: Some("https://ibcx.example/methodology".to_string()),
+            logo_uri: Some("https://ibcx.example/logo.svg".to_string()),
+            decimals,
+        }
+    }
+
+    #[test]
+    fn test_from_payload() {
+        let metadata = IndexMetadata::from_payload(payload("IBCX", None)).unwrap();
+        assert_eq!(metadata.decimals, 6);
+        assert_eq!(metadata.to_payload(), payload("IBCX", Some(6)));
+
+        let cases = [
+            (
+                "IX",
+                None,
+                "metadata.symbol",
+                "IX is not a valid display denom",
+            ),
+            (
+                "1BCX",
+                None,
+                "metadata.symbol",
+                "1BCX is not a valid display denom",
+            ),
+            (
+                "IB X",
+                None,
+                "metadata.symbol",
+                "IB X is not a valid display denom",
+            ),
+            (
+                "IBCX",
+                Some(19),
+                "metadata.decimals",
+                "must be less than or equal to 18",
+            ),
+        ];
+
+        for (symbol, decimals, field, reason) in cases {
+            assert_eq!(
+                IndexMetadata::from_payload(payload(symbol, decimals)).unwrap_err(),
+                ContractError::from(ValidationError::invalid_config(field, reason))
+            );
+        }
+
+        let mut empty_name = payload("IBCX", None);
+        empty_name.name = " ".to_string();
+        assert_eq!(
+            IndexMetadata::from_payload(empty_name).unwrap_err(),
+            ContractError::from(ValidationError::invalid_config(
+                "metadata.name",
+                "empty name"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_to_bank_metadata() {
+        let denom = "factory/core/uibcx";
+
+        let metadata = IndexMetadata::from_payload(payload("IBCX", None)).unwrap();
+        let bank = metadata.to_bank_metadata(denom);
+        assert_eq!(bank.base, denom);
+        assert_eq!(bank.display, "IBCX");
+        assert_eq!(bank.uri, "https://ibcx.example/logo.svg");
+        assert_eq!(
+            bank.denom_units,
+            vec![
+                DenomUnit {
+                    denom: denom.to_string(),
+                    exponent: 0,
+                    aliases: vec![],
+                },
+                DenomUnit {
+                    denom: "IBCX".to_string(),
+                    exponent: 6,
+                    aliases: vec![],
+                }
+            ]
+        );
+
+        // no display unit without decimals
+        let metadata = IndexMetadata::from_payload(payload("IBCX", Some(0))).unwrap();
+        let bank = metadata.to_bank_metadata(denom);
+        assert_eq!(bank.display, denom);
+        assert_eq!(bank.denom_units.len(), 1);
+    }
+}
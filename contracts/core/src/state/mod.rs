@@ -1,16 +1,26 @@
+mod access;
 mod config;
 mod fee;
+mod keeper;
+mod metadata;
 mod pause;
 mod rebalance;
+mod strategy;
 mod units;
 
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Coin, Empty, Uint128};
 use cw_storage_plus::{Item, Map};
 
+pub use access::AccessPolicy;
 pub use config::Config;
-pub use fee::{Fee, StreamingFee};
+pub use fee::{
+    streaming_fee_rate, Fee, FeeGrace, FeeSchedule, FeeSplits, PerformanceFee, StreamingFee,
+};
+pub use keeper::{record_keeper_reward, KeeperReward};
+pub use metadata::IndexMetadata;
 pub use pause::PauseInfo;
-pub use rebalance::{Rebalance, TradeInfo};
+pub use rebalance::{check_price_source, weights_to_units, Rebalance, TradeInfo};
+pub use strategy::{calc_drift, Strategy};
 pub use units::Units;
 
 pub const CONFIG_KEY: &str = "config";
@@ -37,15 +47,36 @@ pub const RESERVE_UNITS: Item<Units> = Item::new(RESERVE_UNITS_KEY);
 pub const TRADE_INFOS_PREFIX: &str = "trade_infos";
 pub const TRADE_INFOS: Map<(&str, &str), TradeInfo> = Map::new(TRADE_INFOS_PREFIX);
 
+pub use ibcx_interface::core::ACCESS_POLICY_KEY;
+pub const ACCESS_POLICY: Item<AccessPolicy> = Item::new(ACCESS_POLICY_KEY);
+
+pub const ALLOWLIST_PREFIX: &str = "allowlist";
+pub const ALLOWLIST: Map<&Addr, Empty> = Map::new(ALLOWLIST_PREFIX);
+
+pub const METADATA_KEY: &str = "metadata";
+pub const METADATA: Item<IndexMetadata> = Item::new(METADATA_KEY);
+
+pub const STRATEGY_KEY: &str = "strategy";
+pub const STRATEGY: Item<Strategy> = Item::new(STRATEGY_KEY);
+
+pub const KEEPER_REWARD_KEY: &str = "keeper_reward";
+pub const KEEPER_REWARD: Item<KeeperReward> = Item::new(KEEPER_REWARD_KEY);
+
+pub const KEEPER_REWARDS_PAID_PREFIX: &str = "keeper_rewards_paid";
+pub const KEEPER_REWARDS_PAID: Map<&Addr, Vec<Coin>> = Map::new(KEEPER_REWARDS_PAID_PREFIX);
+
+pub const TOTAL_KEEPER_REWARDS_PAID_KEY: &str = "total_keeper_rewards_paid";
+pub const TOTAL_KEEPER_REWARDS_PAID: Item<Vec<Coin>> = Item::new(TOTAL_KEEPER_REWARDS_PAID_KEY);
+
 #[cfg(test)]
 pub mod tests {
     use std::str::FromStr;
 
-    use cosmwasm_std::{Addr, Decimal, Env, StdResult, Storage};
+    use cosmwasm_std::{Addr, Decimal, Empty, Env, StdResult, Storage};
 
     use super::{
-        Config, Fee, PauseInfo, Rebalance, StreamingFee, TradeInfo, CONFIG, FEE, INDEX_UNITS,
-        REBALANCE, RESERVE_UNITS, TOTAL_SUPPLY, TRADE_INFOS,
+        AccessPolicy, Config, Fee, PauseInfo, Rebalance, StreamingFee, TradeInfo, ACCESS_POLICY,
+        ALLOWLIST, CONFIG, FEE, INDEX_UNITS, REBALANCE, RESERVE_UNITS, TOTAL_SUPPLY, TRADE_INFOS,
     };
 
     #[derive(Default, Clone)]
@@ -58,6 +89,9 @@ pub mod tests {
         rebalance: Option<Rebalance>,
         reserve_units: Option<Vec<(&'a str, &'a str)>>,
         trade_infos: Option<Vec<((&'a str, &'a str), TradeInfo)>>,
+
+        access_policy: Option<AccessPolicy>,
+        allowlist: Option<Vec<&'a str>>,
     }
 
     impl<'a> StateBuilder<'a> {
@@ -126,6 +160,21 @@ pub mod tests {
             self
         }
 
+        pub fn with_access_policy(mut self, access_policy: AccessPolicy) -> Self {
+            self.access_policy = Some(access_policy);
+            self
+        }
+
+        pub fn add_allowlist(mut self, address: &'a str) -> Self {
+            if let Some(allowlist) = self.allowlist.as_mut() {
+                allowlist.push(address);
+            } else {
+                self.allowlist = Some(vec![address]);
+            }
+
+            self
+        }
+
         pub fn build(self, storage: &mut dyn Storage) {
             if let Some(config) = self.config {
                 CONFIG.save(storage, &config).unwrap();
@@ -158,6 +207,18 @@ pub mod tests {
                         .unwrap();
                 }
             }
+
+            if let Some(access_policy) = self.access_policy {
+                ACCESS_POLICY.save(storage, &access_policy).unwrap();
+            }
+
+            if let Some(allowlist) = self.allowlist {
+                for address in allowlist {
+                    ALLOWLIST
+                        .save(storage, &Addr::unchecked(address), &Empty {})
+                        .unwrap();
+                }
+            }
         }
     }
 
@@ -187,10 +248,16 @@ pub mod tests {
                         collected: vec![],
                         last_collected_at: env.block.time.seconds(),
                         freeze: false,
+                        ..Default::default()
                     })
                 })
                 .transpose()
                 .unwrap(),
+            performance_fee: None,
+            splits: Default::default(),
+            schedule: Default::default(),
+            grace_period: Default::default(),
+            grace: None,
         }
     }
 }
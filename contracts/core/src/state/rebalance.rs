@@ -1,6 +1,6 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use ibcx_interface::types::SwapRoutes;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use ibcx_interface::{core::RebalancePriceSource, types::SwapRoutes};
 
 use crate::{
     error::{ContractError, RebalanceError, ValidationError},
@@ -87,11 +87,94 @@ impl Rebalance {
     }
 }
 
+pub fn check_price_source(source: &RebalancePriceSource) -> Result<(), ContractError> {
+    let reason = match source {
+        RebalancePriceSource::Twap { window: 0, .. } => "zero twap window",
+        RebalancePriceSource::Simulation { probe_amount } if probe_amount.is_zero() => {
+            "zero probe amount"
+        }
+        _ => return Ok(()),
+    };
+
+    Err(ValidationError::invalid_rebalance("price_source", reason).into())
+}
+
+fn price_of(prices: &Units, denom: &str) -> Result<Decimal, ContractError> {
+    match prices.get_key(denom) {
+        Some((_, price)) if !price.is_zero() => Ok(*price),
+        _ => Err(ValidationError::invalid_rebalance(
+            "price_source",
+            format!("missing price: {denom}"),
+        )
+        .into()),
+    }
+}
+
+// converts target weights into deflation target units and inflation weights.
+// prices are in the reserve denom, and must cover every denom of index units and targets
+pub fn weights_to_units(
+    index_units: &Units,
+    targets: &Units,
+    prices: &Units,
+) -> Result<(Units, Units), ContractError> {
+    if targets.is_empty() {
+        return Err(ValidationError::invalid_rebalance("targets", "empty").into());
+    }
+    if targets.check_duplicate() {
+        return Err(ValidationError::invalid_rebalance("targets", "duplicate denom").into());
+    }
+    if targets.iter().any(|(_, weight)| weight.is_zero()) {
+        return Err(ValidationError::invalid_rebalance("targets", "zero weight").into());
+    }
+    if targets.iter().map(|(_, weight)| *weight).sum::<Decimal>() != Decimal::one() {
+        return Err(
+            ValidationError::invalid_rebalance("targets", "weights must sum to one").into(),
+        );
+    }
+
+    // value of an index unit in the reserve denom
+    let mut nav = Decimal::zero();
+    for (denom, unit) in index_units.iter() {
+        nav = nav.checked_add(unit.checked_mul(price_of(prices, denom)?)?)?;
+    }
+    if nav.is_zero() {
+        return Err(ValidationError::invalid_rebalance("index_units", "zero nav").into());
+    }
+
+    let weight_of = |denom: &str| targets.get_key(denom).map(|v| v.1).unwrap_or_default();
+
+    // decrease units over the target value down to the target unit
+    let mut deflation = Units::default();
+    for (denom, unit) in index_units.iter() {
+        let price = price_of(prices, denom)?;
+        let target_unit = nav.checked_mul(weight_of(denom))?.checked_div(price)?;
+        if target_unit < *unit {
+            deflation.extend([(denom.clone(), target_unit)]);
+        }
+    }
+
+    // distribute the reserve by the missing value of each denom
+    let mut inflation = Units::default();
+    for (denom, weight) in targets.iter() {
+        let price = price_of(prices, denom)?;
+        let unit = index_units.get_key(denom).map(|v| v.1).unwrap_or_default();
+
+        let target_value = nav.checked_mul(*weight)?;
+        let current_value = unit.checked_mul(price)?;
+        if current_value < target_value {
+            inflation.extend([(denom.clone(), target_value - current_value)]);
+        }
+    }
+
+    Ok((deflation, inflation))
+}
+
 #[cw_serde]
 pub struct TradeInfo {
     pub routes: SwapRoutes,
     pub cooldown: u64,
     pub max_trade_amount: Uint128,
+    pub max_slippage: Option<Decimal>,
     pub last_traded_at: Option<u64>,
 }
 
@@ -110,6 +193,13 @@ impl TradeInfo {
         Ok(())
     }
 
+    // keepers pick the slippage tolerance of their own trades, so it's bounded
+    pub fn max_slippage(&self, method: &str) -> StdResult<Decimal> {
+        self.max_slippage.ok_or_else(|| {
+            RebalanceError::trade_error(method, "no slippage bound for keepers").into()
+        })
+    }
+
     pub fn update_last_traded_at(self, now: u64) -> Self {
         Self {
             last_traded_at: Some(now),
@@ -124,6 +214,7 @@ impl Default for TradeInfo {
             routes: SwapRoutes(vec![]),
             cooldown: Default::default(),
             max_trade_amount: Default::default(),
+            max_slippage: Default::default(),
             last_traded_at: Default::default(),
         }
     }
@@ -136,7 +227,7 @@ mod test {
         state::Units,
     };
 
-    use super::{Rebalance, TradeInfo};
+    use super::{weights_to_units, Rebalance, TradeInfo};
 
     #[test]
     fn test_rebalance_validate() {
@@ -250,4 +341,58 @@ mod test {
             Some(12345)
         );
     }
+
+    #[test]
+    fn test_weights_to_units() {
+        let index_units: Units = vec![("uatom", "2"), ("ujuno", "10")].into();
+        let prices: Units = vec![
+            ("uatom", "10"),
+            ("ujuno", "1"),
+            ("uosmo", "1"),
+            ("uion", "3"),
+        ]
+        .into();
+
+        // nav = 2 * 10 + 10 * 1 = 30
+        let targets: Units = vec![("uatom", "0.5"), ("uosmo", "0.2"), ("uion", "0.3")].into();
+        let (deflation, inflation) = weights_to_units(&index_units, &targets, &prices).unwrap();
+        assert_eq!(deflation, vec![("uatom", "1.5"), ("ujuno", "0")].into());
+        assert_eq!(inflation, vec![("uosmo", "6"), ("uion", "9")].into());
+
+        // validated by rebalance
+        Rebalance {
+            manager: None,
+            deflation,
+            inflation,
+        }
+        .validate(index_units.clone())
+        .unwrap();
+
+        let cases = [
+            (vec![], ("targets", "empty")),
+            (
+                vec![("uatom", "0.5"), ("uatom", "0.5")],
+                ("targets", "duplicate denom"),
+            ),
+            (
+                vec![("uatom", "1"), ("uosmo", "0")],
+                ("targets", "zero weight"),
+            ),
+            (
+                vec![("uatom", "0.5"), ("uosmo", "0.4")],
+                ("targets", "weights must sum to one"),
+            ),
+            (
+                vec![("uatom", "0.5"), ("ukrw", "0.5")],
+                ("price_source", "missing price: ukrw"),
+            ),
+        ];
+
+        for (targets, (field, reason)) in cases {
+            assert_eq!(
+                weights_to_units(&index_units, &targets.into(), &prices).unwrap_err(),
+                ValidationError::invalid_rebalance(field, reason).into()
+            );
+        }
+    }
 }
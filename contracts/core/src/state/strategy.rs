@@ -0,0 +1,248 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Deps};
+use ibcx_interface::core::{
+    GetTargetsResponse, RebalancePriceSource, StrategyPayload, StrategyQueryMsg,
+};
+
+use crate::{error::ValidationError, StdResult};
+
+use super::{check_price_source, Units};
+
+#[cw_serde]
+pub struct Strategy {
+    pub contract: Addr,
+    pub interval: u64,
+    pub drift_threshold: Option<Decimal>,
+    pub price_source: RebalancePriceSource,
+    pub manager: Option<Addr>,
+    pub last_triggered_at: u64,
+}
+
+impl Strategy {
+    pub fn from_payload(deps: Deps, payload: StrategyPayload, now_in_sec: u64) -> StdResult<Self> {
+        let strategy = Self {
+            contract: deps.api.addr_validate(&payload.contract)?,
+            interval: payload.interval,
+            drift_threshold: payload.drift_threshold,
+            price_source: payload.price_source,
+            manager: payload
+                .manager
+                .map(|v| deps.api.addr_validate(&v))
+                .transpose()?,
+            last_triggered_at: now_in_sec,
+        };
+        strategy.check()?;
+
+        Ok(strategy)
+    }
+
+    pub fn to_payload(&self) -> StrategyPayload {
+        StrategyPayload {
+            contract: self.contract.to_string(),
+            interval: self.interval,
+            drift_threshold: self.drift_threshold,
+            price_source: self.price_source.clone(),
+            manager: self.manager.as_ref().map(|v| v.to_string()),
+        }
+    }
+
+    pub fn check(&self) -> StdResult<()> {
+        if self.interval == 0 && self.drift_threshold.is_none() {
+            return Err(ValidationError::invalid_config("strategy", "no trigger condition").into());
+        }
+
+        if let Some(threshold) = self.drift_threshold {
+            if threshold.is_zero() || Decimal::one() < threshold {
+                return Err(ValidationError::invalid_config(
+                    "strategy.drift_threshold",
+                    "must be in range of (0, 1]",
+                )
+                .into());
+            }
+        }
+
+        // anyone can trigger, so spot prices by simulation could be moved right before it
+        if !matches!(self.price_source, RebalancePriceSource::Twap { .. }) {
+            return Err(
+                ValidationError::invalid_config("strategy.price_source", "must be twap").into(),
+            );
+        }
+        check_price_source(&self.price_source)?;
+
+        Ok(())
+    }
+
+    pub fn interval_elapsed(&self, now_in_sec: u64) -> bool {
+        self.interval != 0 && self.last_triggered_at + self.interval <= now_in_sec
+    }
+
+    pub fn drift_exceeded(&self, drift: Decimal) -> bool {
+        self.drift_threshold.is_some_and(|v| v <= drift)
+    }
+
+    pub fn query_targets(
+        &self,
+        deps: Deps,
+        index_units: &Units,
+        reserve_denom: &str,
+    ) -> StdResult<Units> {
+        let resp: GetTargetsResponse = deps.querier.query_wasm_smart(
+            &self.contract,
+            &StrategyQueryMsg::GetTargets {
+                index_units: index_units.clone().into(),
+                reserve_denom: reserve_denom.to_string(),
+            },
+        )?;
+
+        Ok(resp.targets.into())
+    }
+}
+
+// max gap between current and target weight of each denom
+pub fn calc_drift(index_units: &Units, targets: &Units, prices: &Units) -> StdResult<Decimal> {
+    let price_of = |denom: &str| prices.get_key(denom).map(|v| v.1).unwrap_or_default();
+
+    let values = index_units
+        .iter()
+        .map(|(denom, unit)| Ok((denom.clone(), unit.checked_mul(price_of(denom))?)))
+        .collect::<StdResult<Vec<_>>>()?;
+    let nav = values.iter().map(|v| v.1).sum::<Decimal>();
+    if nav.is_zero() {
+        return Ok(Decimal::zero());
+    }
+
+    let mut drift = Decimal::zero();
+    for (denom, value) in values.iter() {
+        let current = value.checked_div(nav)?;
+        let target = targets.get_key(denom).map(|v| v.1).unwrap_or_default();
+        drift = drift.max(current.abs_diff(target));
+    }
+
+    // denoms to be added
+    for (denom, target) in targets.iter() {
+        if index_units.get_key(denom).is_none() {
+            drift = drift.max(*target);
+        }
+    }
+
+    Ok(drift)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{testing::mock_dependencies, Decimal, Uint128};
+    use ibcx_interface::core::{RebalancePriceSource, StrategyPayload};
+
+    use crate::{
+        error::{ContractError, ValidationError},
+        state::Units,
+    };
+
+    use super::{calc_drift, Strategy};
+
+    fn payload(interval: u64, drift_threshold: Option<&str>) -> StrategyPayload {
+        StrategyPayload {
+            contract: "strategy".to_string(),
+            interval,
+            drift_threshold: drift_threshold.map(|v| v.parse().unwrap()),
+            price_source: RebalancePriceSource::Twap {
+                sources: vec![("uatom".to_string(), 1)],
+                window: 3600,
+            },
+            manager: None,
+        }
+    }
+
+    #[test]
+    fn test_from_payload() {
+        let deps = mock_dependencies();
+
+        let strategy = Strategy::from_payload(deps.as_ref(), payload(86400, None), 100).unwrap();
+        assert_eq!(strategy.last_triggered_at, 100);
+        assert_eq!(strategy.to_payload(), payload(86400, None));
+
+        let cases = [
+            (
+                payload(0, None),
+                ValidationError::invalid_config("strategy", "no trigger condition"),
+            ),
+            (
+                payload(0, Some("0")),
+                ValidationError::invalid_config(
+                    "strategy.drift_threshold",
+                    "must be in range of (0, 1]",
+                ),
+            ),
+            (
+                payload(0, Some("1.1")),
+                ValidationError::invalid_config(
+                    "strategy.drift_threshold",
+                    "must be in range of (0, 1]",
+                ),
+            ),
+            (
+                StrategyPayload {
+                    price_source: RebalancePriceSource::Twap {
+                        sources: vec![],
+                        window: 0,
+                    },
+                    ..payload(86400, None)
+                },
+                ValidationError::invalid_rebalance("price_source", "zero twap window"),
+            ),
+            (
+                StrategyPayload {
+                    price_source: RebalancePriceSource::Simulation {
+                        probe_amount: Uint128::new(1000),
+                    },
+                    ..payload(86400, None)
+                },
+                ValidationError::invalid_config("strategy.price_source", "must be twap"),
+            ),
+        ];
+
+        for (payload, expected) in cases {
+            assert_eq!(
+                Strategy::from_payload(deps.as_ref(), payload, 100).unwrap_err(),
+                ContractError::from(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_trigger_conditions() {
+        let deps = mock_dependencies();
+
+        let strategy =
+            Strategy::from_payload(deps.as_ref(), payload(3600, Some("0.05")), 100).unwrap();
+        assert!(!strategy.interval_elapsed(3699));
+        assert!(strategy.interval_elapsed(3700));
+        assert!(!strategy.drift_exceeded("0.049".parse().unwrap()));
+        assert!(strategy.drift_exceeded("0.05".parse().unwrap()));
+
+        let strategy =
+            Strategy::from_payload(deps.as_ref(), payload(0, Some("0.05")), 100).unwrap();
+        assert!(!strategy.interval_elapsed(u64::MAX / 2));
+    }
+
+    #[test]
+    fn test_calc_drift() {
+        let index_units: Units = vec![("uatom", "2"), ("ujuno", "10")].into();
+        let prices: Units = vec![("uatom", "10"), ("ujuno", "1"), ("uion", "3")].into();
+
+        // current weights: uatom 2/3, ujuno 1/3
+        let targets: Units = vec![("uatom", "0.6"), ("ujuno", "0.4")].into();
+        let drift = calc_drift(&index_units, &targets, &prices).unwrap();
+        assert_eq!(drift, "0.066666666666666667".parse::<Decimal>().unwrap());
+
+        // new denom
+        let targets: Units = vec![("uatom", "0.5"), ("ujuno", "0.3"), ("uion", "0.2")].into();
+        let drift = calc_drift(&index_units, &targets, &prices).unwrap();
+        assert_eq!(drift, Decimal::from_ratio(1u64, 5u64));
+
+        // removed denom
+        let targets: Units = vec![("uatom", "1")].into();
+        let drift = calc_drift(&index_units, &targets, &prices).unwrap();
+        assert_eq!(drift, "0.333333333333333334".parse::<Decimal>().unwrap());
+    }
+}
@@ -3,9 +3,12 @@ use std::{collections::BTreeMap, str::FromStr};
 use cosmwasm_std::{
     to_json_binary, Binary, ContractResult, Decimal, QuerierResult, SystemResult, Uint128,
 };
-use osmosis_std::types::osmosis::poolmanager::v1beta1::{
-    EstimateSwapExactAmountInRequest, EstimateSwapExactAmountInResponse,
-    EstimateSwapExactAmountOutRequest, EstimateSwapExactAmountOutResponse,
+use osmosis_std::types::osmosis::{
+    poolmanager::v1beta1::{
+        EstimateSwapExactAmountInRequest, EstimateSwapExactAmountInResponse,
+        EstimateSwapExactAmountOutRequest, EstimateSwapExactAmountOutResponse,
+    },
+    twap::v1beta1::{ArithmeticTwapToNowRequest, ArithmeticTwapToNowResponse},
 };
 
 type StargateHandler<'a> = Box<dyn Fn(&Binary) -> QuerierResult + 'a>;
@@ -73,6 +76,26 @@ impl<'a> StargateQuerier<'a> {
         );
     }
 
+    // prices: (pool_id, price of base asset in quote asset)
+    pub fn register_arithmetic_twap(&mut self, prices: Vec<(u64, &'a str)>) {
+        self.register_raw(
+            "/osmosis.twap.v1beta1.Query/ArithmeticTwapToNow",
+            move |req| {
+                let req: ArithmeticTwapToNowRequest = req.clone().try_into().unwrap();
+
+                let (_, price) = prices
+                    .iter()
+                    .find(|(pool_id, _)| *pool_id == req.pool_id)
+                    .expect("price not registered");
+
+                to_json_binary(&ArithmeticTwapToNowResponse {
+                    arithmetic_twap: price.to_string(),
+                })
+                .into()
+            },
+        );
+    }
+
     pub fn query(&self, path: &String, data: &Binary) -> QuerierResult {
         self.handlers.get(path).unwrap()(data)
     }
@@ -0,0 +1,456 @@
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{attr, entry_point, Addr, Env, MessageInfo, QueryResponse, Reply};
+use cosmwasm_std::{Deps, DepsMut, Response};
+use ibcx_interface::factory::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use ibcx_interface::helpers::IbcCore;
+
+use crate::state::{save_index, Config, IndexRecord, CONFIG, LATEST_INDEX_ID, PENDING_INDEX};
+use crate::{
+    error::ContractError, execute, query, CONTRACT_NAME, CONTRACT_VERSION,
+    REPLY_ID_INSTANTIATE_CORE,
+};
+
+#[entry_point]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let gov = msg
+        .gov
+        .map(|v| deps.api.addr_validate(&v))
+        .transpose()?
+        .unwrap_or(info.sender);
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            gov,
+            core_code_id: msg.core_code_id,
+            creation_fee: msg.creation_fee,
+            fee_collector: deps.api.addr_validate(&msg.fee_collector)?,
+            allowed_denoms: msg.allowed_denoms,
+        },
+    )?;
+    LATEST_INDEX_ID.save(deps.storage, &0)?;
+
+    let resp = Response::new().add_attributes(vec![attr("method", "instantiate")]);
+
+    Ok(resp)
+}
+
+#[entry_point]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    use ExecuteMsg::*;
+
+    match msg {
+        CreateIndex { label, admin, msg } => execute::create_index(deps, info, label, admin, msg),
+
+        Gov(msg) => execute::handle_gov_msg(deps, info, msg),
+    }
+}
+
+#[entry_point]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        REPLY_ID_INSTANTIATE_CORE => {
+            let res = cw_utils::parse_reply_instantiate_data(msg)?;
+            let address = Addr::unchecked(res.contract_address);
+
+            // core creates its denom in its own reply, which runs before this one
+            let core = IbcCore(address.clone());
+            let core_config = core.get_config(&deps.querier, None)?;
+
+            let pending = PENDING_INDEX.load(deps.storage)?;
+            PENDING_INDEX.remove(deps.storage);
+
+            let id = LATEST_INDEX_ID.load(deps.storage)? + 1;
+            let record = IndexRecord {
+                address,
+                index_denom: core_config.index_denom,
+                creator: pending.creator,
+                label: pending.label,
+                created_at: env.block.time.seconds(),
+            };
+            save_index(deps.storage, id, &record)?;
+            LATEST_INDEX_ID.save(deps.storage, &id)?;
+
+            let resp = Response::new().add_attributes(vec![
+                attr("method", "reply_instantiate_core"),
+                attr("id", id.to_string()),
+                attr("address", record.address),
+                attr("index_denom", record.index_denom),
+                attr("creator", record.creator),
+            ]);
+
+            Ok(resp)
+        }
+        _ => Err(ContractError::InvalidReplyId(msg.id)),
+    }
+}
+
+fn to_binary<T: Serialize>(res: Result<T, ContractError>) -> Result<QueryResponse, ContractError> {
+    match res {
+        Ok(v) => Ok(cosmwasm_std::to_json_binary(&v)?),
+        Err(e) => Err(e),
+    }
+}
+
+#[entry_point]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
+    use QueryMsg::*;
+
+    match msg {
+        GetConfig {} => to_binary(query::get_config(deps)),
+        GetIndex { key } => to_binary(query::get_index(deps, key)),
+        ListIndexes {
+            start_after,
+            limit,
+            order,
+        } => to_binary(query::list_indexes(deps, start_after, limit, order)),
+        ListIndexesByCreator {
+            creator,
+            start_after,
+            limit,
+            order,
+        } => to_binary(query::list_indexes_by_creator(
+            deps,
+            creator,
+            start_after,
+            limit,
+            order,
+        )),
+    }
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    if !msg.force.unwrap_or_default() {
+        ibcx_utils::store_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    } else {
+        cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    }
+
+    Ok(Default::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        coin, from_json,
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+        to_json_binary, Binary, ContractResult, CosmosMsg, Decimal, OwnedDeps, SubMsgResponse,
+        SubMsgResult, SystemResult, WasmMsg, WasmQuery,
+    };
+    use ibcx_interface::{
+        core,
+        factory::{GetIndexResponse, GovMsg, IndexKey, ListIndexesResponse},
+    };
+
+    use super::*;
+
+    fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { contract_addr, .. } => {
+                let resp = core::GetConfigResponse {
+                    gov: Addr::unchecked("gov"),
+                    pending_gov: None,
+                    paused: core::PausedResponse {
+                        paused: false,
+                        expires_at: None,
+                    },
+                    index_denom: format!("factory/{contract_addr}/uibcx"),
+                    reserve_denom: "uosmo".to_string(),
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&resp).unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gov", &[]),
+            InstantiateMsg {
+                gov: None,
+                core_code_id: 7,
+                creation_fee: Some(coin(100, "uosmo")),
+                fee_collector: "collector".to_string(),
+                allowed_denoms: Some(vec!["uatom".to_string(), "uosmo".to_string()]),
+            },
+        )
+        .unwrap();
+
+        deps
+    }
+
+    fn core_msg(index_units: &[(&str, &str)]) -> core::InstantiateMsg {
+        core::InstantiateMsg {
+            gov: "gov".to_string(),
+            fee: Default::default(),
+            index_denom: "uibcx".to_string(),
+            index_units: index_units
+                .iter()
+                .map(|(d, u)| (d.to_string(), u.parse::<Decimal>().unwrap()))
+                .collect(),
+            reserve_denom: "uosmo".to_string(),
+            access_policy: None,
+            metadata: None,
+        }
+    }
+
+    fn create(
+        deps: DepsMut,
+        sender: &str,
+        funds: &[cosmwasm_std::Coin],
+        index_units: &[(&str, &str)],
+    ) -> Result<Response, ContractError> {
+        execute(
+            deps,
+            mock_env(),
+            mock_info(sender, funds),
+            ExecuteMsg::CreateIndex {
+                label: format!("index by {sender}"),
+                admin: None,
+                msg: core_msg(index_units),
+            },
+        )
+    }
+
+    // protobuf encoded MsgInstantiateContractResponse
+    fn instantiate_reply(address: &str) -> Reply {
+        let mut data = vec![0x0a, address.len() as u8];
+        data.extend_from_slice(address.as_bytes());
+
+        Reply {
+            id: REPLY_ID_INSTANTIATE_CORE,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(Binary::from(data)),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_create_index() {
+        let mut deps = setup();
+
+        let resp = create(
+            deps.as_mut(),
+            "creator",
+            &[coin(150, "uosmo"), coin(10, "uatom")],
+            &[("uatom", "1.5"), ("uosmo", "2")],
+        )
+        .unwrap();
+
+        // instantiate core with remaining funds
+        assert_eq!(resp.messages[0].id, REPLY_ID_INSTANTIATE_CORE);
+        assert_eq!(
+            resp.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Instantiate {
+                admin: None,
+                code_id: 7,
+                msg: to_json_binary(&core_msg(&[("uatom", "1.5"), ("uosmo", "2")])).unwrap(),
+                funds: vec![coin(50, "uosmo"), coin(10, "uatom")],
+                label: "index by creator".to_string(),
+            })
+        );
+
+        // forward creation fee to the collector
+        assert_eq!(
+            resp.messages[1].msg,
+            CosmosMsg::Bank(cosmwasm_std::BankMsg::Send {
+                to_address: "collector".to_string(),
+                amount: vec![coin(100, "uosmo")],
+            })
+        );
+
+        reply(deps.as_mut(), mock_env(), instantiate_reply("core1")).unwrap();
+        assert!(PENDING_INDEX
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+
+        let expected = GetIndexResponse {
+            id: 1,
+            address: Addr::unchecked("core1"),
+            index_denom: "factory/core1/uibcx".to_string(),
+            creator: Addr::unchecked("creator"),
+            label: "index by creator".to_string(),
+            created_at: mock_env().block.time.seconds(),
+        };
+
+        for key in [
+            IndexKey::Id(1),
+            IndexKey::Address("core1".to_string()),
+            IndexKey::Denom("factory/core1/uibcx".to_string()),
+        ] {
+            let resp: GetIndexResponse =
+                from_json(query(deps.as_ref(), mock_env(), QueryMsg::GetIndex { key }).unwrap())
+                    .unwrap();
+            assert_eq!(resp, expected);
+        }
+    }
+
+    #[test]
+    fn test_create_index_fee() {
+        let mut deps = setup();
+
+        let units = [("uatom", "1")];
+        for (funds, sent) in [
+            (vec![], 0),
+            (vec![coin(100, "uatom")], 0),
+            (vec![coin(99, "uosmo")], 99),
+        ] {
+            assert_eq!(
+                create(deps.as_mut(), "creator", &funds, &units).unwrap_err(),
+                ContractError::InsufficientCreationFee {
+                    required: coin(100, "uosmo"),
+                    sent: coin(sent, "uosmo"),
+                }
+            );
+        }
+
+        // no fee
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gov", &[]),
+            GovMsg::UpdateCreationFee {
+                fee: None,
+                collector: "collector".to_string(),
+            }
+            .into(),
+        )
+        .unwrap();
+
+        let resp = create(deps.as_mut(), "creator", &[], &units).unwrap();
+        assert_eq!(resp.messages.len(), 1);
+    }
+
+    #[test]
+    fn test_create_index_constituents() {
+        let mut deps = setup();
+
+        let funds = [coin(100, "uosmo")];
+        let cases = [
+            (
+                vec![],
+                ContractError::invalid_constituents("empty index units"),
+            ),
+            (
+                vec![("uatom", "0")],
+                ContractError::invalid_constituents("zero unit for denom uatom"),
+            ),
+            (
+                vec![("uatom", "1"), ("uatom", "2")],
+                ContractError::invalid_constituents("duplicate denom uatom"),
+            ),
+            (
+                vec![("uatom", "1"), ("ujuno", "2")],
+                ContractError::DenomNotAllowed("ujuno".to_string()),
+            ),
+        ];
+
+        for (units, err) in cases {
+            assert_eq!(
+                create(deps.as_mut(), "creator", &funds, &units).unwrap_err(),
+                err
+            );
+        }
+
+        // allow any denom
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gov", &[]),
+            GovMsg::UpdateAllowedDenoms(None).into(),
+        )
+        .unwrap();
+
+        create(
+            deps.as_mut(),
+            "creator",
+            &funds,
+            &[("uatom", "1"), ("ujuno", "2")],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_list_indexes_by_creator() {
+        let mut deps = setup();
+
+        let funds = [coin(100, "uosmo")];
+        for (creator, core) in [("alice", "core1"), ("bob", "core2"), ("alice", "core3")] {
+            create(deps.as_mut(), creator, &funds, &[("uatom", "1")]).unwrap();
+            reply(deps.as_mut(), mock_env(), instantiate_reply(core)).unwrap();
+        }
+
+        let list = |deps: Deps, msg: QueryMsg| -> Vec<String> {
+            let resp: ListIndexesResponse =
+                from_json(query(deps, mock_env(), msg).unwrap()).unwrap();
+            resp.0.into_iter().map(|v| v.address.to_string()).collect()
+        };
+
+        assert_eq!(
+            list(
+                deps.as_ref(),
+                QueryMsg::ListIndexes {
+                    start_after: Some(1),
+                    limit: None,
+                    order: None,
+                }
+            ),
+            vec!["core2", "core3"]
+        );
+        assert_eq!(
+            list(
+                deps.as_ref(),
+                QueryMsg::ListIndexesByCreator {
+                    creator: "alice".to_string(),
+                    start_after: None,
+                    limit: None,
+                    order: None,
+                }
+            ),
+            vec!["core1", "core3"]
+        );
+
+        let config = query::get_config(deps.as_ref()).unwrap();
+        assert_eq!(config.latest_index_id, 3);
+    }
+
+    #[test]
+    fn test_gov() {
+        let mut deps = setup();
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("abuser", &[]),
+            GovMsg::UpdateCoreCodeId(8).into(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gov", &[]),
+            GovMsg::UpdateCoreCodeId(8).into(),
+        )
+        .unwrap();
+        assert_eq!(CONFIG.load(deps.as_ref().storage).unwrap().core_code_id, 8);
+    }
+}
@@ -0,0 +1,37 @@
+use cosmwasm_std::Coin;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] cosmwasm_std::StdError),
+
+    #[error("{0}")]
+    OverflowError(#[from] cosmwasm_std::OverflowError),
+
+    #[error("{0}")]
+    ParseReplyError(#[from] cw_utils::ParseReplyError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid reply id {0}")]
+    InvalidReplyId(u64),
+
+    #[error("Insufficient creation fee. required:{required}, sent:{sent}")]
+    InsufficientCreationFee { required: Coin, sent: Coin },
+
+    #[error("Invalid constituents. reason:{reason}")]
+    InvalidConstituents { reason: String },
+
+    #[error("Denom not allowed. denom:{0}")]
+    DenomNotAllowed(String),
+}
+
+impl ContractError {
+    pub fn invalid_constituents(reason: impl ToString) -> Self {
+        Self::InvalidConstituents {
+            reason: reason.to_string(),
+        }
+    }
+}
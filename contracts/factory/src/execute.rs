@@ -0,0 +1,162 @@
+use std::collections::BTreeSet;
+
+use cosmwasm_std::{
+    attr, coin, to_json_binary, BankMsg, Coin, Decimal, DepsMut, MessageInfo, Response, SubMsg,
+    WasmMsg,
+};
+use ibcx_interface::{core, factory::GovMsg};
+
+use crate::{
+    error::ContractError,
+    state::{Config, PendingIndex, CONFIG, PENDING_INDEX},
+    REPLY_ID_INSTANTIATE_CORE,
+};
+
+pub fn check_constituents(
+    config: &Config,
+    index_units: &[(String, Decimal)],
+) -> Result<(), ContractError> {
+    if index_units.is_empty() {
+        return Err(ContractError::invalid_constituents("empty index units"));
+    }
+
+    let mut seen = BTreeSet::new();
+    for (denom, unit) in index_units {
+        if unit.is_zero() {
+            return Err(ContractError::invalid_constituents(format!(
+                "zero unit for denom {denom}"
+            )));
+        }
+        if !seen.insert(denom) {
+            return Err(ContractError::invalid_constituents(format!(
+                "duplicate denom {denom}"
+            )));
+        }
+        if let Some(allowed_denoms) = &config.allowed_denoms {
+            if !allowed_denoms.contains(denom) {
+                return Err(ContractError::DenomNotAllowed(denom.clone()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// splits the creation fee out of the funds. the rest goes to the new core
+pub fn deduct_creation_fee(
+    creation_fee: &Option<Coin>,
+    funds: Vec<Coin>,
+) -> Result<Vec<Coin>, ContractError> {
+    let Some(fee) = creation_fee else {
+        return Ok(funds);
+    };
+
+    let sent = funds
+        .iter()
+        .find(|v| v.denom == fee.denom)
+        .map(|v| v.amount)
+        .unwrap_or_default();
+    if sent < fee.amount {
+        return Err(ContractError::InsufficientCreationFee {
+            required: fee.clone(),
+            sent: coin(sent.u128(), &fee.denom),
+        });
+    }
+
+    Ok(funds
+        .into_iter()
+        .map(|v| match v.denom == fee.denom {
+            true => coin((v.amount - fee.amount).u128(), v.denom),
+            false => v,
+        })
+        .filter(|v| !v.amount.is_zero())
+        .collect())
+}
+
+pub fn create_index(
+    deps: DepsMut,
+    info: MessageInfo,
+    label: String,
+    admin: Option<String>,
+    msg: core::InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    check_constituents(&config, &msg.index_units)?;
+
+    let funds = deduct_creation_fee(&config.creation_fee, info.funds)?;
+
+    let admin = admin
+        .map(|v| deps.api.addr_validate(&v))
+        .transpose()?
+        .map(|v| v.into_string());
+
+    PENDING_INDEX.save(
+        deps.storage,
+        &PendingIndex {
+            creator: info.sender.clone(),
+            label: label.clone(),
+        },
+    )?;
+
+    let msg_instantiate = SubMsg::reply_on_success(
+        WasmMsg::Instantiate {
+            admin,
+            code_id: config.core_code_id,
+            msg: to_json_binary(&msg)?,
+            funds,
+            label: label.clone(),
+        },
+        REPLY_ID_INSTANTIATE_CORE,
+    );
+
+    let mut resp = Response::new().add_submessage(msg_instantiate);
+
+    if let Some(fee) = config.creation_fee.filter(|v| !v.amount.is_zero()) {
+        resp = resp
+            .add_message(BankMsg::Send {
+                to_address: config.fee_collector.to_string(),
+                amount: vec![fee.clone()],
+            })
+            .add_attribute("creation_fee", fee.to_string());
+    }
+
+    Ok(resp.add_attributes(vec![
+        attr("method", "create_index"),
+        attr("executor", info.sender),
+        attr("label", label),
+    ]))
+}
+
+pub fn handle_gov_msg(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: GovMsg,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    config.check_gov(&info.sender)?;
+
+    let method = match msg {
+        GovMsg::UpdateGov(gov) => {
+            config.gov = deps.api.addr_validate(&gov)?;
+            "update_gov"
+        }
+        GovMsg::UpdateCoreCodeId(code_id) => {
+            config.core_code_id = code_id;
+            "update_core_code_id"
+        }
+        GovMsg::UpdateCreationFee { fee, collector } => {
+            config.creation_fee = fee;
+            config.fee_collector = deps.api.addr_validate(&collector)?;
+            "update_creation_fee"
+        }
+        GovMsg::UpdateAllowedDenoms(allowed_denoms) => {
+            config.allowed_denoms = allowed_denoms;
+            "update_allowed_denoms"
+        }
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![attr("method", method), attr("executor", info.sender)]))
+}
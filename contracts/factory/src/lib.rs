@@ -0,0 +1,11 @@
+#[cfg(not(feature = "library"))]
+pub mod contract;
+pub mod error;
+pub mod execute;
+pub mod query;
+pub mod state;
+
+pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const REPLY_ID_INSTANTIATE_CORE: u64 = 0;
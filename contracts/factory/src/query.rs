@@ -0,0 +1,68 @@
+use cosmwasm_std::{Deps, StdResult};
+use ibcx_interface::{
+    factory::{GetConfigResponse, GetIndexResponse, IndexKey, ListIndexesResponse},
+    range_option,
+    types::RangeOrder,
+};
+
+use crate::{
+    error::ContractError,
+    state::{indexes, load_index, IndexRecord, CONFIG, LATEST_INDEX_ID},
+};
+
+pub fn get_config(deps: Deps) -> Result<GetConfigResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let latest_index_id = LATEST_INDEX_ID.load(deps.storage)?;
+
+    Ok(config.to_response(latest_index_id))
+}
+
+pub fn get_index(deps: Deps, key: IndexKey) -> Result<GetIndexResponse, ContractError> {
+    let (id, record) = load_index(deps.storage, key)?;
+
+    Ok(record.to_response(id))
+}
+
+fn index_map_conv(item: StdResult<(u64, IndexRecord)>) -> StdResult<GetIndexResponse> {
+    let (id, record) = item?;
+
+    Ok(record.to_response(id))
+}
+
+pub fn list_indexes(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    order: Option<RangeOrder>,
+) -> Result<ListIndexesResponse, ContractError> {
+    let ((min, max), limit, order) = range_option(start_after, limit, order)?;
+
+    let res = indexes()
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(index_map_conv)
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListIndexesResponse(res))
+}
+
+pub fn list_indexes_by_creator(
+    deps: Deps,
+    creator: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    order: Option<RangeOrder>,
+) -> Result<ListIndexesResponse, ContractError> {
+    let ((min, max), limit, order) = range_option(start_after, limit, order)?;
+
+    let res = indexes()
+        .idx
+        .by_creator
+        .prefix(deps.api.addr_validate(&creator)?)
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(index_map_conv)
+        .collect::<StdResult<_>>()?;
+
+    Ok(ListIndexesResponse(res))
+}
@@ -0,0 +1,116 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use ibcx_interface::factory::{GetConfigResponse, GetIndexResponse, IndexKey};
+
+use crate::error::ContractError;
+
+#[cw_serde]
+pub struct Config {
+    pub gov: Addr,
+    pub core_code_id: u64,
+    pub creation_fee: Option<Coin>,
+    pub fee_collector: Addr,
+    pub allowed_denoms: Option<Vec<String>>,
+}
+
+impl Config {
+    pub fn check_gov(&self, sender: &Addr) -> Result<(), ContractError> {
+        if self.gov != *sender {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        Ok(())
+    }
+
+    pub fn to_response(&self, latest_index_id: u64) -> GetConfigResponse {
+        GetConfigResponse {
+            gov: self.gov.clone(),
+            core_code_id: self.core_code_id,
+            creation_fee: self.creation_fee.clone(),
+            fee_collector: self.fee_collector.clone(),
+            allowed_denoms: self.allowed_denoms.clone(),
+            latest_index_id,
+        }
+    }
+}
+
+// index creation waiting for the instantiate reply of the core
+#[cw_serde]
+pub struct PendingIndex {
+    pub creator: Addr,
+    pub label: String,
+}
+
+#[cw_serde]
+pub struct IndexRecord {
+    pub address: Addr,
+    pub index_denom: String,
+    pub creator: Addr,
+    pub label: String,
+    pub created_at: u64,
+}
+
+impl IndexRecord {
+    pub fn to_response(self, id: u64) -> GetIndexResponse {
+        GetIndexResponse {
+            id,
+            address: self.address,
+            index_denom: self.index_denom,
+            creator: self.creator,
+            label: self.label,
+            created_at: self.created_at,
+        }
+    }
+}
+
+pub struct IndexRecordIndexes<'a> {
+    pub by_creator: MultiIndex<'a, Addr, IndexRecord, u64>,
+}
+
+impl<'a> IndexList<IndexRecord> for IndexRecordIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<IndexRecord>> + '_> {
+        let v: Vec<&dyn Index<IndexRecord>> = vec![&self.by_creator];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn indexes<'a>() -> IndexedMap<'a, u64, IndexRecord, IndexRecordIndexes<'a>> {
+    let indexes = IndexRecordIndexes {
+        by_creator: MultiIndex::new(|_, v| v.creator.clone(), "indexes", "indexes__by_creator"),
+    };
+    IndexedMap::new("indexes", indexes)
+}
+
+pub fn load_index(storage: &dyn Storage, key: IndexKey) -> StdResult<(u64, IndexRecord)> {
+    let id = match key {
+        IndexKey::Id(id) => id,
+        IndexKey::Address(address) => INDEX_BY_ADDRESS.load(storage, &Addr::unchecked(address))?,
+        IndexKey::Denom(denom) => INDEX_BY_DENOM.load(storage, &denom)?,
+    };
+
+    Ok((id, indexes().load(storage, id)?))
+}
+
+pub fn save_index(storage: &mut dyn Storage, id: u64, record: &IndexRecord) -> StdResult<()> {
+    indexes().save(storage, id, record)?;
+    INDEX_BY_ADDRESS.save(storage, &record.address, &id)?;
+    INDEX_BY_DENOM.save(storage, &record.index_denom, &id)?;
+
+    Ok(())
+}
+
+pub const CONFIG_KEY: &str = "config";
+pub const CONFIG: Item<Config> = Item::new(CONFIG_KEY);
+
+pub const PENDING_INDEX_KEY: &str = "pending_index";
+pub const PENDING_INDEX: Item<PendingIndex> = Item::new(PENDING_INDEX_KEY);
+
+pub const LATEST_INDEX_ID_KEY: &str = "latest_index_id";
+pub const LATEST_INDEX_ID: Item<u64> = Item::new(LATEST_INDEX_ID_KEY);
+
+pub const INDEX_BY_ADDRESS_PREFIX: &str = "index_by_address";
+pub const INDEX_BY_ADDRESS: Map<&Addr, u64> = Map::new(INDEX_BY_ADDRESS_PREFIX);
+
+pub const INDEX_BY_DENOM_PREFIX: &str = "index_by_denom";
+pub const INDEX_BY_DENOM: Map<&str, u64> = Map::new(INDEX_BY_DENOM_PREFIX);
@@ -4,17 +4,39 @@ use cosmwasm_std::{Deps, DepsMut, Response};
 use ibcx_interface::periphery::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 use crate::query;
-use crate::{error::ContractError, execute, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::state::{Config, CONFIG};
+use crate::{error::ContractError, execute, CONTRACT_NAME, CONTRACT_VERSION, MAX_FEE_BPS};
 
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
-    _msg: InstantiateMsg,
+    info: MessageInfo,
+    msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
+    if MAX_FEE_BPS < msg.max_referral_fee_bps {
+        return Err(ContractError::ReferralFeeExceeded {
+            fee_bps: msg.max_referral_fee_bps,
+            max_fee_bps: MAX_FEE_BPS,
+        });
+    }
+
+    let gov = msg
+        .gov
+        .map(|v| deps.api.addr_validate(&v))
+        .transpose()?
+        .unwrap_or(info.sender);
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            gov,
+            max_referral_fee_bps: msg.max_referral_fee_bps,
+        },
+    )?;
+
     let resp = Response::new().add_attributes(vec![attr("method", "instantiate")]);
 
     Ok(resp)
@@ -35,6 +57,8 @@ pub fn execute(
             input_asset,
             min_output_amount,
             swap_info,
+            referral,
+            receiver,
         } => execute::mint_exact_amount_in(
             deps,
             env,
@@ -43,12 +67,15 @@ pub fn execute(
             input_asset,
             min_output_amount,
             swap_info.into(),
+            referral,
+            receiver,
         ),
         MintExactAmountOut {
             core_addr,
             output_amount,
             input_asset,
             swap_info,
+            referral,
         } => execute::mint_exact_amount_out(
             deps,
             env,
@@ -57,12 +84,14 @@ pub fn execute(
             output_amount,
             input_asset,
             swap_info.into(),
+            referral,
         ),
         BurnExactAmountIn {
             core_addr,
             output_asset,
             min_output_amount,
             swap_info,
+            referral,
         } => execute::burn_exact_amount_in(
             deps,
             env,
@@ -71,11 +100,13 @@ pub fn execute(
             output_asset,
             min_output_amount,
             swap_info.into(),
+            referral,
         ),
         BurnExactAmountOut {
             core_addr,
             output_asset,
             swap_info,
+            referral,
         } => execute::burn_exact_amount_out(
             deps,
             env,
@@ -83,11 +114,16 @@ pub fn execute(
             core_addr,
             output_asset,
             swap_info.into(),
+            referral,
         ),
         FinishOperation {
             refund_to,
             refund_asset,
         } => execute::finish_operation(deps, env, info, refund_to, refund_asset),
+        UpdateConfig {
+            gov,
+            max_referral_fee_bps,
+        } => execute::update_config(deps, info, gov, max_referral_fee_bps),
     }
 }
 
@@ -106,18 +142,24 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
             core_addr,
             input_asset,
             swap_info,
+            sender,
+            referral,
         } => to_binary(query::simulate_mint_exact_amount_in(
             deps,
             env,
             core_addr,
             input_asset,
             swap_info.into(),
+            sender,
+            referral,
         )),
         SimulateMintExactAmountOut {
             core_addr,
             output_amount,
             input_asset,
             swap_info,
+            sender,
+            referral,
         } => to_binary(query::simulate_mint_exact_amount_out(
             deps,
             env,
@@ -125,6 +167,8 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
             output_amount,
             input_asset,
             swap_info.into(),
+            sender,
+            referral,
         )),
 
         SimulateBurnExactAmountIn {
@@ -132,6 +176,8 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
             input_amount,
             output_asset,
             swap_info,
+            sender,
+            referral,
         } => to_binary(query::simulate_burn_exact_amount_in(
             deps,
             env,
@@ -139,18 +185,25 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, Contr
             input_amount,
             output_asset,
             swap_info.into(),
+            sender,
+            referral,
         )),
         SimulateBurnExactAmountOut {
             core_addr,
             swap_info,
             output_asset,
+            sender,
+            referral,
         } => to_binary(query::simulate_burn_exact_amount_out(
             deps,
             env,
             core_addr,
             output_asset,
             swap_info.into(),
+            sender,
+            referral,
         )),
+        GetConfig {} => to_binary(query::get_config(deps)),
     }
 }
 
@@ -162,5 +215,17 @@ pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, Co
         cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     }
 
+    if let Some(gov) = msg.gov {
+        if CONFIG.may_load(deps.storage)?.is_none() {
+            CONFIG.save(
+                deps.storage,
+                &Config {
+                    gov: deps.api.addr_validate(&gov)?,
+                    max_referral_fee_bps: 0,
+                },
+            )?;
+        }
+    }
+
     Ok(Default::default())
 }
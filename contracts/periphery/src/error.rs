@@ -75,4 +75,13 @@ pub enum ContractError {
 
     #[error("Invalid index amount range")]
     InvalidIndexAmountRange,
+
+    #[error("Access denied by core. address:{address}, reason:{reason}")]
+    AccessDenied { address: String, reason: String },
+
+    #[error("Unauthorized")]
+    Unauthorized,
+
+    #[error("Referral fee exceeded. fee_bps:{fee_bps}, max_fee_bps:{max_fee_bps}")]
+    ReferralFeeExceeded { fee_bps: u16, max_fee_bps: u16 },
 }
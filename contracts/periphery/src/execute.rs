@@ -2,13 +2,19 @@ use cosmwasm_std::{
     attr, coin, to_json_binary, BankMsg, Coin, CosmosMsg, Env, MessageInfo, Uint128, WasmMsg,
 };
 use cosmwasm_std::{DepsMut, Response};
-use ibcx_interface::periphery::{extract_pool_ids, ExecuteMsg, SwapInfo};
-use ibcx_interface::{core, helpers::IbcCore};
+use ibcx_interface::periphery::{extract_pool_ids, ExecuteMsg, Referral, SwapInfo};
+use ibcx_interface::{core, core::AccessAction, helpers::IbcCore};
 use ibcx_pool::{query_pools, Simulator};
 
 use crate::error::ContractError;
-use crate::{coin_sorter, deduct_fee, expand_fee, make_unit_converter};
+use crate::state::CONFIG;
+use crate::{
+    assert_core_access, calc_amount_before_fee, calc_referral_fee, coin_sorter,
+    make_referral_fee_msg, make_unit_converter, simulate_burn_output, simulate_mint_output,
+    ReferralFee, MAX_FEE_BPS,
+};
 
+#[allow(clippy::too_many_arguments)]
 pub fn mint_exact_amount_in(
     deps: DepsMut,
     env: Env,
@@ -17,16 +23,41 @@ pub fn mint_exact_amount_in(
     desired_denom: String,
     min_index_amount: Uint128,
     swap_info: Vec<SwapInfo>,
+    referral: Option<Referral>,
+    receiver: Option<String>,
 ) -> Result<Response, ContractError> {
     // query to core contract
     let core = IbcCore(deps.api.addr_validate(&core_addr)?);
-    let core_fee = core.get_fee(&deps.querier, None)?;
     let core_config = core.get_config(&deps.querier, None)?;
     let core_portfolio = core.get_portfolio(&deps.querier, None)?;
 
+    let receiver = receiver
+        .map(|v| deps.api.addr_validate(&v))
+        .transpose()?
+        .unwrap_or_else(|| info.sender.clone());
+
+    // receiver is also checked when minting on behalf of it
+    let mut accessors = vec![info.sender.as_str(), env.contract.address.as_str()];
+    if receiver != info.sender {
+        accessors.push(receiver.as_str());
+    }
+    assert_core_access(&deps.querier, &core, AccessAction::Mint, &accessors)?;
+
     let desired_asset =
         cw_utils::must_pay(&info, &desired_denom).map(|v| coin(v.u128(), &desired_denom))?;
 
+    // deduct referral fee from input
+    let referral = ReferralFee::load(&deps.as_ref(), referral)?;
+    let referral_fee = calc_referral_fee(&referral, desired_asset.amount, &desired_denom);
+    let (referral_msg, referral_attrs) = make_referral_fee_msg(&referral, &referral_fee);
+    let swap_asset = coin(
+        desired_asset
+            .amount
+            .checked_sub(referral_fee.amount)?
+            .u128(),
+        &desired_denom,
+    );
+
     let pool_ids = extract_pool_ids(swap_info.clone());
     let pools = query_pools(&deps.as_ref(), pool_ids)?;
 
@@ -34,7 +65,7 @@ pub fn mint_exact_amount_in(
     let sim = Simulator::new(&deps_ref, &pools, &swap_info, &core_portfolio.units);
     let sim_res = sim
         .estimate_index_for_input(
-            desired_asset.clone(),
+            swap_asset,
             Some(min_index_amount),
             Some(min_index_amount),
             None,
@@ -50,7 +81,7 @@ pub fn mint_exact_amount_in(
     let finish_msg = WasmMsg::Execute {
         contract_addr: env.contract.address.to_string(),
         msg: to_json_binary(&ExecuteMsg::FinishOperation {
-            refund_to: info.sender.to_string(),
+            refund_to: receiver.to_string(),
             refund_asset: desired_denom,
         })?,
         funds: vec![],
@@ -66,29 +97,38 @@ pub fn mint_exact_amount_in(
     let mint_msg = core.call_with_funds(
         core::ExecuteMsg::Mint {
             amount: sim_res.est_min_token_out,
-            receiver: Some(info.sender.to_string()),
-            refund_to: Some(info.sender.to_string()),
+            receiver: Some(receiver.to_string()),
+            refund_to: Some(receiver.to_string()),
         },
         mint_msg_funds,
     )?;
 
-    let act_mint_amount = sim_res.est_min_token_out * deduct_fee(core_fee.mint_fee)?;
+    let act_mint_amount = simulate_mint_output(
+        &deps.querier,
+        &core,
+        &env.contract.address,
+        sim_res.est_min_token_out,
+    )?;
     let act_mint_asset = coin(act_mint_amount.u128(), core_config.index_denom);
 
     let resp = Response::new()
+        .add_messages(referral_msg)
         .add_messages(swap_msgs)
         .add_message(mint_msg)
         .add_message(finish_msg)
         .add_attributes(vec![
             attr("method", "mint_exact_amount_in"),
             attr("executor", info.sender),
+            attr("receiver", receiver),
             attr("input", desired_asset.to_string()),
             attr("min_output", act_mint_asset.to_string()),
-        ]);
+        ])
+        .add_attributes(referral_attrs);
 
     Ok(resp)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn mint_exact_amount_out(
     deps: DepsMut,
     env: Env,
@@ -97,13 +137,20 @@ pub fn mint_exact_amount_out(
     index_amount: Uint128,
     input_denom: String,
     swap_info: Vec<SwapInfo>,
+    referral: Option<Referral>,
 ) -> Result<Response, ContractError> {
     // query to core contract
     let core = IbcCore(deps.api.addr_validate(&core_addr)?);
-    let core_fee = core.get_fee(&deps.querier, None)?;
     let core_config = core.get_config(&deps.querier, None)?;
     let core_portfolio = core.get_portfolio(&deps.querier, None)?;
 
+    assert_core_access(
+        &deps.querier,
+        &core,
+        AccessAction::Mint,
+        &[info.sender.as_str(), env.contract.address.as_str()],
+    )?;
+
     // input & output
     let input_asset =
         cw_utils::must_pay(&info, &input_denom).map(|v| coin(v.u128(), &input_denom))?;
@@ -115,11 +162,20 @@ pub fn mint_exact_amount_out(
     let deps_ref = deps.as_ref();
     let sim = Simulator::new(&deps_ref, &pools, &swap_info, &core_portfolio.units);
     let sim_res = sim.estimate_input_for_index(&input_asset.denom, index_asset.amount)?;
-    let sim_refund = input_asset.amount.checked_sub(sim_res.total_input)?;
+
+    // referral fee is charged on top of required input
+    let referral = ReferralFee::load(&deps.as_ref(), referral)?;
+    let referral_fee = calc_referral_fee(&referral, sim_res.total_input, &input_asset.denom);
+    let (referral_msg, referral_attrs) = make_referral_fee_msg(&referral, &referral_fee);
+    let swap_max_input = input_asset
+        .amount
+        .checked_sub(referral_fee.amount)
+        .map_err(|_| ContractError::TradeAmountExceeded {})?;
+    let sim_refund = swap_max_input.checked_sub(sim_res.total_input)?;
 
     let swap_msgs = sim_res
         .sim_routes
-        .to_msgs(&env.contract.address, input_asset.amount)?;
+        .to_msgs(&env.contract.address, swap_max_input)?;
 
     let conv = make_unit_converter(sim_res.index_out);
     let mut mint_msg_funds: Vec<_> = core_portfolio.units.into_iter().map(conv).collect();
@@ -143,10 +199,16 @@ pub fn mint_exact_amount_out(
         funds: vec![],
     };
 
-    let act_mint_amount = index_asset.amount * deduct_fee(core_fee.mint_fee)?;
+    let act_mint_amount = simulate_mint_output(
+        &deps.querier,
+        &core,
+        &env.contract.address,
+        index_asset.amount,
+    )?;
     let act_mint_asset = coin(act_mint_amount.u128(), &core_config.index_denom);
 
     let resp = Response::new()
+        .add_messages(referral_msg)
         .add_messages(swap_msgs)
         .add_message(mint_msg)
         .add_message(finish_msg)
@@ -156,11 +218,13 @@ pub fn mint_exact_amount_out(
             attr("max_input", input_asset.to_string()),
             attr("output", act_mint_asset.to_string()),
             attr("refund", sim_refund.to_string()),
-        ]);
+        ])
+        .add_attributes(referral_attrs);
 
     Ok(resp)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn burn_exact_amount_in(
     deps: DepsMut,
     env: Env,
@@ -169,22 +233,36 @@ pub fn burn_exact_amount_in(
     output_denom: String,
     min_output_amount: Uint128,
     swap_info: Vec<SwapInfo>,
+    referral: Option<Referral>,
 ) -> Result<Response, ContractError> {
     // query to core contract
     let core = IbcCore(deps.api.addr_validate(&core_addr)?);
-    let core_fee = core.get_fee(&deps.querier, None)?;
     let core_config = core.get_config(&deps.querier, None)?;
     let core_portfolio = core.get_portfolio(&deps.querier, None)?;
 
+    assert_core_access(
+        &deps.querier,
+        &core,
+        AccessAction::Burn,
+        &[info.sender.as_str(), env.contract.address.as_str()],
+    )?;
+
     // input & output
     let index_asset = cw_utils::must_pay(&info, &core_config.index_denom)
         .map(|v| coin(v.u128(), &core_config.index_denom))?;
     let output_asset = coin(min_output_amount.u128(), output_denom);
 
+    // deduct referral fee from input
+    let referral = ReferralFee::load(&deps.as_ref(), referral)?;
+    let referral_fee = calc_referral_fee(&referral, index_asset.amount, &index_asset.denom);
+    let (referral_msg, referral_attrs) = make_referral_fee_msg(&referral, &referral_fee);
+    let burn_amount = index_asset.amount.checked_sub(referral_fee.amount)?;
+
     let pool_ids = extract_pool_ids(swap_info.clone());
     let pools = query_pools(&deps.as_ref(), pool_ids)?;
 
-    let act_burn_amount = index_asset.amount * deduct_fee(core_fee.burn_fee)?;
+    let act_burn_amount =
+        simulate_burn_output(&deps.querier, &core, &env.contract.address, burn_amount)?;
 
     let deps_ref = deps.as_ref();
     let sim = Simulator::new(&deps_ref, &pools, &swap_info, &core_portfolio.units);
@@ -192,7 +270,7 @@ pub fn burn_exact_amount_in(
 
     let burn_msg = core.call_with_funds(
         core::ExecuteMsg::Burn { redeem_to: None },
-        vec![index_asset.clone()],
+        vec![coin(burn_amount.u128(), &index_asset.denom)],
     )?;
 
     let swap_msgs = sim_res
@@ -209,6 +287,7 @@ pub fn burn_exact_amount_in(
     };
 
     let resp = Response::new()
+        .add_messages(referral_msg)
         .add_message(burn_msg)
         .add_messages(swap_msgs)
         .add_message(finish_msg)
@@ -217,7 +296,8 @@ pub fn burn_exact_amount_in(
             attr("executor", info.sender),
             attr("input_amount", index_asset.to_string()),
             attr("min_output_amount", output_asset.to_string()),
-        ]);
+        ])
+        .add_attributes(referral_attrs);
 
     Ok(resp)
 }
@@ -229,6 +309,7 @@ pub fn burn_exact_amount_out(
     core_addr: String,
     desired_output: Coin,
     swap_info: Vec<SwapInfo>,
+    referral: Option<Referral>,
 ) -> Result<Response, ContractError> {
     // query to core contract
     let core = IbcCore(deps.api.addr_validate(&core_addr)?);
@@ -236,6 +317,13 @@ pub fn burn_exact_amount_out(
     let core_config = core.get_config(&deps.querier, None)?;
     let core_portfolio = core.get_portfolio(&deps.querier, None)?;
 
+    assert_core_access(
+        &deps.querier,
+        &core,
+        AccessAction::Burn,
+        &[info.sender.as_str(), env.contract.address.as_str()],
+    )?;
+
     let index_asset = cw_utils::must_pay(&info, &core_config.index_denom)
         .map(|v| coin(v.u128(), &core_config.index_denom))?;
 
@@ -251,9 +339,17 @@ pub fn burn_exact_amount_out(
         None,
     )?;
 
-    let burn_amount =
-        sim_res.min.est_min_token_in * expand_fee(core_fee.burn_fee)? - Uint128::new(1);
-    if index_asset.amount < burn_amount {
+    let (burn_amount, act_burn_amount) = calc_amount_before_fee(
+        sim_res.min.est_min_token_in,
+        core_fee.schedule.min_fee,
+        |v| simulate_burn_output(&deps.querier, &core, &env.contract.address, v),
+    )?;
+
+    // referral fee is charged on top of burn amount
+    let referral = ReferralFee::load(&deps.as_ref(), referral)?;
+    let referral_fee = calc_referral_fee(&referral, burn_amount, &index_asset.denom);
+    let (referral_msg, referral_attrs) = make_referral_fee_msg(&referral, &referral_fee);
+    if index_asset.amount < burn_amount + referral_fee.amount {
         return Err(ContractError::TradeAmountExceeded {});
     }
 
@@ -262,8 +358,6 @@ pub fn burn_exact_amount_out(
         vec![coin(burn_amount.u128(), &core_config.index_denom)],
     )?;
 
-    let act_burn_amount = burn_amount * deduct_fee(core_fee.burn_fee)?;
-
     let sim_res = sim.estimate_output_for_index(act_burn_amount, &desired_output.denom)?;
 
     let swap_msgs = sim_res
@@ -292,6 +386,7 @@ pub fn burn_exact_amount_out(
     ];
 
     let resp = Response::new()
+        .add_messages(referral_msg)
         .add_message(burn_msg)
         .add_messages(swap_msgs)
         .add_messages(finish_msgs)
@@ -300,7 +395,8 @@ pub fn burn_exact_amount_out(
             attr("executor", info.sender),
             attr("max_input", index_asset.to_string()),
             attr("output", desired_output.to_string()),
-        ]);
+        ])
+        .add_attributes(referral_attrs);
 
     Ok(resp)
 }
@@ -333,3 +429,41 @@ pub fn finish_operation(
 
     Ok(resp)
 }
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    gov: Option<String>,
+    max_referral_fee_bps: Option<u16>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    config.check_gov(&info.sender)?;
+
+    if let Some(gov) = gov {
+        config.gov = deps.api.addr_validate(&gov)?;
+    }
+    if let Some(max_referral_fee_bps) = max_referral_fee_bps {
+        if MAX_FEE_BPS < max_referral_fee_bps {
+            return Err(ContractError::ReferralFeeExceeded {
+                fee_bps: max_referral_fee_bps,
+                max_fee_bps: MAX_FEE_BPS,
+            });
+        }
+        config.max_referral_fee_bps = max_referral_fee_bps;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    let resp = Response::new().add_attributes(vec![
+        attr("method", "update_config"),
+        attr("executor", info.sender),
+        attr("gov", config.gov),
+        attr(
+            "max_referral_fee_bps",
+            config.max_referral_fee_bps.to_string(),
+        ),
+    ]);
+
+    Ok(resp)
+}
@@ -1,33 +1,89 @@
 use std::cmp::Ordering;
 
-use cosmwasm_std::{coin, Coin, Decimal, Uint128};
+use cosmwasm_std::{
+    attr, coin, Addr, Attribute, BankMsg, Coin, Decimal, Deps, QuerierWrapper, Uint128,
+};
 use error::ContractError;
+use ibcx_interface::{core::AccessAction, helpers::IbcCore, periphery::Referral};
+use state::CONFIG;
 
 #[cfg(not(feature = "library"))]
 pub mod contract;
 pub mod error;
 pub mod execute;
 pub mod query;
+pub mod state;
 
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub const REPLY_ID_BURN_EXACT_AMOUNT_IN: u64 = 0;
 
-pub fn deduct_fee(rate: Option<Decimal>) -> Result<Decimal, ContractError> {
-    Ok(rate
-        .map(|v| Ok::<_, ContractError>(Decimal::one().checked_sub(v)?))
-        .transpose()?
-        .unwrap_or(Decimal::one()))
+pub const MAX_FEE_BPS: u16 = 10000;
+
+pub const MAX_FEE_SEARCH_LOOP: usize = 10;
+
+// core charges mint / burn fee by its schedule (tiers, min fee and discount of the sender),
+// so amounts are sized with core's simulation rather than the flat rates
+
+// index tokens received for minting the amount
+pub fn simulate_mint_output(
+    querier: &QuerierWrapper,
+    core: &IbcCore,
+    sender: &Addr,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    if amount.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let resp = core.simulate_mint(querier, amount, None, None, Some(sender.to_string()))?;
+
+    Ok(resp.mint_amount)
 }
 
-pub fn expand_fee(rate: Option<Decimal>) -> Result<Decimal, ContractError> {
-    Ok(rate
-        .map(|v| {
-            Ok::<_, ContractError>(Decimal::one().checked_div(Decimal::one().checked_sub(v)?)?)
-        })
-        .transpose()?
-        .unwrap_or(Decimal::one()))
+// index tokens redeemed to underlying assets for burning the amount
+pub fn simulate_burn_output(
+    querier: &QuerierWrapper,
+    core: &IbcCore,
+    sender: &Addr,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    if amount.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    let resp = core.simulate_burn(querier, amount, None, Some(sender.to_string()))?;
+
+    Ok(resp.burn_amount)
+}
+
+// finds an amount of which output after fee reaches the target. returns (amount, output).
+// fee isn't linear over tiers and min fee, so the guess is scaled by the observed output until it fits
+pub fn calc_amount_before_fee(
+    target: Uint128,
+    min_fee: Option<Uint128>,
+    simulate: impl Fn(Uint128) -> Result<Uint128, ContractError>,
+) -> Result<(Uint128, Uint128), ContractError> {
+    if target.is_zero() {
+        return Ok((Uint128::zero(), Uint128::zero()));
+    }
+
+    let mut amount = target.checked_add(min_fee.unwrap_or_default())?;
+    for _ in 0..MAX_FEE_SEARCH_LOOP {
+        let output = simulate(amount)?;
+        if target <= output {
+            return Ok((amount, output));
+        }
+
+        let next = match output.is_zero() {
+            true => amount.checked_mul(Uint128::new(2))?,
+            false => target.checked_multiply_ratio(amount, output)?,
+        };
+        amount = next.max(amount.checked_add(Uint128::one())?);
+    }
+
+    Err(ContractError::MaxLoopExceeded)
 }
 
 pub fn make_unit_converter(v: Uint128) -> Box<dyn Fn((String, Decimal)) -> Coin> {
@@ -37,3 +93,392 @@ pub fn make_unit_converter(v: Uint128) -> Box<dyn Fn((String, Decimal)) -> Coin>
 pub fn coin_sorter(a: &Coin, b: &Coin) -> Ordering {
     a.denom.cmp(&b.denom)
 }
+
+pub fn assert_core_access(
+    querier: &QuerierWrapper,
+    core: &IbcCore,
+    action: AccessAction,
+    addresses: &[&str],
+) -> Result<(), ContractError> {
+    // cores without access policy don't know CheckAccess query
+    if !core.has_access_policy(querier)? {
+        return Ok(());
+    }
+
+    for address in addresses {
+        let resp = core.check_access(querier, action.clone(), *address)?;
+        if !resp.allowed {
+            return Err(ContractError::AccessDenied {
+                address: address.to_string(),
+                reason: resp.reason.unwrap_or_else(|| "unknown".to_string()),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct ReferralFee {
+    pub address: Addr,
+    pub rate: Decimal,
+}
+
+impl ReferralFee {
+    pub fn load(deps: &Deps, referral: Option<Referral>) -> Result<Option<Self>, ContractError> {
+        let Some(referral) = referral else {
+            return Ok(None);
+        };
+
+        // referral fee is disabled until gov sets the maximum
+        let max_fee_bps = CONFIG
+            .may_load(deps.storage)?
+            .map(|v| v.max_referral_fee_bps)
+            .unwrap_or_default();
+        if max_fee_bps < referral.fee_bps {
+            return Err(ContractError::ReferralFeeExceeded {
+                fee_bps: referral.fee_bps,
+                max_fee_bps,
+            });
+        }
+
+        Ok(Some(Self {
+            address: deps.api.addr_validate(&referral.address)?,
+            rate: Decimal::from_ratio(referral.fee_bps, MAX_FEE_BPS),
+        }))
+    }
+}
+
+pub fn calc_referral_fee(referral: &Option<ReferralFee>, amount: Uint128, denom: &str) -> Coin {
+    let fee = referral
+        .as_ref()
+        .map(|v| amount * v.rate)
+        .unwrap_or_default();
+
+    coin(fee.u128(), denom)
+}
+
+// returns msg for sending referral fee and event attributes for it
+pub fn make_referral_fee_msg(
+    referral: &Option<ReferralFee>,
+    fee: &Coin,
+) -> (Option<BankMsg>, Vec<Attribute>) {
+    let Some(referral) = referral else {
+        return (None, vec![]);
+    };
+
+    let attrs = vec![
+        attr("referral", referral.address.as_str()),
+        attr("referral_fee", fee.to_string()),
+    ];
+
+    let msg = (!fee.amount.is_zero()).then(|| BankMsg::Send {
+        to_address: referral.address.to_string(),
+        amount: vec![fee.clone()],
+    });
+
+    (msg, attrs)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{
+        attr, coin, from_json,
+        testing::{
+            mock_dependencies, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+        },
+        to_json_binary, Addr, BankMsg, Binary, ContractResult, Decimal, OwnedDeps, SystemError,
+        SystemResult, Uint128, WasmQuery,
+    };
+    use ibcx_interface::{
+        core::{
+            AccessAction, CheckAccessResponse, QueryMsg as CoreQueryMsg, SimulateBurnResponse,
+            SimulateMintResponse, ACCESS_POLICY_KEY,
+        },
+        helpers::IbcCore,
+        periphery::Referral,
+    };
+
+    use crate::{
+        error::ContractError,
+        execute::update_config,
+        state::{Config, CONFIG},
+    };
+
+    use super::{
+        assert_core_access, calc_amount_before_fee, calc_referral_fee, make_referral_fee_msg,
+        simulate_burn_output, simulate_mint_output, ReferralFee,
+    };
+
+    const CORE: &str = "core";
+
+    // mocks core which denies every address except "allowed"
+    fn mock_core(with_policy: bool) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Raw { key, .. } if key.as_slice() == ACCESS_POLICY_KEY.as_bytes() => {
+                // raw query returns empty value for missing key
+                let policy = match with_policy {
+                    true => to_json_binary(&"allowlist").unwrap(),
+                    false => Binary::default(),
+                };
+                SystemResult::Ok(ContractResult::Ok(policy))
+            }
+            WasmQuery::Smart { msg, .. } if with_policy => match from_json(msg).unwrap() {
+                CoreQueryMsg::CheckAccess { address, .. } => {
+                    let allowed = address == "allowed";
+                    let resp = CheckAccessResponse {
+                        allowed,
+                        reason: (!allowed).then(|| "not in allowlist".to_string()),
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&resp).unwrap()))
+                }
+                _ => unimplemented!(),
+            },
+            // legacy core fails to parse CheckAccess
+            _ => SystemResult::Err(SystemError::InvalidRequest {
+                error: "unknown variant `check_access`".to_string(),
+                request: Default::default(),
+            }),
+        });
+
+        deps
+    }
+
+    #[test]
+    fn test_assert_core_access() {
+        let core = IbcCore(Addr::unchecked(CORE));
+
+        let deps = mock_core(false);
+        let querier = deps.as_ref().querier;
+        assert_core_access(&querier, &core, AccessAction::Mint, &["allowed", "denied"]).unwrap();
+
+        let deps = mock_core(true);
+        let querier = deps.as_ref().querier;
+        assert_core_access(&querier, &core, AccessAction::Mint, &["allowed"]).unwrap();
+
+        let err = assert_core_access(&querier, &core, AccessAction::Burn, &["allowed", "denied"])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::AccessDenied { address, reason }
+                if address == "denied" && reason == "not in allowlist"
+        ));
+    }
+
+    // mocks core with tiered fee schedule. periphery gets 50% discount
+    //   mint: 2% below 1000, 1% from 1000
+    //   burn: 4% below 1000, 2% from 1000
+    //   min fee: 2
+    fn mock_tiered_core() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+
+        fn calc_fee(amount: Uint128, base: u64, tier: u64, sender: Option<String>) -> Uint128 {
+            let rate = match amount < Uint128::new(1000) {
+                true => Decimal::percent(base),
+                false => Decimal::percent(tier),
+            };
+            let rate = match sender.as_deref() == Some(MOCK_CONTRACT_ADDR) {
+                true => rate * Decimal::percent(50),
+                false => rate,
+            };
+            (amount * rate).max(Uint128::new(2))
+        }
+
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => {
+                let resp = match from_json(msg).unwrap() {
+                    CoreQueryMsg::SimulateMint { amount, sender, .. } => {
+                        let fee_amount = calc_fee(amount, 2, 1, sender);
+                        to_json_binary(&SimulateMintResponse {
+                            mint_amount: amount - fee_amount,
+                            fee_amount,
+                            refund_amount: vec![],
+                            fund_spent: vec![],
+                        })
+                    }
+                    CoreQueryMsg::SimulateBurn { amount, sender, .. } => {
+                        let fee_amount = calc_fee(amount, 4, 2, sender);
+                        to_json_binary(&SimulateBurnResponse {
+                            burn_amount: amount - fee_amount,
+                            fee_amount,
+                            redeem_amount: vec![],
+                        })
+                    }
+                    _ => unimplemented!(),
+                };
+                SystemResult::Ok(ContractResult::Ok(resp.unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+
+        deps
+    }
+
+    #[test]
+    fn test_calc_amount_with_tiered_fee() {
+        let deps = mock_tiered_core();
+        let querier = deps.as_ref().querier;
+        let core = IbcCore(Addr::unchecked(CORE));
+        let periphery = Addr::unchecked(MOCK_CONTRACT_ADDR);
+        let min_fee = Some(Uint128::new(2));
+
+        // discount of the periphery applies
+        let mint = |v| simulate_mint_output(&querier, &core, &periphery, v);
+        assert_eq!(mint(Uint128::new(1000)).unwrap(), Uint128::new(995));
+        let other = Addr::unchecked("other");
+        let output = simulate_mint_output(&querier, &core, &other, Uint128::new(1000)).unwrap();
+        assert_eq!(output, Uint128::new(990));
+
+        let burn = |v| simulate_burn_output(&querier, &core, &periphery, v);
+        assert_eq!(burn(Uint128::new(1000)).unwrap(), Uint128::new(990));
+        assert_eq!(burn(Uint128::zero()).unwrap(), Uint128::zero());
+
+        // (target, amount, output)
+        let cases = [
+            // min fee
+            (1, 3, 1),
+            // below the tier
+            (500, 505, 500),
+            (985, 994, 985),
+            // crossing the tier lowers the rate
+            (992, 1001, 996),
+            // above the tier
+            (2000, 2010, 2000),
+        ];
+        for (target, amount, output) in cases {
+            let target = Uint128::new(target);
+            let res = calc_amount_before_fee(target, min_fee, mint).unwrap();
+            assert_eq!(res, (Uint128::new(amount), Uint128::new(output)));
+            assert_eq!(mint(res.0).unwrap(), res.1);
+            assert!(target <= res.1);
+        }
+
+        let cases = [(980, 999, 980), (3000, 3030, 3000)];
+        for (target, amount, output) in cases {
+            let target = Uint128::new(target);
+            let res = calc_amount_before_fee(target, min_fee, burn).unwrap();
+            assert_eq!(res, (Uint128::new(amount), Uint128::new(output)));
+            assert!(target <= res.1);
+        }
+
+        assert_eq!(
+            calc_amount_before_fee(Uint128::zero(), min_fee, mint).unwrap(),
+            (Uint128::zero(), Uint128::zero())
+        );
+    }
+
+    fn referral(fee_bps: u16) -> Option<Referral> {
+        Some(Referral {
+            address: "referrer".to_string(),
+            fee_bps,
+        })
+    }
+
+    #[test]
+    fn test_referral_fee_cap() {
+        let mut deps = mock_dependencies();
+
+        // disabled until gov sets the maximum
+        let err = ReferralFee::load(&deps.as_ref(), referral(1)).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::ReferralFeeExceeded {
+                fee_bps: 1,
+                max_fee_bps: 0
+            }
+        ));
+
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config {
+                    gov: Addr::unchecked("gov"),
+                    max_referral_fee_bps: 100,
+                },
+            )
+            .unwrap();
+
+        let err = ReferralFee::load(&deps.as_ref(), referral(101)).unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::ReferralFeeExceeded {
+                fee_bps: 101,
+                max_fee_bps: 100
+            }
+        ));
+
+        let fee = ReferralFee::load(&deps.as_ref(), referral(100))
+            .unwrap()
+            .unwrap();
+        assert_eq!(fee.address, Addr::unchecked("referrer"));
+        assert!(ReferralFee::load(&deps.as_ref(), None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_referral_fee_payout() {
+        let mut deps = mock_dependencies();
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config {
+                    gov: Addr::unchecked("gov"),
+                    max_referral_fee_bps: 100,
+                },
+            )
+            .unwrap();
+
+        // mint takes the fee from the input asset, burn from the index token
+        let referral = ReferralFee::load(&deps.as_ref(), referral(50)).unwrap();
+        for denom in ["uosmo", "uibcx"] {
+            let fee = calc_referral_fee(&referral, Uint128::new(12345), denom);
+            assert_eq!(fee, coin(61, denom));
+
+            let (msg, attrs) = make_referral_fee_msg(&referral, &fee);
+            assert_eq!(
+                msg,
+                Some(BankMsg::Send {
+                    to_address: "referrer".to_string(),
+                    amount: vec![coin(61, denom)],
+                })
+            );
+            assert_eq!(
+                attrs,
+                vec![
+                    attr("referral", "referrer"),
+                    attr("referral_fee", format!("61{denom}")),
+                ]
+            );
+        }
+
+        // dust amount doesn't send zero coin
+        let fee = calc_referral_fee(&referral, Uint128::new(10), "uosmo");
+        let (msg, attrs) = make_referral_fee_msg(&referral, &fee);
+        assert_eq!(msg, None);
+        assert_eq!(attrs.len(), 2);
+
+        // no referral
+        let fee = calc_referral_fee(&None, Uint128::new(12345), "uosmo");
+        assert_eq!(fee, coin(0, "uosmo"));
+        assert_eq!(make_referral_fee_msg(&None, &fee), (None, vec![]));
+    }
+
+    #[test]
+    fn test_update_config_unauthorized() {
+        let mut deps = mock_dependencies();
+        CONFIG
+            .save(
+                deps.as_mut().storage,
+                &Config {
+                    gov: Addr::unchecked("gov"),
+                    max_referral_fee_bps: 100,
+                },
+            )
+            .unwrap();
+
+        let err =
+            update_config(deps.as_mut(), mock_info("user", &[]), None, Some(200)).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized));
+    }
+}
@@ -1,37 +1,73 @@
 use cosmwasm_std::{coin, Coin, Deps, Env, Uint128};
 use ibcx_interface::{
+    core::AccessAction,
     helpers::IbcCore,
     periphery::{
-        extract_pool_ids, SimulateBurnExactAmountInResponse, SimulateBurnExactAmountOutResponse,
-        SimulateMintExactAmountOutResponse, SwapInfo,
+        extract_pool_ids, GetConfigResponse, Referral, SimulateBurnExactAmountInResponse,
+        SimulateBurnExactAmountOutResponse, SimulateMintExactAmountOutResponse, SwapInfo,
     },
 };
 use ibcx_pool::{query_pools, Simulator};
 
-use crate::{deduct_fee, error::ContractError, expand_fee, make_unit_converter};
+use crate::{
+    assert_core_access, calc_amount_before_fee, calc_referral_fee, error::ContractError,
+    make_unit_converter, simulate_burn_output, simulate_mint_output, state::CONFIG, ReferralFee,
+};
+
+fn check_access(
+    deps: &Deps,
+    env: &Env,
+    core: &IbcCore,
+    action: AccessAction,
+    sender: Option<String>,
+) -> Result<(), ContractError> {
+    let mut addresses = vec![env.contract.address.to_string()];
+    if let Some(sender) = sender {
+        addresses.push(deps.api.addr_validate(&sender)?.into_string());
+    }
+
+    let addresses: Vec<_> = addresses.iter().map(|v| v.as_str()).collect();
+
+    assert_core_access(&deps.querier, core, action, &addresses)
+}
 
 pub fn simulate_mint_exact_amount_in(
     deps: Deps,
-    _env: Env,
+    env: Env,
     core_addr: String,
     input_asset: Coin,
     swap_info: Vec<SwapInfo>,
+    sender: Option<String>,
+    referral: Option<Referral>,
 ) -> Result<SimulateMintExactAmountOutResponse, ContractError> {
     let core = IbcCore(deps.api.addr_validate(&core_addr)?);
     let core_fee = core.get_fee(&deps.querier, None)?;
     let core_portfolio = core.get_portfolio(&deps.querier, None)?;
 
+    check_access(&deps, &env, &core, AccessAction::Mint, sender)?;
+
+    // deduct referral fee from input
+    let referral = ReferralFee::load(&deps, referral)?;
+    let referral_fee = calc_referral_fee(&referral, input_asset.amount, &input_asset.denom);
+    let swap_asset = coin(
+        input_asset.amount.checked_sub(referral_fee.amount)?.u128(),
+        &input_asset.denom,
+    );
+
     let pool_ids = extract_pool_ids(swap_info.clone());
     let pools = query_pools(&deps, pool_ids)?;
 
     let sim = Simulator::new(&deps, &pools, &swap_info, &core_portfolio.units);
     let sim_res = sim
-        .estimate_index_for_input(input_asset.clone(), None, None, None)?
+        .estimate_index_for_input(swap_asset, None, None, None)?
         .max;
 
     // apply mint fee
-    let mint_amount =
-        (sim_res.est_min_token_out * expand_fee(core_fee.mint_fee)?) - Uint128::new(100); // FIXME: hacky calibration
+    let (mint_amount, _) =
+        calc_amount_before_fee(sim_res.est_min_token_out, core_fee.schedule.min_fee, |v| {
+            simulate_mint_output(&deps.querier, &core, &env.contract.address, v)
+        })?;
+    let mint_amount = mint_amount - Uint128::new(100); // FIXME: hacky calibration
 
     let conv = make_unit_converter(mint_amount);
     let mut mint_spend_amount: Vec<_> = core_portfolio.units.into_iter().map(conv).collect();
@@ -41,27 +77,33 @@ pub fn simulate_mint_exact_amount_in(
         mint_amount,
         mint_spend_amount,
         swap_result_amount: coin(sim_res.max_token_in.u128(), input_asset.denom),
+        referral_fee: referral.map(|_| referral_fee),
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn simulate_mint_exact_amount_out(
     deps: Deps,
-    _env: Env,
+    env: Env,
     core_addr: String,
     index_amount: Uint128,
     input_asset: String,
     swap_info: Vec<SwapInfo>,
+    sender: Option<String>,
+    referral: Option<Referral>,
 ) -> Result<SimulateMintExactAmountOutResponse, ContractError> {
     // query to core contract
     let core = IbcCore(deps.api.addr_validate(&core_addr)?);
-    let core_fee = core.get_fee(&deps.querier, None)?;
     let core_portfolio = core.get_portfolio(&deps.querier, None)?;
 
+    check_access(&deps, &env, &core, AccessAction::Mint, sender)?;
+
     let pool_ids = extract_pool_ids(swap_info.clone());
     let pools = query_pools(&deps, pool_ids)?;
 
     // apply mint fee
-    let mint_amount = index_amount * deduct_fee(core_fee.mint_fee)?;
+    let mint_amount =
+        simulate_mint_output(&deps.querier, &core, &env.contract.address, index_amount)?;
 
     let conv = make_unit_converter(mint_amount);
     let mut mint_spend_amount: Vec<_> =
@@ -71,31 +113,50 @@ pub fn simulate_mint_exact_amount_out(
     let sim = Simulator::new(&deps, &pools, &swap_info, &core_portfolio.units);
     let sim_res = sim.estimate_input_for_index(&input_asset, mint_amount)?;
 
+    // referral fee is charged on top of required input
+    let referral = ReferralFee::load(&deps, referral)?;
+    let referral_fee = calc_referral_fee(&referral, sim_res.total_input, &input_asset);
+
     Ok(SimulateMintExactAmountOutResponse {
         mint_amount,
         mint_spend_amount,
         swap_result_amount: coin(sim_res.total_input.u128(), input_asset),
+        referral_fee: referral.map(|_| referral_fee),
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn simulate_burn_exact_amount_in(
     deps: Deps,
-    _env: Env,
+    env: Env,
     core_addr: String,
     index_amount: Uint128,
     output_asset: String,
     swap_info: Vec<SwapInfo>,
+    sender: Option<String>,
+    referral: Option<Referral>,
 ) -> Result<SimulateBurnExactAmountInResponse, ContractError> {
     // query to core contract
     let core = IbcCore(deps.api.addr_validate(&core_addr)?);
-    let core_fee = core.get_fee(&deps.querier, None)?;
     let core_portfolio = core.get_portfolio(&deps.querier, None)?;
 
+    check_access(&deps, &env, &core, AccessAction::Burn, sender)?;
+
     let pool_ids = extract_pool_ids(swap_info.clone());
     let pools = query_pools(&deps, pool_ids)?;
 
+    // deduct referral fee from input
+    let referral = ReferralFee::load(&deps, referral)?;
+    let core_config = core.get_config(&deps.querier, None)?;
+    let referral_fee = calc_referral_fee(&referral, index_amount, &core_config.index_denom);
+
     // apply burn fee before simulating
-    let burn_amount = index_amount * deduct_fee(core_fee.burn_fee)?;
+    let burn_amount = simulate_burn_output(
+        &deps.querier,
+        &core,
+        &env.contract.address,
+        index_amount.checked_sub(referral_fee.amount)?,
+    )?;
 
     let conv = make_unit_converter(burn_amount);
     let mut burn_redeem_amount: Vec<_> =
@@ -109,21 +170,26 @@ pub fn simulate_burn_exact_amount_in(
         burn_amount,
         burn_redeem_amount,
         swap_result_amount: coin(sim_res.total_output.u128(), &output_asset),
+        referral_fee: referral.map(|_| referral_fee),
     })
 }
 
 pub fn simulate_burn_exact_amount_out(
     deps: Deps,
-    _env: Env,
+    env: Env,
     core_addr: String,
     output_asset: Coin,
     swap_info: Vec<SwapInfo>,
+    sender: Option<String>,
+    referral: Option<Referral>,
 ) -> Result<SimulateBurnExactAmountOutResponse, ContractError> {
     // query to core contract
     let core = IbcCore(deps.api.addr_validate(&core_addr)?);
     let core_fee = core.get_fee(&deps.querier, None)?;
     let core_portfolio = core.get_portfolio(&deps.querier, None)?;
 
+    check_access(&deps, &env, &core, AccessAction::Burn, sender)?;
+
     let pool_ids = extract_pool_ids(swap_info.clone());
     let pools = query_pools(&deps, pool_ids)?;
 
@@ -133,7 +199,15 @@ pub fn simulate_burn_exact_amount_out(
         .min;
 
     // apply burn fee after simulation
-    let burn_amount = sim_res.est_min_token_in * expand_fee(core_fee.burn_fee)?;
+    let (burn_amount, _) =
+        calc_amount_before_fee(sim_res.est_min_token_in, core_fee.schedule.min_fee, |v| {
+            simulate_burn_output(&deps.querier, &core, &env.contract.address, v)
+        })?;
+
+    // referral fee is charged on top of burn amount
+    let referral = ReferralFee::load(&deps, referral)?;
+    let core_config = core.get_config(&deps.querier, None)?;
+    let referral_fee = calc_referral_fee(&referral, burn_amount, &core_config.index_denom);
 
     let conv = make_unit_converter(burn_amount);
     let mut burn_redeem_amount: Vec<_> = core_portfolio.units.into_iter().map(conv).collect();
@@ -143,5 +217,15 @@ pub fn simulate_burn_exact_amount_out(
         burn_amount,
         burn_redeem_amount,
         swap_result_amount: coin(sim_res.max_token_out.u128(), output_asset.denom),
+        referral_fee: referral.map(|_| referral_fee),
+    })
+}
+
+pub fn get_config(deps: Deps) -> Result<GetConfigResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(GetConfigResponse {
+        gov: config.gov,
+        max_referral_fee_bps: config.max_referral_fee_bps,
     })
 }
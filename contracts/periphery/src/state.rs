@@ -0,0 +1,24 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+
+use crate::error::ContractError;
+
+#[cw_serde]
+pub struct Config {
+    pub gov: Addr,
+    pub max_referral_fee_bps: u16,
+}
+
+impl Config {
+    pub fn check_gov(&self, sender: &Addr) -> Result<(), ContractError> {
+        if self.gov != *sender {
+            return Err(ContractError::Unauthorized);
+        }
+
+        Ok(())
+    }
+}
+
+pub const CONFIG_KEY: &str = "config";
+pub const CONFIG: Item<Config> = Item::new(CONFIG_KEY);
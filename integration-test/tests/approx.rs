@@ -62,6 +62,8 @@ fn execute_mint_exact_amount_in(token_in: Uint128) {
                 core_addr: env.core_addr.clone(),
                 input_asset: coin(token_in_amount.u128(), &uatom),
                 swap_info: swap_info.clone(),
+                sender: None,
+                referral: None,
             },
         )
         .unwrap();
@@ -77,6 +79,8 @@ fn execute_mint_exact_amount_in(token_in: Uint128) {
                 input_asset: uatom.clone(),
                 swap_info,
                 min_output_amount: test_res.mint_amount,
+                referral: None,
+                receiver: None,
             },
             &[coin(test_res.swap_result_amount.amount.u128(), &uatom)],
             owner,
@@ -189,6 +193,8 @@ fn execute_burn_exact_amount_out(token_out: Uint128) {
                 core_addr: env.core_addr.clone(),
                 output_asset: coin(token_out_amount.u128(), &uatom),
                 swap_info: swap_info.clone(),
+                sender: None,
+                referral: None,
             },
         )
         .unwrap();
@@ -204,6 +210,8 @@ fn execute_burn_exact_amount_out(token_out: Uint128) {
                 input_amount: test_res.burn_amount,
                 output_asset: uatom,
                 swap_info: swap_info.clone(),
+                sender: None,
+                referral: None,
             },
         )
         .unwrap();
@@ -218,6 +226,7 @@ fn execute_burn_exact_amount_out(token_out: Uint128) {
                 core_addr: env.core_addr.clone(),
                 output_asset: test_res.swap_result_amount.clone(),
                 swap_info,
+                referral: None,
             },
             &[coin(test_res.burn_amount.u128(), core_config.index_denom)],
             owner,
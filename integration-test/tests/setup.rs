@@ -197,6 +197,10 @@ pub fn setup(initial_fund: &[Coin], signer_count: u64) -> TestEnv<'static, Osmos
                     mint_fee: None,
                     burn_fee: Some(Decimal::from_ratio(15u64, 10000u64)),
                     streaming_fee: None,
+                    performance_fee: None,
+                    splits: None,
+                    schedule: None,
+                    grace_period: None,
                 },
                 index_denom: "uibcx".to_string(),
                 index_units: vec![
@@ -206,6 +210,8 @@ pub fn setup(initial_fund: &[Coin], signer_count: u64) -> TestEnv<'static, Osmos
                     (ukrw.clone(), Decimal::from_str("496.225").unwrap()),
                 ],
                 reserve_denom: "uosmo".to_string(),
+                access_policy: None,
+                metadata: None,
             },
             Some(&owner.address()),
             Some("label"),
@@ -219,7 +225,10 @@ pub fn setup(initial_fund: &[Coin], signer_count: u64) -> TestEnv<'static, Osmos
     let perp_addr = Wasm::new(&app)
         .instantiate(
             perp_code,
-            &periphery::InstantiateMsg {},
+            &periphery::InstantiateMsg {
+                gov: None,
+                max_referral_fee_bps: 0,
+            },
             Some(&owner.address()),
             Some("label"),
             &[],
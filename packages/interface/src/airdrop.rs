@@ -1,7 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Coin, Uint128};
 
-use crate::types::RangeOrder;
+use crate::{periphery::SwapInfosCompact, types::RangeOrder};
 
 #[cw_serde]
 pub struct InstantiateMsg {}
@@ -26,6 +26,8 @@ impl AirdropId {
 pub enum AirdropType {
     Open,
     Bearer,
+    Vesting,
+    MultiDenom,
 }
 
 impl ToString for AirdropType {
@@ -33,6 +35,8 @@ impl ToString for AirdropType {
         match self {
             AirdropType::Open => "open".to_string(),
             AirdropType::Bearer => "bearer".to_string(),
+            AirdropType::Vesting => "vesting".to_string(),
+            AirdropType::MultiDenom => "multi_denom".to_string(),
         }
     }
 }
@@ -55,6 +59,13 @@ pub enum RegisterPayload {
 
         // optional: label
         label: Option<String>,
+
+        // optional: claims are available from starts_at until expires_at (in seconds)
+        starts_at: Option<u64>,
+        expires_at: Option<u64>,
+
+        // optional: receives unclaimed funds on sweep - default is creator
+        beneficiary: Option<String>,
     },
 
     // Payload for bearer airdrop
@@ -73,6 +84,55 @@ pub enum RegisterPayload {
 
         // optional: label
         label: Option<String>,
+
+        // optional: claims are available from starts_at until expires_at (in seconds)
+        starts_at: Option<u64>,
+        expires_at: Option<u64>,
+
+        // optional: receives unclaimed funds on sweep - default is creator
+        beneficiary: Option<String>,
+    },
+
+    // Payload for vesting airdrop
+    Vesting {
+        // merkle root of airdrop. leaf grants the total allocation
+        merkle_root: String,
+
+        // denomination of airdrop
+        denom: String,
+
+        // allocation unlocks linearly from start_time to end_time (in seconds)
+        start_time: u64,
+        end_time: u64,
+
+        // optional: nothing is unlocked before the cliff
+        cliff_time: Option<u64>,
+
+        // optional: label
+        label: Option<String>,
+
+        // optional: claims are available from starts_at until expires_at (in seconds)
+        starts_at: Option<u64>,
+        expires_at: Option<u64>,
+
+        // optional: receives unclaimed funds on sweep - default is creator
+        beneficiary: Option<String>,
+    },
+
+    // Payload for multi-denom airdrop. funded with every coin attached
+    MultiDenom {
+        // merkle root of airdrop. leaf grants a vector of coins
+        merkle_root: String,
+
+        // optional: label
+        label: Option<String>,
+
+        // optional: claims are available from starts_at until expires_at (in seconds)
+        starts_at: Option<u64>,
+        expires_at: Option<u64>,
+
+        // optional: receives unclaimed funds on sweep - default is creator
+        beneficiary: Option<String>,
     },
 }
 
@@ -82,6 +142,9 @@ impl RegisterPayload {
             merkle_root: merkle_root.to_string(),
             denom: denom.to_string(),
             label: label.map(|x| x.to_string()),
+            starts_at: None,
+            expires_at: None,
+            beneficiary: None,
         }
     }
 
@@ -98,8 +161,82 @@ impl RegisterPayload {
             signer_pub: signer_pub.to_string(),
             signer_sig: signer_sig.to_string(),
             label: label.map(|x| x.to_string()),
+            starts_at: None,
+            expires_at: None,
+            beneficiary: None,
         }
     }
+
+    pub fn vesting(
+        merkle_root: impl ToString,
+        denom: impl ToString,
+        start_time: u64,
+        end_time: u64,
+        cliff_time: Option<u64>,
+        label: Option<&str>,
+    ) -> Self {
+        Self::Vesting {
+            merkle_root: merkle_root.to_string(),
+            denom: denom.to_string(),
+            start_time,
+            end_time,
+            cliff_time,
+            label: label.map(|x| x.to_string()),
+            starts_at: None,
+            expires_at: None,
+            beneficiary: None,
+        }
+    }
+
+    pub fn multi_denom(merkle_root: impl ToString, label: Option<&str>) -> Self {
+        Self::MultiDenom {
+            merkle_root: merkle_root.to_string(),
+            label: label.map(|x| x.to_string()),
+            starts_at: None,
+            expires_at: None,
+            beneficiary: None,
+        }
+    }
+
+    pub fn with_period(
+        mut self,
+        new_starts_at: Option<u64>,
+        new_expires_at: Option<u64>,
+        new_beneficiary: Option<&str>,
+    ) -> Self {
+        match &mut self {
+            Self::Open {
+                starts_at,
+                expires_at,
+                beneficiary,
+                ..
+            }
+            | Self::Bearer {
+                starts_at,
+                expires_at,
+                beneficiary,
+                ..
+            }
+            | Self::Vesting {
+                starts_at,
+                expires_at,
+                beneficiary,
+                ..
+            }
+            | Self::MultiDenom {
+                starts_at,
+                expires_at,
+                beneficiary,
+                ..
+            } => {
+                *starts_at = new_starts_at;
+                *expires_at = new_expires_at;
+                *beneficiary = new_beneficiary.map(|x| x.to_string());
+            }
+        }
+
+        self
+    }
 }
 
 #[cw_serde]
@@ -121,6 +258,34 @@ pub enum ClaimPayload {
         claim_sign: String,        // signature of signer
         merkle_proof: Vec<String>, // merkle proof of airdrop
     },
+
+    // Payload for vesting airdrop. can be claimed repeatedly as the allocation unlocks
+    Vesting {
+        airdrop: AirdropId,        // airdrop specifier
+        amount: Uint128,           // total allocation
+        account: Option<String>,   // address who claims - default is tx.sender
+        merkle_proof: Vec<String>, // merkle proof of airdrop
+    },
+
+    // Payload for open airdrop, sending funds to the recipient authorized by the account
+    OpenOnBehalf {
+        airdrop: AirdropId,  // airdrop specifier
+        amount: Uint128,     // claim amount
+        account: String,     // address who claims
+        recipient: String,   // address who receives funds
+        account_pub: String, // public key of account
+        // signature of account - sign(sha256({CHAIN_ID}/{AIRDROP_CONTRACT}/{AIRDROP_ID}/{ACCOUNT}/{RECIPIENT}/{AMOUNT}))
+        recipient_sig: String,
+        merkle_proof: Vec<String>, // merkle proof of airdrop
+    },
+
+    // Payload for multi-denom airdrop
+    MultiDenom {
+        airdrop: AirdropId,        // airdrop specifier
+        amount: Vec<Coin>,         // claim amount of each denom
+        account: Option<String>,   // address who claims - default is tx.sender
+        merkle_proof: Vec<String>, // merkle proof of airdrop
+    },
 }
 
 impl ClaimPayload {
@@ -147,6 +312,26 @@ impl ClaimPayload {
         }
     }
 
+    pub fn open_on_behalf_id(
+        id: u64,
+        amount: u128,
+        account: impl ToString,
+        recipient: impl ToString,
+        account_pub: impl ToString,
+        recipient_sig: impl ToString,
+        merkle_proof: &[&str],
+    ) -> Self {
+        Self::OpenOnBehalf {
+            airdrop: AirdropId::id(id),
+            amount: Uint128::new(amount),
+            account: account.to_string(),
+            recipient: recipient.to_string(),
+            account_pub: account_pub.to_string(),
+            recipient_sig: recipient_sig.to_string(),
+            merkle_proof: merkle_proof.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
     pub fn bearer_id(
         id: u64,
         amount: u128,
@@ -165,6 +350,29 @@ impl ClaimPayload {
         }
     }
 
+    pub fn vesting_id(id: u64, amount: u128, account: Option<&str>, merkle_proof: &[&str]) -> Self {
+        Self::Vesting {
+            airdrop: AirdropId::id(id),
+            amount: Uint128::new(amount),
+            account: account.map(|x| x.to_string()),
+            merkle_proof: merkle_proof.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
+    pub fn multi_denom_id(
+        id: u64,
+        amount: Vec<Coin>,
+        account: Option<&str>,
+        merkle_proof: &[&str],
+    ) -> Self {
+        Self::MultiDenom {
+            airdrop: AirdropId::id(id),
+            amount,
+            account: account.map(|x| x.to_string()),
+            merkle_proof: merkle_proof.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
     pub fn bearer_label(
         label: &str,
         amount: u128,
@@ -184,12 +392,88 @@ impl ClaimPayload {
     }
 }
 
+// mints the index with claimed funds instead of sending them to the recipient
+#[cw_serde]
+pub enum MintOption {
+    // swaps claimed funds into the index via periphery MintExactAmountIn. single denom only
+    Periphery {
+        periphery: String,
+        core: String,
+        min_output_amount: Uint128,
+        swap_info: SwapInfosCompact,
+    },
+
+    // mints the index via core Mint. claimed denoms must cover the index units
+    Core {
+        core: String,
+        min_output_amount: Uint128,
+    },
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum ClaimManyMode {
+    // fails the whole batch on the first invalid claim
+    #[default]
+    Atomic,
+    // skips invalid claims and reports them as events
+    BestEffort,
+}
+
+impl std::fmt::Display for ClaimManyMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClaimManyMode::Atomic => write!(f, "atomic"),
+            ClaimManyMode::BestEffort => write!(f, "best_effort"),
+        }
+    }
+}
+
+impl From<ClaimManyMode> for String {
+    fn from(val: ClaimManyMode) -> Self {
+        val.to_string()
+    }
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     Register(RegisterPayload),
     Fund(AirdropId),
     Claim(ClaimPayload),
+    // claims are processed in order. transfers are merged per recipient
+    ClaimMany {
+        claims: Vec<ClaimPayload>,
+        mode: Option<ClaimManyMode>,
+    },
+    // recipient of the claim must be the sender
+    ClaimAndMint {
+        claim: ClaimPayload,
+        mint: MintOption,
+    },
     Close(AirdropId),
+    // replaces the root of open airdrop. leaves hold cumulative amounts. attached funds are added
+    UpdateRoot {
+        airdrop: AirdropId,
+        merkle_root: String,
+    },
+    // replaces the signer of bearer airdrop. signatures of the previous signer are no longer valid
+    RotateSigner {
+        airdrop: AirdropId,
+        signer_pub: String,
+        signer_sig: String,
+    },
+    // revokes outstanding claim hashes of bearer airdrop
+    RevokeClaims {
+        airdrop: AirdropId,
+        claim_hashes: Vec<String>,
+    },
+    // returns unclaimed funds of the expired airdrop to the beneficiary. anyone can execute
+    Sweep(AirdropId),
+    // indexes the next page of claims logged before claim stats existed. started by the migration.
+    // claims of bearer airdrops are counted only, as their claimers are not logged. anyone can execute
+    BackfillClaims {
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -301,6 +585,17 @@ pub enum QueryMsg {
         order: Option<RangeOrder>,
     },
 
+    #[returns(ListClaimsResponse)]
+    ListClaimsByAccount {
+        account: String,
+        start_after: Option<(u64, String)>, // (airdrop_id, claim_key)
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    },
+
+    #[returns(GetAirdropStatsResponse)]
+    GetAirdropStats { airdrop: AirdropId },
+
     #[returns(GetLabelResponse)]
     GetLabel { label: String },
 
@@ -326,6 +621,9 @@ pub enum GetAirdropResponse {
         label: Option<String>,
         created_at: u64,
         closed_at: Option<u64>,
+        starts_at: Option<u64>,
+        expires_at: Option<u64>,
+        beneficiary: Option<String>,
     },
 
     Bearer {
@@ -342,6 +640,46 @@ pub enum GetAirdropResponse {
         label: Option<String>,
         created_at: u64,
         closed_at: Option<u64>,
+        starts_at: Option<u64>,
+        expires_at: Option<u64>,
+        beneficiary: Option<String>,
+    },
+
+    Vesting {
+        id: u64,
+        creator: String,
+
+        denom: String,
+        total_amount: Uint128,
+        total_claimed: Uint128,
+        merkle_root: String,
+
+        start_time: u64,
+        end_time: u64,
+        cliff_time: Option<u64>,
+
+        label: Option<String>,
+        created_at: u64,
+        closed_at: Option<u64>,
+        starts_at: Option<u64>,
+        expires_at: Option<u64>,
+        beneficiary: Option<String>,
+    },
+
+    MultiDenom {
+        id: u64,
+        creator: String,
+
+        total_amount: Vec<Coin>,
+        total_claimed: Vec<Coin>,
+        merkle_root: String,
+
+        label: Option<String>,
+        created_at: u64,
+        closed_at: Option<u64>,
+        starts_at: Option<u64>,
+        expires_at: Option<u64>,
+        beneficiary: Option<String>,
     },
 }
 
@@ -354,7 +692,9 @@ pub struct LatestAirdropResponse(pub u64);
 #[cw_serde]
 pub struct GetClaimResponse {
     pub id: u64,
-    pub amount: Uint128,
+    pub amount: Uint128,    // claimed amount. cumulative for vesting airdrops
+    pub claimable: Uint128, // unlocked but not claimed yet. only for vesting airdrops
+    pub coins: Vec<Coin>,   // claimed coins. only for multi-denom airdrops
     pub claim_key: String,
 }
 
@@ -363,6 +703,8 @@ pub struct GetClaimResponse {
 pub struct VerifyClaimResponse {
     pub valid: bool,
     pub reason: Option<String>,
+    // claim hash of bearer airdrop is revoked by the creator
+    pub revoked: bool,
 }
 
 impl VerifyClaimResponse {
@@ -371,6 +713,11 @@ impl VerifyClaimResponse {
         self
     }
 
+    pub fn revoked(mut self) -> Self {
+        self.revoked = true;
+        self.fail("claim hash is revoked")
+    }
+
     pub fn ok(mut self) -> Self {
         self.valid = true;
         self
@@ -380,6 +727,15 @@ impl VerifyClaimResponse {
 #[cw_serde]
 pub struct ListClaimsResponse(pub Vec<GetClaimResponse>);
 
+#[cw_serde]
+pub struct GetAirdropStatsResponse {
+    pub id: u64,
+    pub claimants: u64,
+    pub claimed: Vec<Coin>,
+    pub remaining: Vec<Coin>, // empty if the airdrop is closed. zero amounts are omitted
+    pub last_claimed_at: Option<u64>, // block height
+}
+
 #[cw_serde]
 pub struct GetLabelResponse {
     pub creator: String,
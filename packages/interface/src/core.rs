@@ -3,13 +3,72 @@ use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
 
 use crate::types::{RangeOrder, SwapRoutes};
 
+#[cw_serde]
+#[derive(Default)]
+pub enum StreamingFeeMode {
+    // takes fee out of each unit and sends the underlying assets on realize
+    #[default]
+    Units,
+    // dilutes units and mints the equivalent index tokens on realize
+    Mint,
+}
+
 #[cw_serde]
 #[derive(Default)]
 pub struct StreamingFeePayload {
+    // compounding rate per second. exclusive with apr
+    pub rate: Option<Decimal>,
+    // annualized rate converted to the rate per second. exclusive with rate
+    pub apr: Option<Decimal>,
+    pub freeze: bool,
+    // defaults to units mode
+    pub mode: Option<StreamingFeeMode>,
+}
+
+#[cw_serde]
+pub struct PerformanceFeePayload {
+    // portion of nav growth above the high-water mark
     pub rate: Decimal,
+    // (denom, pool_id) pairs used to price each index unit in the reserve denom
+    pub price_sources: Vec<(String, u64)>,
+    // twap window in seconds
+    pub twap_window: u64,
+    // starting high-water mark. keeps current one if not provided
+    pub high_water_mark: Option<Decimal>,
     pub freeze: bool,
 }
 
+// list of (recipient, share) for each fee type.
+// shares must sum up to 1. empty list sends the whole fee to the collector
+#[cw_serde]
+#[derive(Default)]
+pub struct FeeSplitPayload {
+    pub mint: Vec<(String, Decimal)>,
+    pub burn: Vec<(String, Decimal)>,
+    pub streaming: Vec<(String, Decimal)>,
+    pub performance: Vec<(String, Decimal)>,
+}
+
+#[cw_serde]
+pub struct FeeTier {
+    // applied to trades with index token amount equal or greater than this
+    pub min_amount: Uint128,
+    pub rate: Decimal,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct FeeSchedulePayload {
+    // tiers by trade size in ascending order of min_amount.
+    // mint_fee / burn_fee applies to trades below the first tier
+    pub mint_tiers: Vec<FeeTier>,
+    pub burn_tiers: Vec<FeeTier>,
+    // list of (address, discount). discount of 1 waives the fee entirely
+    pub discounts: Vec<(String, Decimal)>,
+    // minimum fee in index tokens. not applied to fully waived fees. amounts below it are rejected
+    pub min_fee: Option<Uint128>,
+}
+
 #[cw_serde]
 #[derive(Default)]
 pub struct FeePayload {
@@ -17,6 +76,44 @@ pub struct FeePayload {
     pub mint_fee: Option<Decimal>,
     pub burn_fee: Option<Decimal>,
     pub streaming_fee: Option<StreamingFeePayload>,
+    pub performance_fee: Option<PerformanceFeePayload>,
+    pub splits: Option<FeeSplitPayload>,
+    pub schedule: Option<FeeSchedulePayload>,
+    // seconds for which burn keeps the previous fee after a fee change,
+    // and keeps the current fee after a rebalance starts. disabled if not provided on instantiate.
+    // fee updates keep the current value if not provided, and a new value applies from the next change
+    pub grace_period: Option<u64>,
+}
+
+// storage key of the access policy. peers read it raw to stay compatible with
+// cores deployed before the access policy was introduced
+pub const ACCESS_POLICY_KEY: &str = "access_policy";
+
+#[cw_serde]
+pub enum AccessAction {
+    Mint,
+    Burn,
+}
+
+#[cw_serde]
+pub enum AccessPolicyPayload {
+    // only addresses registered by gov can mint / burn
+    Allowlist {},
+    // delegates the decision to an external gate contract
+    Gate { contract: String },
+}
+
+// displayed by wallets and explorers through the bank denom metadata
+#[cw_serde]
+pub struct IndexMetadataPayload {
+    pub name: String,
+    // also used as the display denom
+    pub symbol: String,
+    pub description: Option<String>,
+    pub methodology_url: Option<String>,
+    pub logo_uri: Option<String>,
+    // exponent of the display denom. defaults to 6
+    pub decimals: Option<u32>,
 }
 
 #[cw_serde]
@@ -27,9 +124,12 @@ pub struct InstantiateMsg {
     pub index_denom: String,
     pub index_units: Vec<(String, Decimal)>,
     pub reserve_denom: String,
+    pub access_policy: Option<AccessPolicyPayload>,
+    pub metadata: Option<IndexMetadataPayload>,
 }
 
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum GovMsg {
     // pause mint / burn
     Pause {
@@ -42,6 +142,14 @@ pub enum GovMsg {
     RevokeGov {},
 
     UpdateFeeStrategy(FeePayload),
+    UpdateAccessPolicy(Option<AccessPolicyPayload>),
+    UpdateMetadata(IndexMetadataPayload),
+    UpdateStrategy(Option<StrategyPayload>),
+    UpdateKeeperReward(Option<KeeperRewardPayload>),
+    UpdateAllowlist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
     UpdateRebalanceManager(Option<String>),
     UpdateReserveDenom(String),
     UpdateTradeInfo {
@@ -49,6 +157,9 @@ pub enum GovMsg {
         routes: SwapRoutes,
         cooldown: u64,
         max_trade_amount: Uint128,
+        // max slippage tolerance of trades by keepers over the simulated amount.
+        // trades of a rebalance without manager are rejected if not set
+        max_slippage: Option<Decimal>,
     },
 }
 
@@ -68,6 +179,45 @@ pub enum RebalanceTradeMsg {
     },
 }
 
+// prices of each denom in the reserve denom, used to convert target weights into units
+#[cw_serde]
+pub enum RebalancePriceSource {
+    // arithmetic twap of (denom, pool_id) pairs for the window in seconds
+    Twap {
+        sources: Vec<(String, u64)>,
+        window: u64,
+    },
+    // simulates a swap of the probe amount through the registered trade routes
+    Simulation {
+        probe_amount: Uint128,
+    },
+}
+
+// strategy contract decides target portfolio of the index.
+// triggered rebalances are started if any of the conditions is met
+#[cw_serde]
+pub struct StrategyPayload {
+    pub contract: String,
+    // seconds between triggered rebalances. zero disables the interval condition
+    pub interval: u64,
+    // max gap between current and target weight of a denom. disabled if not provided
+    pub drift_threshold: Option<Decimal>,
+    // twap only. simulation is open to price moves right before the trigger
+    pub price_source: RebalancePriceSource,
+    // manager of triggered rebalances
+    pub manager: Option<String>,
+}
+
+// paid to the executor of trades of a rebalance without manager
+#[cw_serde]
+pub enum KeeperRewardPayload {
+    // share of the trade output, paid in the output denom
+    TradeShare(Decimal),
+    // bounty per trade of the max trade amount, paid out of the collected streaming fee.
+    // scaled down for smaller trades. requires streaming fee in units mode
+    Bounty(Coin),
+}
+
 #[cw_serde]
 pub enum RebalanceMsg {
     Init {
@@ -75,11 +225,19 @@ pub enum RebalanceMsg {
         deflation: Vec<(String, Decimal)>, // target units
         inflation: Vec<(String, Decimal)>, // conversion weights
     },
+    InitByWeights {
+        manager: Option<String>,
+        targets: Vec<(String, Decimal)>, // target portfolio weights. must sum to one
+        price_source: RebalancePriceSource,
+    },
+    // permissionless. starts a rebalance with the targets of the strategy
+    Trigger {},
     Trade(RebalanceTradeMsg),
     Finalize {},
 }
 
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     Mint {
         amount: Uint128,
@@ -132,10 +290,72 @@ pub enum QueryMsg {
         amount: Uint128,
         funds: Option<Vec<Coin>>,
         time: Option<u64>,
+        // applies fee discount of the sender
+        sender: Option<String>,
     },
 
     #[returns(SimulateBurnResponse)]
-    SimulateBurn { amount: Uint128, time: Option<u64> },
+    SimulateBurn {
+        amount: Uint128,
+        time: Option<u64>,
+        // applies fee discount of the sender
+        sender: Option<String>,
+    },
+
+    #[returns(GetAccessPolicyResponse)]
+    GetAccessPolicy {},
+
+    #[returns(GetMetadataResponse)]
+    GetMetadata {},
+
+    #[returns(GetStrategyResponse)]
+    GetStrategy {},
+
+    #[returns(GetKeeperRewardResponse)]
+    GetKeeperReward { keeper: Option<String> },
+
+    #[returns(CheckAccessResponse)]
+    CheckAccess {
+        action: AccessAction,
+        address: String,
+    },
+
+    #[returns(ListAllowlistResponse)]
+    ListAllowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    },
+}
+
+/// Query interface that an external gate contract must implement
+/// to be used as an access policy of the index.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum GateQueryMsg {
+    #[returns(CheckAccessResponse)]
+    CheckAccess {
+        action: AccessAction,
+        address: String,
+    },
+}
+
+/// StrategyQueryMsg is an interface that should be implemented by any contract
+/// to be used as a rebalance strategy of the index.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum StrategyQueryMsg {
+    #[returns(GetTargetsResponse)]
+    GetTargets {
+        index_units: Vec<(String, Decimal)>,
+        reserve_denom: String,
+    },
+}
+
+#[cw_serde]
+pub struct GetTargetsResponse {
+    // target portfolio weights. must sum to one
+    pub targets: Vec<(String, Decimal)>,
 }
 
 #[cw_serde]
@@ -156,17 +376,68 @@ pub struct GetConfigResponse {
 #[cw_serde]
 pub struct StreamingFeeResponse {
     pub rate: Decimal,
+    // effective annualized rate of the rate per second
+    pub apr: Decimal,
+    pub mode: StreamingFeeMode,
     pub collected: Vec<Coin>,
+    // index tokens to be minted on realize
+    pub pending_mint: Uint128,
+    // collected fees distributed by the streaming split
+    pub accruals: Vec<(Addr, Vec<Coin>)>,
     pub freeze: bool,
     pub last_collected_at: u64,
 }
 
+#[cw_serde]
+pub struct PerformanceFeeResponse {
+    pub rate: Decimal,
+    pub price_sources: Vec<(String, u64)>,
+    pub twap_window: u64,
+    // nav per index unit in the reserve denom
+    pub high_water_mark: Decimal,
+    // crystallized on realize
+    pub collected: Vec<Coin>,
+    pub accruals: Vec<(Addr, Vec<Coin>)>,
+    pub freeze: bool,
+    pub last_collected_at: u64,
+}
+
+#[cw_serde]
+pub struct FeeSplitResponse {
+    pub mint: Vec<(Addr, Decimal)>,
+    pub burn: Vec<(Addr, Decimal)>,
+    pub streaming: Vec<(Addr, Decimal)>,
+    pub performance: Vec<(Addr, Decimal)>,
+}
+
+#[cw_serde]
+pub struct FeeScheduleResponse {
+    pub mint_tiers: Vec<FeeTier>,
+    pub burn_tiers: Vec<FeeTier>,
+    pub discounts: Vec<(Addr, Decimal)>,
+    pub min_fee: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct FeeGraceResponse {
+    // burn fee in effect during the grace window. none means no fee
+    pub burn_fee: Option<Decimal>,
+    pub burn_tiers: Vec<FeeTier>,
+    pub expires_at: u64,
+}
+
 #[cw_serde]
 pub struct GetFeeResponse {
     pub collector: Addr,
     pub mint_fee: Option<Decimal>,
     pub burn_fee: Option<Decimal>,
     pub streaming_fee: Option<StreamingFeeResponse>,
+    pub performance_fee: Option<PerformanceFeeResponse>,
+    pub splits: FeeSplitResponse,
+    pub schedule: FeeScheduleResponse,
+    pub grace_period: u64,
+    // active grace window at the given time
+    pub grace: Option<FeeGraceResponse>,
 }
 
 #[cw_serde]
@@ -195,6 +466,7 @@ pub struct TradeInfoPayload {
     pub routes: SwapRoutes,
     pub cooldown: u64,
     pub max_trade_amount: Uint128,
+    pub max_slippage: Option<Decimal>,
     pub last_traded_at: Option<u64>,
 }
 #[cw_serde]
@@ -208,6 +480,7 @@ pub struct ListTradeInfoResponse(pub Vec<TradeInfoPayload>);
 #[cw_serde]
 pub struct SimulateMintResponse {
     pub mint_amount: Uint128,
+    pub fee_amount: Uint128,
     pub refund_amount: Vec<Coin>,
     pub fund_spent: Vec<Coin>,
 }
@@ -215,9 +488,45 @@ pub struct SimulateMintResponse {
 #[cw_serde]
 pub struct SimulateBurnResponse {
     pub burn_amount: Uint128,
+    pub fee_amount: Uint128,
     pub redeem_amount: Vec<Coin>,
 }
 
+#[cw_serde]
+pub struct GetAccessPolicyResponse {
+    pub policy: Option<AccessPolicyPayload>,
+}
+
+#[cw_serde]
+pub struct GetMetadataResponse {
+    pub index_denom: String,
+    pub metadata: Option<IndexMetadataPayload>,
+}
+
+#[cw_serde]
+pub struct GetStrategyResponse {
+    pub strategy: Option<StrategyPayload>,
+    pub last_triggered_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct GetKeeperRewardResponse {
+    pub reward: Option<KeeperRewardPayload>,
+    // accumulated rewards paid to all keepers
+    pub total_paid: Vec<Coin>,
+    // accumulated rewards paid to the keeper. empty if not provided
+    pub keeper_paid: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct CheckAccessResponse {
+    pub allowed: bool,
+    pub reason: Option<String>,
+}
+
+#[cw_serde]
+pub struct ListAllowlistResponse(pub Vec<Addr>);
+
 #[cw_serde]
 pub struct MigrateMsg {
     pub force: Option<bool>,
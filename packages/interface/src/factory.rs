@@ -0,0 +1,107 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Coin};
+
+use crate::{core, types::RangeOrder};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    // defaults to the instantiator
+    pub gov: Option<String>,
+    pub core_code_id: u64,
+    // charged on every index creation and sent to the fee collector
+    pub creation_fee: Option<Coin>,
+    pub fee_collector: String,
+    // restricts constituents of new indexes. none allows any denom
+    pub allowed_denoms: Option<Vec<String>>,
+}
+
+#[cw_serde]
+pub enum GovMsg {
+    UpdateGov(String),
+    UpdateCoreCodeId(u64),
+    UpdateCreationFee {
+        fee: Option<Coin>,
+        collector: String,
+    },
+    UpdateAllowedDenoms(Option<Vec<String>>),
+}
+
+#[cw_serde]
+#[allow(clippy::large_enum_variant)]
+pub enum ExecuteMsg {
+    // funds beyond the creation fee are forwarded to the new core (e.g. for denom creation fee)
+    CreateIndex {
+        label: String,
+        // wasm admin of the new core
+        admin: Option<String>,
+        msg: core::InstantiateMsg,
+    },
+
+    Gov(GovMsg),
+}
+
+impl From<GovMsg> for ExecuteMsg {
+    fn from(v: GovMsg) -> Self {
+        Self::Gov(v)
+    }
+}
+
+#[cw_serde]
+pub enum IndexKey {
+    Id(u64),
+    Address(String),
+    Denom(String),
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(GetConfigResponse)]
+    GetConfig {},
+
+    #[returns(GetIndexResponse)]
+    GetIndex { key: IndexKey },
+
+    #[returns(ListIndexesResponse)]
+    ListIndexes {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    },
+
+    #[returns(ListIndexesResponse)]
+    ListIndexesByCreator {
+        creator: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        order: Option<RangeOrder>,
+    },
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    pub force: Option<bool>,
+}
+
+#[cw_serde]
+pub struct GetConfigResponse {
+    pub gov: Addr,
+    pub core_code_id: u64,
+    pub creation_fee: Option<Coin>,
+    pub fee_collector: Addr,
+    pub allowed_denoms: Option<Vec<String>>,
+    pub latest_index_id: u64,
+}
+
+#[cw_serde]
+pub struct GetIndexResponse {
+    pub id: u64,
+    pub address: Addr,
+    pub index_denom: String,
+    pub creator: Addr,
+    pub label: String,
+    pub created_at: u64,
+}
+
+#[cw_serde]
+pub struct ListIndexesResponse(pub Vec<GetIndexResponse>);
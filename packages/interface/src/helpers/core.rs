@@ -5,8 +5,8 @@ use cosmwasm_std::{
 };
 
 use crate::core::{
-    ExecuteMsg, GetConfigResponse, GetFeeResponse, GetPortfolioResponse, QueryMsg,
-    SimulateBurnResponse, SimulateMintResponse,
+    AccessAction, CheckAccessResponse, ExecuteMsg, GetConfigResponse, GetFeeResponse,
+    GetPortfolioResponse, QueryMsg, SimulateBurnResponse, SimulateMintResponse, ACCESS_POLICY_KEY,
 };
 
 /// IbcCore is a wrapper around Addr that provides a lot of helpers
@@ -106,6 +106,7 @@ impl IbcCore {
         amount: Uint128,
         funds: Option<Vec<Coin>>,
         time: Option<u64>,
+        sender: Option<String>,
     ) -> StdResult<SimulateMintResponse>
     where
         CQ: CustomQuery,
@@ -114,6 +115,7 @@ impl IbcCore {
             amount,
             funds,
             time,
+            sender,
         };
 
         querier.query(
@@ -130,11 +132,50 @@ impl IbcCore {
         querier: &QuerierWrapper<CQ>,
         amount: Uint128,
         time: Option<u64>,
+        sender: Option<String>,
     ) -> StdResult<SimulateBurnResponse>
     where
         CQ: CustomQuery,
     {
-        let msg = QueryMsg::SimulateBurn { amount, time };
+        let msg = QueryMsg::SimulateBurn {
+            amount,
+            time,
+            sender,
+        };
+
+        querier.query(
+            &WasmQuery::Smart {
+                contract_addr: self.addr().into(),
+                msg: to_json_binary(&msg)?,
+            }
+            .into(),
+        )
+    }
+
+    /// returns whether the core gates mint / burn with an access policy.
+    /// reads raw storage, so cores without the access policy feature report false
+    pub fn has_access_policy<CQ>(&self, querier: &QuerierWrapper<CQ>) -> StdResult<bool>
+    where
+        CQ: CustomQuery,
+    {
+        let policy = querier.query_wasm_raw(self.addr(), ACCESS_POLICY_KEY.as_bytes())?;
+
+        Ok(policy.is_some())
+    }
+
+    pub fn check_access<CQ>(
+        &self,
+        querier: &QuerierWrapper<CQ>,
+        action: AccessAction,
+        address: impl Into<String>,
+    ) -> StdResult<CheckAccessResponse>
+    where
+        CQ: CustomQuery,
+    {
+        let msg = QueryMsg::CheckAccess {
+            action,
+            address: address.into(),
+        };
 
         querier.query(
             &WasmQuery::Smart {
@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{to_json_binary, Addr, Coin, CosmosMsg, StdResult, WasmMsg};
 
-use crate::core::ExecuteMsg;
+use crate::periphery::ExecuteMsg;
 
 /// IbcPeriphery is a wrapper around Addr that provides a lot of helpers
 /// for working with this.
@@ -1,12 +1,25 @@
 use std::collections::HashSet;
 
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Coin, Uint128};
+use cosmwasm_std::{Addr, Coin, Uint128};
 
 use crate::types::{SwapRoute, SwapRoutes};
 
 #[cw_serde]
-pub struct InstantiateMsg {}
+#[derive(Default)]
+pub struct InstantiateMsg {
+    // defaults to the instantiator
+    pub gov: Option<String>,
+    // upper bound of referral fee. zero disables referral fee
+    pub max_referral_fee_bps: u16,
+}
+
+// fee paid to the integrator, deducted from the input asset of each operation
+#[cw_serde]
+pub struct Referral {
+    pub address: String,
+    pub fee_bps: u16,
+}
 
 #[cw_serde]
 pub struct RouteKey(pub (String, String));
@@ -98,6 +111,9 @@ pub enum ExecuteMsg {
         input_asset: String,
         min_output_amount: Uint128,
         swap_info: SwapInfosCompact,
+        referral: Option<Referral>,
+        // receives minted index and refunds. defaults to the sender
+        receiver: Option<String>,
     },
     // max input
     // fixed output
@@ -106,6 +122,7 @@ pub enum ExecuteMsg {
         output_amount: Uint128,
         input_asset: String,
         swap_info: SwapInfosCompact,
+        referral: Option<Referral>,
     },
     // fixed input
     // min output
@@ -114,6 +131,7 @@ pub enum ExecuteMsg {
         output_asset: String,
         min_output_amount: Uint128,
         swap_info: SwapInfosCompact,
+        referral: Option<Referral>,
     },
     // max input
     // fixed output
@@ -121,6 +139,7 @@ pub enum ExecuteMsg {
         core_addr: String,
         output_asset: Coin,
         swap_info: SwapInfosCompact,
+        referral: Option<Referral>,
     },
 
     // internal
@@ -128,6 +147,12 @@ pub enum ExecuteMsg {
         refund_to: String,
         refund_asset: String,
     },
+
+    // gov
+    UpdateConfig {
+        gov: Option<String>,
+        max_referral_fee_bps: Option<u16>,
+    },
 }
 
 #[cw_serde]
@@ -138,6 +163,8 @@ pub enum QueryMsg {
         core_addr: String,
         input_asset: Coin,
         swap_info: SwapInfosCompact,
+        sender: Option<String>,
+        referral: Option<Referral>,
     },
 
     #[returns(SimulateMintExactAmountOutResponse)]
@@ -146,6 +173,8 @@ pub enum QueryMsg {
         output_amount: Uint128,
         input_asset: String,
         swap_info: SwapInfosCompact,
+        sender: Option<String>,
+        referral: Option<Referral>,
     },
 
     #[returns(SimulateBurnExactAmountInResponse)]
@@ -154,6 +183,8 @@ pub enum QueryMsg {
         input_amount: Uint128,
         output_asset: String,
         swap_info: SwapInfosCompact,
+        sender: Option<String>,
+        referral: Option<Referral>,
     },
 
     #[returns(SimulateBurnExactAmountOutResponse)]
@@ -161,7 +192,12 @@ pub enum QueryMsg {
         core_addr: String,
         output_asset: Coin,
         swap_info: SwapInfosCompact,
+        sender: Option<String>,
+        referral: Option<Referral>,
     },
+
+    #[returns(GetConfigResponse)]
+    GetConfig {},
 }
 
 #[cw_serde]
@@ -169,6 +205,8 @@ pub struct SimulateMintExactAmountInResponse {
     pub mint_amount: Uint128,
     pub mint_spend_amount: Vec<Coin>,
     pub swap_result_amount: Coin,
+    // deducted from the input asset before swap
+    pub referral_fee: Option<Coin>,
 }
 
 #[cw_serde]
@@ -176,6 +214,8 @@ pub struct SimulateMintExactAmountOutResponse {
     pub mint_amount: Uint128,
     pub mint_spend_amount: Vec<Coin>,
     pub swap_result_amount: Coin,
+    // paid in addition to swap_result_amount
+    pub referral_fee: Option<Coin>,
 }
 
 #[cw_serde]
@@ -183,6 +223,8 @@ pub struct SimulateBurnExactAmountInResponse {
     pub burn_amount: Uint128,
     pub burn_redeem_amount: Vec<Coin>,
     pub swap_result_amount: Coin,
+    // deducted from the input amount before burn
+    pub referral_fee: Option<Coin>,
 }
 
 #[cw_serde]
@@ -190,11 +232,21 @@ pub struct SimulateBurnExactAmountOutResponse {
     pub burn_amount: Uint128,
     pub burn_redeem_amount: Vec<Coin>,
     pub swap_result_amount: Coin,
+    // paid in addition to burn_amount
+    pub referral_fee: Option<Coin>,
+}
+
+#[cw_serde]
+pub struct GetConfigResponse {
+    pub gov: Addr,
+    pub max_referral_fee_bps: u16,
 }
 
 #[cw_serde]
 pub struct MigrateMsg {
     pub force: Option<bool>,
+    // sets gov for deployments made before config was introduced
+    pub gov: Option<String>,
 }
 
 #[cfg(test)]
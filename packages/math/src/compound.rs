@@ -0,0 +1,281 @@
+use cosmwasm_std::{Decimal256, Uint256};
+
+use crate::error::MathError;
+
+pub const SECONDS_PER_YEAR: u64 = 86400 * 365;
+
+/// Calculates `(1 + rate)^periods` using exponentiation by squaring.
+/// Takes at most 64 squarings and returns an error instead of overflowing
+pub fn compound(rate: Decimal256, periods: u64) -> Result<Decimal256, MathError> {
+    let mut base = Decimal256::one().checked_add(rate)?;
+    let mut result = Decimal256::one();
+    let mut exp = periods;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.checked_mul(base)?;
+        }
+
+        exp >>= 1;
+
+        // skip the last squaring. it isn't used and may overflow
+        if exp > 0 {
+            base = base.checked_mul(base)?;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Calculates the fraction `(1 + rate)^periods - 1` of the principal taken by a fee
+/// compounding at `rate` for `periods`. Capped to the whole principal, so the result is in range of [0, 1]
+pub fn compound_fee_rate(rate: Decimal256, periods: u64) -> Result<Decimal256, MathError> {
+    let factor = match compound(rate, periods) {
+        Ok(factor) => factor,
+        // grows far beyond the whole principal
+        Err(MathError::OverflowError(_)) => return Ok(Decimal256::one()),
+        Err(e) => return Err(e),
+    };
+
+    Ok((factor - Decimal256::one()).min(Decimal256::one()))
+}
+
+/// Finds the largest rate per period that doesn't compound over `periods` beyond `1 + apr`.
+/// Bisects over atomics, so it takes at most a few hundred iterations even for huge `apr`
+pub fn annual_to_periodic_rate(apr: Decimal256, periods: u64) -> Result<Decimal256, MathError> {
+    if periods == 0 {
+        return Err(MathError::ZeroPeriods);
+    }
+
+    let target = Decimal256::one().checked_add(apr)?;
+
+    // (1 + r)^n >= 1 + n * r, so the rate never exceeds apr / n.
+    // doubled to leave room for truncation in compound
+    let mut lo = Uint256::zero();
+    let mut hi = apr
+        .checked_div(Decimal256::from_ratio(periods, 1u64))?
+        .atomics()
+        .saturating_mul(Uint256::from(2u64))
+        .saturating_add(Uint256::one());
+
+    while lo < hi {
+        let mid = lo + (hi - lo + Uint256::one()) / Uint256::from(2u64);
+
+        match compound(Decimal256::new(mid), periods) {
+            Ok(factor) if factor <= target => lo = mid,
+            Ok(_) | Err(MathError::OverflowError(_)) => hi = mid - Uint256::one(),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(Decimal256::new(lo))
+}
+
+/// Calculates the annualized rate `(1 + rate)^periods - 1` of a rate per period
+pub fn periodic_to_annual_rate(rate: Decimal256, periods: u64) -> Result<Decimal256, MathError> {
+    Ok(compound(rate, periods)? - Decimal256::one())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::str::FromStr;
+
+    use cosmwasm_std::{Decimal256, Uint256};
+
+    // deterministic xorshift64 to keep fuzz cases reproducible
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn rate(&mut self, max_atomics: u64) -> Decimal256 {
+            Decimal256::new(Uint256::from(self.next() % (max_atomics + 1)))
+        }
+    }
+
+    fn to_f64(v: Decimal256) -> f64 {
+        f64::from_str(&v.to_string()).unwrap()
+    }
+
+    fn naive(rate: Decimal256, periods: u64) -> Decimal256 {
+        let base = Decimal256::one() + rate;
+        (0..periods).fold(Decimal256::one(), |acc, _| acc * base)
+    }
+
+    #[test]
+    fn test_compound() -> anyhow::Result<()> {
+        let cases = [
+            ("0", 1_000_000, "1"),
+            ("0.5", 0, "1"),
+            ("0.5", 1, "1.5"),
+            ("0.5", 2, "2.25"),
+            ("1", 10, "1024"),
+            ("0.01", 10, "1.104622125411204"),
+        ];
+
+        for (rate, periods, expected) in cases {
+            let res = compound(Decimal256::from_str(rate)?, periods)?;
+            let diff = res.abs_diff(Decimal256::from_str(expected)?);
+            assert!(diff < Decimal256::from_str("0.000000000000001")?);
+        }
+
+        // 2^197 exceeds the range of Decimal256
+        assert!(compound(Decimal256::one(), 196).is_ok());
+        assert!(matches!(
+            compound(Decimal256::one(), 197),
+            Err(MathError::OverflowError(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compound_no_truncation() -> anyhow::Result<()> {
+        let rate = Decimal256::from_str("0.000000000001")?;
+        let periods = u32::MAX as u64 + 1_000_000;
+
+        let res = compound(rate, periods)?;
+        let truncated = compound(rate, periods as u32 as u64)?;
+        assert!(truncated < res);
+
+        let expected = (periods as f64 * 1e-12f64.ln_1p()).exp();
+        assert!((to_f64(res) - expected).abs() / expected < 1e-9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compound_fee_rate() -> anyhow::Result<()> {
+        let cases = [
+            ("0", 1_000_000, "0"),
+            ("0.5", 0, "0"),
+            ("0.5", 1, "0.5"),
+            ("0.01", 10, "0.104622125411204"),
+            ("1", 1, "1"),
+            // capped to the whole principal
+            ("0.5", 2, "1"),
+            // saturates instead of overflowing
+            ("1", 197, "1"),
+            ("0.000000021979553", u64::MAX, "1"),
+        ];
+
+        for (rate, periods, expected) in cases {
+            let res = compound_fee_rate(Decimal256::from_str(rate)?, periods)?;
+            let diff = res.abs_diff(Decimal256::from_str(expected)?);
+            assert!(diff < Decimal256::from_str("0.000000000000001")?);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fuzz_compound_against_naive() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..200 {
+            // up to 0.1% per period
+            let rate = rng.rate(1_000_000_000_000_000);
+            let periods = rng.next() % 2000;
+
+            let res = compound(rate, periods).unwrap();
+            let expected = naive(rate, periods);
+
+            // both truncate at 18 decimals, so allow a few ulps per multiplication
+            let tolerance = Decimal256::new(Uint256::from(4 * periods + 1));
+            assert!(
+                res.abs_diff(expected) <= tolerance,
+                "rate: {rate}, periods: {periods}, res: {res}, expected: {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fuzz_compound_against_reference() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..1000 {
+            // up to 100% APR per second, for up to 100 years
+            let rate = rng.rate(21_979_553_000);
+            let periods = rng.next() % (86400 * 365 * 100);
+
+            let expected = (periods as f64 * to_f64(rate).ln_1p()).exp();
+
+            match compound(rate, periods) {
+                Ok(res) => {
+                    let err = (to_f64(res) - expected).abs() / expected;
+                    assert!(
+                        err < 1e-6,
+                        "rate: {rate}, periods: {periods}, res: {res}, expected: {expected}"
+                    );
+                }
+                Err(MathError::OverflowError(_)) => assert!(expected > 1e58),
+                Err(e) => panic!("unexpected error: {e}"),
+            }
+
+            let fee_rate = compound_fee_rate(rate, periods).unwrap();
+            assert!(fee_rate <= Decimal256::one());
+
+            let expected = (expected - 1.0).min(1.0);
+            assert!(
+                (to_f64(fee_rate) - expected).abs() < 1e-6,
+                "rate: {rate}, periods: {periods}, fee_rate: {fee_rate}, expected: {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_annual_to_periodic_rate() -> anyhow::Result<()> {
+        let cases = [
+            ("0", 100, "0"),
+            ("1", 1, "1"),
+            ("3", 2, "1"),
+            ("0.21", 2, "0.1"),
+            // APR 100%
+            ("1", SECONDS_PER_YEAR, "0.000000021979553"),
+        ];
+
+        for (apr, periods, expected) in cases {
+            let rate = annual_to_periodic_rate(Decimal256::from_str(apr)?, periods)?;
+            let diff = rate.abs_diff(Decimal256::from_str(expected)?);
+            assert!(diff <= Decimal256::from_str("0.000000000000001")?);
+
+            // never compounds beyond the given apr
+            let annual = periodic_to_annual_rate(rate, periods)?;
+            assert!(annual <= Decimal256::from_str(apr)?);
+        }
+
+        assert_eq!(
+            annual_to_periodic_rate(Decimal256::one(), 0),
+            Err(MathError::ZeroPeriods)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fuzz_annual_rate_roundtrip() {
+        let mut rng = Rng(0xd1b5_4a32_d192_ed03);
+
+        for _ in 0..100 {
+            // up to 1000% APR
+            let apr = rng.rate(10_000_000_000_000_000_000);
+            let periods = 1 + rng.next() % SECONDS_PER_YEAR;
+
+            let rate = annual_to_periodic_rate(apr, periods).unwrap();
+            let annual = periodic_to_annual_rate(rate, periods).unwrap();
+            assert!(annual <= apr, "apr: {apr}, periods: {periods}");
+
+            // next atomic rate compounds beyond the apr
+            let next = rate + Decimal256::new(Uint256::one());
+            let annual = periodic_to_annual_rate(next, periods).unwrap();
+            assert!(annual > apr, "apr: {apr}, periods: {periods}");
+        }
+    }
+}
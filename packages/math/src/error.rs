@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq)]
 pub enum MathError {
     #[error("base must be greater than 0")]
     NegativeBase,
@@ -8,6 +8,15 @@ pub enum MathError {
     #[error("base must be less than two")]
     BaseTooLarge,
 
+    #[error("periods must be greater than 0")]
+    ZeroPeriods,
+
     #[error("{0}")]
     DecimalError(#[from] cosmwasm_std::Decimal256RangeExceeded),
+
+    #[error("{0}")]
+    OverflowError(#[from] cosmwasm_std::OverflowError),
+
+    #[error("{0}")]
+    CheckedFromRatioError(#[from] cosmwasm_std::CheckedFromRatioError),
 }
@@ -1,5 +1,9 @@
+mod compound;
 mod error;
 mod pow;
 
+pub use compound::{
+    annual_to_periodic_rate, compound, compound_fee_rate, periodic_to_annual_rate, SECONDS_PER_YEAR,
+};
 pub use error::MathError;
 pub use pow::{abs_diff_with_sign, pow};
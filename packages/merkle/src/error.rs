@@ -0,0 +1,34 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MerkleError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    FromHexError(#[from] hex::FromHexError),
+
+    #[error("{0}")]
+    Ecdsa(#[from] k256::ecdsa::Error),
+
+    #[error("{0}")]
+    Bech32(#[from] bech32::Error),
+
+    #[error("Tree must have at least one leaf")]
+    EmptyTree {},
+
+    #[error("Leaf index {index} out of range")]
+    LeafNotFound { index: usize },
+
+    #[error("Invalid amount {amount}: {reason}")]
+    InvalidAmount { amount: String, reason: String },
+
+    #[error("Invalid input at line {line}: {reason}")]
+    InvalidInput { line: usize, reason: String },
+
+    #[error("Duplicated address {address}")]
+    DuplicatedAddress { address: String },
+}
@@ -0,0 +1,222 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use cosmwasm_std::{Coin, Uint128};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::MerkleError;
+
+// amount of a leaf. multi-denom airdrops take a list of coins - 1000uatom,2000ukrw
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Amount {
+    Single(Uint128),
+    MultiDenom(Vec<Coin>),
+}
+
+impl Amount {
+    // sums up amounts of the same kind
+    pub fn checked_add(&self, other: &Amount) -> Result<Amount, MerkleError> {
+        let invalid = |reason: &str| MerkleError::InvalidAmount {
+            amount: other.to_string(),
+            reason: reason.to_string(),
+        };
+
+        match (self, other) {
+            (Amount::Single(a), Amount::Single(b)) => a
+                .checked_add(*b)
+                .map(Amount::Single)
+                .map_err(|_| invalid("overflow")),
+
+            (Amount::MultiDenom(a), Amount::MultiDenom(b)) => {
+                let mut sum: BTreeMap<&str, Uint128> = BTreeMap::new();
+                for coin in a.iter().chain(b) {
+                    let v = sum.entry(&coin.denom).or_default();
+                    *v = v
+                        .checked_add(coin.amount)
+                        .map_err(|_| invalid("overflow"))?;
+                }
+
+                Ok(Amount::MultiDenom(
+                    sum.into_iter()
+                        .map(|(denom, amount)| Coin::new(amount.u128(), denom))
+                        .collect(),
+                ))
+            }
+
+            _ => Err(invalid("must not mix single and multi denom amounts")),
+        }
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Amount::Single(amount) => write!(f, "{amount}"),
+            Amount::MultiDenom(coins) => {
+                let coins: Vec<_> = coins.iter().map(|c| c.to_string()).collect();
+                write!(f, "{}", coins.join(","))
+            }
+        }
+    }
+}
+
+impl FromStr for Amount {
+    type Err = MerkleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| MerkleError::InvalidAmount {
+            amount: s.to_string(),
+            reason: reason.to_string(),
+        };
+        let parse_uint = |v: &str| {
+            v.parse::<u128>()
+                .map(Uint128::new)
+                .map_err(|_| invalid("invalid number"))
+        };
+
+        let s = s.trim();
+        if s.chars().all(|c| c.is_ascii_digit()) {
+            let amount = parse_uint(s)?;
+            if amount.is_zero() {
+                return Err(invalid("must not be zero"));
+            }
+            return Ok(Amount::Single(amount));
+        }
+
+        let mut coins = s
+            .split(',')
+            .map(|v| {
+                let v = v.trim();
+                let pos = v
+                    .find(|c: char| !c.is_ascii_digit())
+                    .filter(|pos| *pos > 0)
+                    .ok_or_else(|| invalid("coin must be {amount}{denom}"))?;
+                let (amount, denom) = v.split_at(pos);
+
+                Ok(Coin::new(parse_uint(amount)?.u128(), denom))
+            })
+            .collect::<Result<Vec<_>, MerkleError>>()?;
+
+        // same normalization as the contract applies to claimed coins
+        coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+        if coins.iter().any(|c| c.amount.is_zero()) {
+            return Err(invalid("must not contain zero amount"));
+        }
+        if coins.windows(2).any(|w| w[0].denom == w[1].denom) {
+            return Err(invalid("must not contain duplicated denoms"));
+        }
+
+        Ok(Amount::MultiDenom(coins))
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+// address is the claim hash for bearer airdrops
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub address: String,
+    pub amount: Amount,
+}
+
+impl Entry {
+    pub fn new(address: impl Into<String>, amount: Amount) -> Self {
+        Self {
+            address: address.into(),
+            amount,
+        }
+    }
+
+    // {address}:{amount} or {address}:{amount}{denom},... (sorted by denom)
+    pub fn leaf(&self) -> String {
+        format!("{}:{}", self.address, self.amount)
+    }
+}
+
+// [{"address": "osmo1...", "amount": "1000"}, ...]
+pub fn parse_json(content: &str) -> Result<Vec<Entry>, MerkleError> {
+    Ok(serde_json::from_str(content)?)
+}
+
+// address,amount per line. header line is optional.
+// amount of multi-denom airdrop takes the rest of the line - osmo1...,1000uatom,2000ukrw
+pub fn parse_csv(content: &str) -> Result<Vec<Entry>, MerkleError> {
+    let mut entries = vec![];
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (i == 0 && line.starts_with("address")) {
+            continue;
+        }
+
+        let (address, amount) = line
+            .split_once(',')
+            .ok_or_else(|| MerkleError::InvalidInput {
+                line: i + 1,
+                reason: "must be address,amount".to_string(),
+            })?;
+
+        entries.push(Entry::new(
+            address.trim(),
+            amount.trim().trim_matches('"').parse()?,
+        ));
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, Uint128};
+
+    use super::{parse_csv, parse_json, Amount, Entry};
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(
+            "1000".parse::<Amount>().unwrap(),
+            Amount::Single(Uint128::new(1000))
+        );
+        assert_eq!(
+            "2000ukrw,1000uatom".parse::<Amount>().unwrap(),
+            Amount::MultiDenom(vec![coin(1000, "uatom"), coin(2000, "ukrw")])
+        );
+
+        for invalid in ["", "0", "uatom", "1000uatom,0ukrw", "1000uatom,1000uatom"] {
+            assert!(invalid.parse::<Amount>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let csv = "address,amount\nosmo1a,1000\n\nosmo1b,\"2000ukrw,1000uatom\"\n";
+        let json = r#"[
+            {"address": "osmo1a", "amount": "1000"},
+            {"address": "osmo1b", "amount": "2000ukrw,1000uatom"}
+        ]"#;
+
+        let expected = vec![
+            Entry::new("osmo1a", Amount::Single(Uint128::new(1000))),
+            Entry::new(
+                "osmo1b",
+                Amount::MultiDenom(vec![coin(1000, "uatom"), coin(2000, "ukrw")]),
+            ),
+        ];
+        assert_eq!(parse_csv(csv).unwrap(), expected);
+        assert_eq!(parse_json(json).unwrap(), expected);
+
+        assert_eq!(expected[1].leaf(), "osmo1b:1000uatom,2000ukrw");
+        assert!(parse_csv("osmo1a").is_err());
+    }
+}
@@ -0,0 +1,119 @@
+pub mod error;
+pub mod input;
+pub mod signer;
+pub mod tree;
+
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+
+pub use crate::error::MerkleError;
+pub use crate::input::{parse_csv, parse_json, Amount, Entry};
+pub use crate::signer::BearerSigner;
+pub use crate::tree::MerkleTree;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Claim {
+    pub address: String,
+    pub amount: Amount,
+    pub merkle_proof: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AirdropTree {
+    pub merkle_root: String,
+    pub total_amount: Amount,
+    pub claims: Vec<Claim>,
+}
+
+// builds the tree in the order of entries and collects the proof of every leaf
+pub fn build(entries: Vec<Entry>) -> Result<AirdropTree, MerkleError> {
+    let mut addresses = BTreeSet::new();
+    for entry in &entries {
+        if !addresses.insert(entry.address.as_str()) {
+            return Err(MerkleError::DuplicatedAddress {
+                address: entry.address.clone(),
+            });
+        }
+    }
+
+    let tree = MerkleTree::new(entries.iter().map(|v| v.leaf()))?;
+
+    let mut total_amount = entries[0].amount.clone();
+    for entry in &entries[1..] {
+        total_amount = total_amount.checked_add(&entry.amount)?;
+    }
+
+    let claims = entries
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            Ok(Claim {
+                address: entry.address,
+                amount: entry.amount,
+                merkle_proof: tree.proof(i)?,
+            })
+        })
+        .collect::<Result<_, MerkleError>>()?;
+
+    Ok(AirdropTree {
+        merkle_root: tree.root(),
+        total_amount,
+        claims,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::coin;
+    use ibcx_airdrop::verify::verify_multi_denom_merkle_proof;
+
+    use crate::{build, parse_csv, Amount, MerkleError};
+
+    #[test]
+    fn test_build_multi_denom() {
+        // fixture of multi-denom airdrop tests of the contract
+        let entries = parse_csv(
+            "osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a,1000uatom,2000ukrw
+            osmo1z7huy904a3yf3aj8mxt5z6shy7dezrlw5gduju,500ukrw
+            osmo1cyyzpxplxdzkeea7kwsydadg87357qnahakaks,300uatom
+            osmo1jv65s3grqf6v6jl3dp4t6c9t9rk99cd80yhvld,100uatom,100ukrw",
+        )
+        .unwrap();
+
+        let airdrop = build(entries).unwrap();
+        assert_eq!(
+            airdrop.merkle_root,
+            "556cf80af5076e913ad43047ce75b914ef549f75065cd09f739545af14899214"
+        );
+        assert_eq!(
+            airdrop.total_amount,
+            Amount::MultiDenom(vec![coin(1400, "uatom"), coin(2600, "ukrw")])
+        );
+
+        for claim in airdrop.claims {
+            let Amount::MultiDenom(coins) = &claim.amount else {
+                unreachable!()
+            };
+            verify_multi_denom_merkle_proof(
+                &airdrop.merkle_root,
+                claim.merkle_proof,
+                &claim.address,
+                coins,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_build_invalid() {
+        let err = build(parse_csv("osmo1a,1000\nosmo1a,2000").unwrap()).unwrap_err();
+        assert!(matches!(err, MerkleError::DuplicatedAddress { .. }));
+
+        let err = build(parse_csv("osmo1a,1000\nosmo1b,2000uatom").unwrap()).unwrap_err();
+        assert!(matches!(err, MerkleError::InvalidAmount { .. }));
+
+        let err = build(vec![]).unwrap_err();
+        assert!(matches!(err, MerkleError::EmptyTree {}));
+    }
+}
@@ -0,0 +1,85 @@
+use std::{env, fs, path::Path, process::exit};
+
+use cosmwasm_std::Uint128;
+use ibcx_merkle::{build, parse_csv, parse_json, BearerSigner, MerkleError};
+use serde_json::json;
+
+const USAGE: &str = "usage:
+  ibcx-merkle build <input.csv|input.json> [output.json]
+  ibcx-merkle signer <secret_hex> [prefix]
+  ibcx-merkle sign-claim <secret_hex> <claim_hash> <claimer> <amount>";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(|v| v.as_str()).collect();
+
+    let res = match args.as_slice() {
+        ["build", input] => run_build(input, None),
+        ["build", input, output] => run_build(input, Some(output)),
+        ["signer", secret] => run_signer(secret, "osmo"),
+        ["signer", secret, prefix] => run_signer(secret, prefix),
+        ["sign-claim", secret, claim_hash, claimer, amount] => {
+            run_sign_claim(secret, claim_hash, claimer, amount)
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            exit(1);
+        }
+    };
+
+    if let Err(err) = res {
+        eprintln!("error: {err}");
+        exit(1);
+    }
+}
+
+fn run_build(input: &str, output: Option<&str>) -> Result<(), MerkleError> {
+    let content = fs::read_to_string(input)?;
+    let entries = match Path::new(input).extension().and_then(|v| v.to_str()) {
+        Some("json") => parse_json(&content)?,
+        _ => parse_csv(&content)?,
+    };
+
+    let airdrop = serde_json::to_string_pretty(&build(entries)?)?;
+    match output {
+        Some(output) => fs::write(output, airdrop)?,
+        None => println!("{airdrop}"),
+    }
+
+    Ok(())
+}
+
+fn run_signer(secret: &str, prefix: &str) -> Result<(), MerkleError> {
+    let signer = BearerSigner::from_hex(secret)?;
+
+    let resp = json!({
+        "signer": signer.address(prefix)?,
+        "signer_pub": signer.pub_key(),
+        "signer_sig": signer.register_sig(prefix)?,
+    });
+    println!("{}", serde_json::to_string_pretty(&resp)?);
+
+    Ok(())
+}
+
+fn run_sign_claim(
+    secret: &str,
+    claim_hash: &str,
+    claimer: &str,
+    amount: &str,
+) -> Result<(), MerkleError> {
+    let amount = amount
+        .parse::<u128>()
+        .map_err(|_| MerkleError::InvalidAmount {
+            amount: amount.to_string(),
+            reason: "invalid number".to_string(),
+        })?;
+
+    let signer = BearerSigner::from_hex(secret)?;
+    println!(
+        "{}",
+        signer.claim_sig(claim_hash, claimer, Uint128::new(amount))?
+    );
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use bech32::ToBase32;
+use cosmwasm_std::Uint128;
+use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+use ripemd::{Digest, Ripemd160};
+
+use crate::{error::MerkleError, tree::sha256};
+
+// signer of bearer airdrops. claimers must present its signature to claim
+pub struct BearerSigner {
+    key: SigningKey,
+}
+
+impl BearerSigner {
+    pub fn from_hex(secret: &str) -> Result<Self, MerkleError> {
+        let key = SigningKey::from_slice(&hex::decode(secret)?)?;
+
+        Ok(Self { key })
+    }
+
+    // compressed public key - signer_pub of RegisterPayload::Bearer
+    pub fn pub_key(&self) -> String {
+        hex::encode(self.key.verifying_key().to_sec1_bytes())
+    }
+
+    pub fn address(&self, prefix: &str) -> Result<String, MerkleError> {
+        let pub_key = self.key.verifying_key().to_sec1_bytes();
+        let hash = Ripemd160::digest(sha256(pub_key));
+
+        Ok(bech32::encode(
+            prefix,
+            hash.to_base32(),
+            bech32::Variant::Bech32,
+        )?)
+    }
+
+    // signer_sig of RegisterPayload::Bearer - sign(sha256({signer}))
+    pub fn register_sig(&self, prefix: &str) -> Result<String, MerkleError> {
+        self.sign(self.address(prefix)?)
+    }
+
+    // claim_sign of ClaimPayload::Bearer - sign(sha256({claim_hash}/{claimer}/{amount}))
+    pub fn claim_sig(
+        &self,
+        claim_hash: &str,
+        claimer: &str,
+        amount: Uint128,
+    ) -> Result<String, MerkleError> {
+        self.sign(format!("{claim_hash}/{claimer}/{amount}"))
+    }
+
+    fn sign(&self, msg: impl AsRef<[u8]>) -> Result<String, MerkleError> {
+        let sig: Signature = self.key.sign_prehash(&sha256(msg))?;
+        let sig = sig.normalize_s().unwrap_or(sig);
+
+        Ok(hex::encode(sig.to_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{testing::MockApi, Api, Uint128};
+    use ibcx_airdrop::verify::{pub_to_addr, sha256_digest};
+
+    use super::BearerSigner;
+
+    const SECRET: &str = "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+
+    #[test]
+    fn test_signatures_verified_by_contract() {
+        let api = MockApi::default();
+        let signer = BearerSigner::from_hex(SECRET).unwrap();
+        let pub_key = hex::decode(signer.pub_key()).unwrap();
+
+        // same key as claim_open_on_behalf test of the contract
+        let address = signer.address("osmo").unwrap();
+        assert_eq!(address, "osmo15wgtkntdf26hqan77g0kdsldcxjddypx5ny5a6");
+        assert_eq!(
+            pub_to_addr(pub_key.clone().into(), "osmo").unwrap(),
+            address
+        );
+
+        let register_sig = hex::decode(signer.register_sig("osmo").unwrap()).unwrap();
+        assert!(api
+            .secp256k1_verify(
+                &sha256_digest(address.as_bytes()).unwrap(),
+                &register_sig,
+                &pub_key,
+            )
+            .unwrap());
+
+        let claim_hash = "c2ae4a162c574a85c596cfda7c057c5aa15c7b4d6a5679f44fb365d76e63e24e";
+        let claimer = "osmo1phaxpevm5wecex2jyaqty2a4v02qj7qmlmzk5a";
+        let claim_sig = signer
+            .claim_sig(claim_hash, claimer, Uint128::new(10000))
+            .unwrap();
+        assert!(api
+            .secp256k1_verify(
+                &sha256_digest(format!("{claim_hash}/{claimer}/10000")).unwrap(),
+                &hex::decode(claim_sig).unwrap(),
+                &pub_key,
+            )
+            .unwrap());
+
+        assert!(BearerSigner::from_hex("00").is_err());
+    }
+}
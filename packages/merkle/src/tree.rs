@@ -0,0 +1,138 @@
+use sha2::{Digest, Sha256};
+
+use crate::error::MerkleError;
+
+pub type Hash = [u8; 32];
+
+pub fn sha256(bz: impl AsRef<[u8]>) -> Hash {
+    Sha256::digest(bz).into()
+}
+
+// sorted-pair hashing, same as verify::verify_merkle_proof of the airdrop contract
+fn hash_pair(a: &Hash, b: &Hash) -> Hash {
+    let mut pair = [*a, *b];
+    pair.sort_unstable();
+
+    sha256(pair.concat())
+}
+
+// layers[0] holds the leaf hashes and the last layer holds the root.
+// a node without sibling is carried up to the next layer as is
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    layers: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    // leaves are hashed with sha256 before being placed in the tree
+    pub fn new<I, T>(leaves: I) -> Result<Self, MerkleError>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        let leaves: Vec<Hash> = leaves.into_iter().map(sha256).collect();
+        if leaves.is_empty() {
+            return Err(MerkleError::EmptyTree {});
+        }
+
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|v| match v {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+
+        Ok(Self { layers })
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers[0].is_empty()
+    }
+
+    pub fn root(&self) -> String {
+        hex::encode(self.layers.last().unwrap()[0])
+    }
+
+    // sibling hashes from the leaf up to the root
+    pub fn proof(&self, index: usize) -> Result<Vec<String>, MerkleError> {
+        if index >= self.len() {
+            return Err(MerkleError::LeafNotFound { index });
+        }
+
+        let mut index = index;
+        let mut proof = vec![];
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(hex::encode(sibling));
+            }
+            index /= 2;
+        }
+
+        Ok(proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Uint128;
+    use ibcx_airdrop::{error::ContractError, verify::verify_merkle_proof};
+
+    use super::MerkleTree;
+
+    #[test]
+    fn test_empty_tree() {
+        assert!(MerkleTree::new(Vec::<String>::new()).is_err());
+    }
+
+    #[test]
+    fn test_proofs_verified_by_contract() {
+        for size in 1..=17u128 {
+            let accounts: Vec<_> = (0..size).map(|i| (format!("account{i}"), i + 1)).collect();
+
+            let tree = MerkleTree::new(accounts.iter().map(|(a, v)| format!("{a}:{v}"))).unwrap();
+            assert_eq!(tree.len(), size as usize);
+
+            for (i, (account, amount)) in accounts.iter().enumerate() {
+                let proof = tree.proof(i).unwrap();
+
+                verify_merkle_proof(&tree.root(), proof.clone(), account, Uint128::new(*amount))
+                    .unwrap();
+
+                // proof doesn't cover other amounts
+                let err =
+                    verify_merkle_proof(&tree.root(), proof, account, Uint128::new(amount + 1))
+                        .unwrap_err();
+                assert_eq!(err, ContractError::InvalidProof {});
+            }
+
+            assert!(tree.proof(size as usize).is_err());
+        }
+    }
+
+    #[test]
+    fn test_root_of_contract_fixture() {
+        // fixture of claim_open_on_behalf test
+        let tree = MerkleTree::new([
+            "osmo15wgtkntdf26hqan77g0kdsldcxjddypx5ny5a6:1000",
+            "osmo1z7huy904a3yf3aj8mxt5z6shy7dezrlw5gduju:500",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            tree.root(),
+            "806b77d35677d481ca65bc86308424032adb08c870cba01e62390470610901c3"
+        );
+    }
+}